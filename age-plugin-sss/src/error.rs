@@ -0,0 +1,29 @@
+use std::io;
+use std::path::PathBuf;
+use thiserror::Error;
+
+/// Everything that makes `age-plugin-sss` fail, either on the command line
+/// or as an `error` reported back to age.
+#[derive(Debug, Error)]
+pub enum PluginError {
+    #[error("{}: {source}", path.display())]
+    File { path: PathBuf, source: io::Error },
+    #[error("{0}")]
+    Io(#[from] io::Error),
+    #[error("{0}")]
+    Usage(String),
+    #[error("invalid age-plugin-sss identity: {0}")]
+    InvalidIdentity(String),
+    #[error("no shares given")]
+    NoShares,
+    #[error("shares of {0} disagree on the threshold")]
+    ThresholdMismatch(String),
+    #[error("need {needed} shares of {recipient}, got {got}")]
+    NotEnoughShares {
+        recipient: String,
+        needed: usize,
+        got: usize,
+    },
+    #[error("shares of {0} do not reconstruct its identity; one is corrupt or from another split")]
+    WrongIdentity(String),
+}
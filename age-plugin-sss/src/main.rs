@@ -0,0 +1,60 @@
+// main.rs
+//
+// `age-plugin-sss`: an age plugin whose identities are Shamir shares of an
+// X25519 identity. `split` hands each custodian an identity file; age runs
+// the plugin with `--age-plugin=identity-v1` when those files are passed to
+// `age -d -i`, and the identity is rebuilt in memory once enough are given.
+
+use clap::{CommandFactory, Parser, Subcommand};
+use std::process::ExitCode;
+
+mod error;
+mod plugin;
+mod share;
+mod split;
+
+use error::PluginError;
+
+#[derive(Parser)]
+#[command(
+    name = "age-plugin-sss",
+    version,
+    about = "age plugin for identities split with Shamir's Secret Sharing"
+)]
+struct Cli {
+    /// State machine to run; set by age when it starts the plugin
+    #[arg(long, hide = true)]
+    age_plugin: Option<String>,
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Split an age identity into share identity files
+    Split(split::SplitArgs),
+}
+
+fn run(cli: Cli) -> Result<(), PluginError> {
+    match (cli.age_plugin.as_deref(), cli.command) {
+        (Some(age_core::plugin::IDENTITY_V1), None) => Ok(plugin::run_identity_v1()?),
+        (Some(machine), None) => Err(PluginError::Usage(format!(
+            "unsupported state machine {machine}; encrypt to the age1… recipient printed by `split`"
+        ))),
+        (None, Some(Command::Split(args))) => split::run(args),
+        _ => {
+            Cli::command().print_help()?;
+            Err(PluginError::Usage("expected a subcommand".into()))
+        }
+    }
+}
+
+fn main() -> ExitCode {
+    match run(Cli::parse()) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("age-plugin-sss: error: {e}");
+            ExitCode::FAILURE
+        }
+    }
+}
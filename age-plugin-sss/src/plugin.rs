@@ -0,0 +1,119 @@
+// plugin.rs
+//
+// The identity-v1 state machine (https://c2sp.org/age-plugin). age hands over
+// every `AGE-PLUGIN-SSS-1…` identity it was given, plus every stanza in the
+// file header. Identities are grouped by the recipient they were split from.
+// Each group that reaches its threshold is reconstructed in memory, and the
+// resulting X25519 identity unwraps the file key from the native `X25519`
+// stanzas. Nothing is written to disk, and the identity is dropped (and
+// zeroized by age) before the plugin exits.
+
+use crate::share::{ShareIdentity, combine};
+use age::Identity;
+use age::secrecy::ExposeSecret;
+use age_core::format::{FileKey, Stanza};
+use age_core::plugin::Connection;
+use std::io;
+
+/// Metadata and message of an `error` command for age.
+type Report = (Vec<String>, String);
+
+fn internal(message: String) -> Report {
+    (vec!["internal".into()], message)
+}
+
+/// Find the file key, or say why not.
+fn unwrap(
+    identities: Vec<Result<ShareIdentity, String>>,
+    stanzas: &[Stanza],
+) -> Result<FileKey, Vec<Report>> {
+    let mut errors = Vec::new();
+    let mut groups: Vec<Vec<(usize, ShareIdentity)>> = Vec::new();
+    for (index, identity) in identities.into_iter().enumerate() {
+        match identity {
+            Ok(identity) => match groups
+                .iter_mut()
+                .find(|g| g[0].1.recipient == identity.recipient)
+            {
+                Some(group) => group.push((index, identity)),
+                None => groups.push(vec![(index, identity)]),
+            },
+            Err(message) => errors.push((vec!["identity".into(), index.to_string()], message)),
+        }
+    }
+
+    for group in &groups {
+        let shares: Vec<&ShareIdentity> = group.iter().map(|(_, s)| s).collect();
+        match combine(&shares) {
+            Ok(identity) => match identity.unwrap_stanzas(stanzas) {
+                Some(Ok(file_key)) => return Ok(file_key),
+                Some(Err(e)) => errors.push(internal(e.to_string())),
+                None => {}
+            },
+            Err(e) => errors.push((
+                vec!["identity".into(), group[0].0.to_string()],
+                e.to_string(),
+            )),
+        }
+    }
+    Err(errors)
+}
+
+pub fn run_identity_v1() -> io::Result<()> {
+    let mut conn = Connection::accept();
+
+    // Phase 1: collect identities and the stanzas of file 0.
+    let (identities, stanzas, _, _) = conn.unidir_receive(
+        ("add-identity", |s: Stanza| match s.args.as_slice() {
+            [identity] => Ok(ShareIdentity::decode(identity).map_err(|e| e.to_string())),
+            _ => Err("malformed add-identity".to_string()),
+        }),
+        ("recipient-stanza", |s: Stanza| match s.args.as_slice() {
+            [file, tag, args @ ..] => Ok((
+                file.clone(),
+                Stanza {
+                    tag: tag.clone(),
+                    args: args.to_vec(),
+                    body: s.body,
+                },
+            )),
+            _ => Err("malformed recipient-stanza".to_string()),
+        }),
+        (None, |_: Stanza| Ok::<(), String>(())),
+        (None, |_: Stanza| Ok::<(), String>(())),
+    )?;
+
+    let outcome = match (identities, stanzas) {
+        (Ok(identities), Ok(stanzas)) => {
+            let stanzas: Vec<Stanza> = stanzas
+                .into_iter()
+                .filter(|(file, _)| file == "0")
+                .map(|(_, stanza)| stanza)
+                .collect();
+            unwrap(identities, &stanzas)
+        }
+        (identities, stanzas) => Err(identities
+            .err()
+            .into_iter()
+            .chain(stanzas.err())
+            .flatten()
+            .map(internal)
+            .collect()),
+    };
+
+    // Phase 2: hand back the file key, or the reasons there is none.
+    conn.bidir_send(|mut phase| {
+        match &outcome {
+            Ok(file_key) => {
+                let _ = phase.send("file-key", &["0"], file_key.expose_secret())?;
+            }
+            Err(errors) => {
+                for (metadata, message) in errors {
+                    let metadata: Vec<&str> = metadata.iter().map(String::as_str).collect();
+                    let _ = phase.send("error", &metadata, message.as_bytes())?;
+                }
+            }
+        }
+        Ok(())
+    })
+}
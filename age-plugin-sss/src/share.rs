@@ -0,0 +1,150 @@
+// share.rs
+//
+// A plugin identity carries one share of an X25519 identity's secret scalar,
+// plus what is needed to use it without the other custodians' files:
+//
+//   threshold (1) | x (1) | recipient public key (32) | y (32)
+//
+// Bech32-encoded under `AGE-PLUGIN-SSS-`, as age expects of plugin
+// identities. The public key is not secret; it groups the shares of one
+// identity and confirms that a reconstruction is the right one.
+
+use crate::error::PluginError;
+use age::secrecy::ExposeSecret;
+use bech32::{FromBase32, ToBase32, Variant, u5};
+use shamir_gf256::{GF256, Share, reconstruct, split};
+use std::ptr;
+use std::sync::atomic::{Ordering, compiler_fence};
+use zeroize::Zeroizing;
+
+const IDENTITY_HRP: &str = "age-plugin-sss-";
+const SECRET_KEY_HRP: &str = "age-secret-key-";
+const RECIPIENT_HRP: &str = "age";
+const KEY_LEN: usize = 32;
+const PAYLOAD_LEN: usize = 2 + 2 * KEY_LEN;
+
+/// One custodian's part of a split X25519 identity.
+pub struct ShareIdentity {
+    pub threshold: u8,
+    pub recipient: [u8; KEY_LEN],
+    pub share: Share,
+}
+
+/// `u5` has no `Zeroize` impl; overwrite the digits with volatile writes so
+/// the compiler cannot drop the stores as dead before the vector is freed.
+fn wipe(data: &mut [u5]) {
+    let zero = u5::try_from_u8(0).expect("0 is a valid u5");
+    for digit in data.iter_mut() {
+        // SAFETY: `digit` is a valid, aligned, exclusive reference.
+        unsafe { ptr::write_volatile(digit, zero) };
+    }
+    compiler_fence(Ordering::SeqCst);
+}
+
+fn from_bech32(text: &str, hrp: &str) -> Option<Zeroizing<Vec<u8>>> {
+    let (found, mut data, variant) = bech32::decode(text).ok()?;
+    let bytes = (found == hrp && variant == Variant::Bech32)
+        .then(|| Vec::<u8>::from_base32(&data).ok().map(Zeroizing::new))
+        .flatten();
+    wipe(&mut data);
+    bytes
+}
+
+fn to_bech32(hrp: &str, bytes: &[u8]) -> Zeroizing<String> {
+    let mut data = bytes.to_base32();
+    let encoded = bech32::encode(hrp, &data, Variant::Bech32).expect("HRP is valid");
+    wipe(&mut data);
+    Zeroizing::new(encoded)
+}
+
+/// The `age1…` recipient for a raw public key.
+pub fn recipient_string(public: &[u8; KEY_LEN]) -> String {
+    to_bech32(RECIPIENT_HRP, public).to_string()
+}
+
+impl ShareIdentity {
+    /// The `AGE-PLUGIN-SSS-1…` identity string.
+    pub fn encode(&self) -> Zeroizing<String> {
+        let mut payload = Zeroizing::new(Vec::with_capacity(PAYLOAD_LEN));
+        payload.extend_from_slice(&[self.threshold, self.share.x.0]);
+        payload.extend_from_slice(&self.recipient);
+        payload.extend_from_slice(&self.share.y);
+        Zeroizing::new(to_bech32(IDENTITY_HRP, &payload).to_uppercase())
+    }
+
+    pub fn decode(text: &str) -> Result<Self, PluginError> {
+        let invalid = |reason: &str| PluginError::InvalidIdentity(reason.to_string());
+        let payload = from_bech32(text, IDENTITY_HRP).ok_or_else(|| invalid("bad Bech32"))?;
+        if payload.len() != PAYLOAD_LEN {
+            return Err(invalid("wrong length"));
+        }
+        let (threshold, x) = (payload[0], payload[1]);
+        if threshold == 0 || x == 0 {
+            return Err(invalid("threshold and share number must be non-zero"));
+        }
+        Ok(ShareIdentity {
+            threshold,
+            recipient: payload[2..2 + KEY_LEN].try_into().unwrap(),
+            share: Share {
+                x: GF256(x),
+                y: payload[2 + KEY_LEN..].to_vec(),
+            },
+        })
+    }
+}
+
+/// Split `identity` into `count` share identities, any `threshold` of which
+/// reconstruct it.
+pub fn split_identity(
+    identity: &age::x25519::Identity,
+    threshold: u8,
+    count: u8,
+) -> Vec<ShareIdentity> {
+    let encoded = Zeroizing::new(identity.to_string().expose_secret().to_lowercase());
+    let secret = from_bech32(&encoded, SECRET_KEY_HRP).expect("age encodes identities in Bech32");
+    let public = from_bech32(&identity.to_public().to_string(), RECIPIENT_HRP)
+        .expect("age encodes recipients in Bech32");
+    let recipient: [u8; KEY_LEN] = public[..].try_into().expect("X25519 keys are 32 bytes");
+    split(&secret, threshold.into(), count.into())
+        .into_iter()
+        .map(|share| ShareIdentity {
+            threshold,
+            recipient,
+            share,
+        })
+        .collect()
+}
+
+/// Reconstruct the identity behind `shares`, which must all belong to one
+/// recipient. Repeated share numbers count once.
+pub fn combine(shares: &[&ShareIdentity]) -> Result<age::x25519::Identity, PluginError> {
+    let first = shares.first().ok_or(PluginError::NoShares)?;
+    let recipient = recipient_string(&first.recipient);
+    if shares.iter().any(|s| s.threshold != first.threshold) {
+        return Err(PluginError::ThresholdMismatch(recipient));
+    }
+    let mut distinct: Vec<Share> = Vec::with_capacity(shares.len());
+    for s in shares {
+        if !distinct.iter().any(|d| d.x == s.share.x) {
+            distinct.push(s.share.clone());
+        }
+    }
+    let needed = usize::from(first.threshold);
+    if distinct.len() < needed {
+        return Err(PluginError::NotEnoughShares {
+            recipient,
+            needed,
+            got: distinct.len(),
+        });
+    }
+
+    let secret = Zeroizing::new(reconstruct(&distinct, needed));
+    let encoded = Zeroizing::new(to_bech32(SECRET_KEY_HRP, &secret).to_uppercase());
+    let identity: age::x25519::Identity = encoded
+        .parse()
+        .map_err(|_| PluginError::WrongIdentity(recipient.clone()))?;
+    if identity.to_public().to_string() != recipient {
+        return Err(PluginError::WrongIdentity(recipient));
+    }
+    Ok(identity)
+}
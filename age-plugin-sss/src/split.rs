@@ -0,0 +1,96 @@
+// split.rs
+//
+// `age-plugin-sss split`: turn an age X25519 identity (or a freshly generated
+// one) into share identity files, one per custodian. Files keep being
+// encrypted to the printed `age1…` recipient as before; decrypting needs any
+// `threshold` of the share files passed to age with `-i`.
+
+use crate::error::PluginError;
+use crate::share::{recipient_string, split_identity};
+use clap::Args;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::PathBuf;
+
+#[derive(Args)]
+pub struct SplitArgs {
+    /// age identity file to split; a new identity is generated if omitted
+    pub identity: Option<PathBuf>,
+    /// Shares needed to decrypt
+    #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..))]
+    pub threshold: u8,
+    /// Shares to create
+    #[arg(short = 'n', long = "shares", value_parser = clap::value_parser!(u8).range(1..))]
+    pub share_count: u8,
+    /// Directory to write `share-<x>.txt` files into
+    #[arg(short, long, default_value = ".")]
+    pub out_dir: PathBuf,
+}
+
+/// The single `AGE-SECRET-KEY-1…` line of an identity file.
+fn read_identity(path: &PathBuf) -> Result<age::x25519::Identity, PluginError> {
+    let text =
+        zeroize::Zeroizing::new(
+            fs::read_to_string(path).map_err(|source| PluginError::File {
+                path: path.clone(),
+                source,
+            })?,
+        );
+    let mut keys = text
+        .lines()
+        .map(str::trim)
+        .filter(|l| !l.is_empty() && !l.starts_with('#'));
+    let bad = |reason: &str| PluginError::Usage(format!("{}: {reason}", path.display()));
+    match (keys.next(), keys.next()) {
+        (Some(key), None) => key
+            .parse()
+            .map_err(|e: &str| bad(&format!("not an X25519 identity ({e})"))),
+        (None, _) => Err(bad("no identity found")),
+        (Some(_), Some(_)) => Err(bad("holds more than one identity")),
+    }
+}
+
+pub fn run(args: SplitArgs) -> Result<(), PluginError> {
+    if args.threshold > args.share_count {
+        return Err(PluginError::Usage(format!(
+            "threshold {} is larger than the {} shares",
+            args.threshold, args.share_count
+        )));
+    }
+    let identity = match &args.identity {
+        Some(path) => read_identity(path)?,
+        None => age::x25519::Identity::generate(),
+    };
+    fs::create_dir_all(&args.out_dir).map_err(|source| PluginError::File {
+        path: args.out_dir.clone(),
+        source,
+    })?;
+    let shares = split_identity(&identity, args.threshold, args.share_count);
+    let recipient = recipient_string(&shares[0].recipient);
+    debug_assert_eq!(recipient, identity.to_public().to_string());
+
+    for share in &shares {
+        let path = args.out_dir.join(format!("share-{}.txt", share.share.x.0));
+        let file_error = |source| PluginError::File {
+            path: path.clone(),
+            source,
+        };
+        let mut options = OpenOptions::new();
+        options.write(true).create_new(true);
+        #[cfg(unix)]
+        std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+        let mut file = options.open(&path).map_err(file_error)?;
+        write!(
+            file,
+            "# age-plugin-sss share {} of {}; any {} decrypt\n# recipient: {recipient}\n{}\n",
+            share.share.x.0,
+            args.share_count,
+            args.threshold,
+            share.encode().as_str()
+        )
+        .map_err(file_error)?;
+        eprintln!("age-plugin-sss: wrote {}", path.display());
+    }
+    println!("{recipient}");
+    Ok(())
+}
@@ -0,0 +1,137 @@
+// age.rs
+//
+// Drives the plugin through the `age` library, the way `age -d -i …` would:
+// age finds `age-plugin-sss` on `$PATH` and runs its identity-v1 state
+// machine with every share identity it was given.
+
+use age::plugin::{Identity as PluginIdentity, IdentityPluginV1};
+use age::secrecy::ExposeSecret;
+use age::{DecryptError, NoCallbacks};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::{env, fs, process};
+
+const BIN: &str = env!("CARGO_BIN_EXE_age-plugin-sss");
+
+fn temp_dir(name: &str) -> PathBuf {
+    let dir = env::temp_dir().join(format!("age-plugin-sss-{name}-{}", process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+/// Run `split` and return the printed recipient.
+fn split(args: &[&str], out_dir: &Path) -> String {
+    let output = Command::new(BIN)
+        .arg("split")
+        .args(args)
+        .arg("--out-dir")
+        .arg(out_dir)
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    String::from_utf8(output.stdout).unwrap().trim().to_string()
+}
+
+fn share(dir: &Path, x: u8) -> PluginIdentity {
+    let text = fs::read_to_string(dir.join(format!("share-{x}.txt"))).unwrap();
+    let line = text.lines().find(|l| !l.starts_with('#')).unwrap();
+    line.parse().unwrap()
+}
+
+fn decrypt(identities: &[PluginIdentity], ciphertext: &[u8]) -> Result<Vec<u8>, DecryptError> {
+    let plugin = IdentityPluginV1::new("sss", identities, NoCallbacks)?;
+    age::decrypt(&plugin, ciphertext)
+}
+
+#[test]
+fn threshold_share_identities_decrypt() {
+    // SAFETY: this is the only test in this binary, so no other thread is
+    // reading the environment.
+    unsafe {
+        let bin_dir = Path::new(BIN).parent().unwrap();
+        let path = env::join_paths(
+            std::iter::once(bin_dir.to_path_buf())
+                .chain(env::split_paths(&env::var_os("PATH").unwrap_or_default())),
+        )
+        .unwrap();
+        env::set_var("PATH", path);
+    }
+
+    let dir = temp_dir("generate");
+    let recipient = split(&["-t", "2", "-n", "3"], &dir);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let mode = fs::metadata(dir.join("share-1.txt"))
+            .unwrap()
+            .permissions()
+            .mode();
+        assert_eq!(mode & 0o777, 0o600);
+    }
+    let ciphertext = age::encrypt(
+        &recipient.parse::<age::x25519::Recipient>().unwrap(),
+        b"nightly backup",
+    )
+    .unwrap();
+
+    let plaintext = decrypt(&[share(&dir, 1), share(&dir, 3)], &ciphertext).unwrap();
+    assert_eq!(plaintext, b"nightly backup");
+    let plaintext = decrypt(
+        &[share(&dir, 3), share(&dir, 2), share(&dir, 1)],
+        &ciphertext,
+    )
+    .unwrap();
+    assert_eq!(plaintext, b"nightly backup");
+
+    let too_few = decrypt(&[share(&dir, 2), share(&dir, 2)], &ciphertext).unwrap_err();
+    assert!(too_few.to_string().contains("need 2 shares"), "{too_few}");
+
+    // Splitting an existing identity keeps its recipient.
+    let existing = temp_dir("existing");
+    let identity = age::x25519::Identity::generate();
+    fs::write(
+        existing.join("key.txt"),
+        format!("# created: now\n{}\n", identity.to_string().expose_secret()),
+    )
+    .unwrap();
+    let key_path = existing.join("key.txt");
+    let (first, second) = (existing.join("first"), existing.join("second"));
+    for out_dir in [&first, &second] {
+        let printed = split(&["-t", "2", "-n", "2", key_path.to_str().unwrap()], out_dir);
+        assert_eq!(printed, identity.to_public().to_string());
+    }
+    let backup = age::encrypt(&identity.to_public(), b"offsite copy").unwrap();
+    let plaintext = decrypt(&[share(&first, 1), share(&first, 2)], &backup).unwrap();
+    assert_eq!(plaintext, b"offsite copy");
+
+    // Two splits of the same identity share a recipient but not a
+    // polynomial: mixing them rebuilds the wrong key, which is caught.
+    let mixed = decrypt(&[share(&first, 1), share(&second, 2)], &backup).unwrap_err();
+    assert!(
+        mixed
+            .to_string()
+            .contains("do not reconstruct its identity"),
+        "{mixed}"
+    );
+
+    // Shares of different identities are grouped apart, so neither group
+    // reaches its threshold.
+    let foreign = decrypt(&[share(&dir, 1), share(&first, 2)], &ciphertext).unwrap_err();
+    assert!(foreign.to_string().contains("need 2 shares"), "{foreign}");
+
+    // Existing share files are never overwritten.
+    let again = Command::new(BIN)
+        .args(["split", "-t", "1", "-n", "1", "--out-dir"])
+        .arg(&dir)
+        .output()
+        .unwrap();
+    assert!(!again.status.success());
+
+    fs::remove_dir_all(&dir).unwrap();
+    fs::remove_dir_all(&existing).unwrap();
+}
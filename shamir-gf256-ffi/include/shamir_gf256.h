@@ -0,0 +1,186 @@
+#ifndef SHAMIR_GF256_H
+#define SHAMIR_GF256_H
+
+/* Generated by cbindgen from shamir-gf256-ffi/src/lib.rs; do not edit. */
+
+#include <stddef.h>
+#include <stdint.h>
+
+/**
+ * Result of every `sss_*` call.
+ */
+typedef enum SssStatus {
+  SSS_STATUS_OK = 0,
+  /**
+   * A required pointer argument was NULL.
+   */
+  SSS_STATUS_NULL_POINTER = 1,
+  /**
+   * Threshold or share count out of range, or an ECC length outside 2..=254.
+   */
+  SSS_STATUS_INVALID_PARAMETERS = 2,
+  /**
+   * Fewer shares than the threshold.
+   */
+  SSS_STATUS_NOT_ENOUGH_SHARES = 3,
+  /**
+   * Shares of different lengths.
+   */
+  SSS_STATUS_LENGTH_MISMATCH = 4,
+  /**
+   * Repeated or zero share x-coordinates.
+   */
+  SSS_STATUS_INVALID_X = 5,
+  /**
+   * Unknown encoding name, or text that is not UTF-8.
+   */
+  SSS_STATUS_INVALID_ENCODING = 6,
+  /**
+   * Text that is not a well-formed share.
+   */
+  SSS_STATUS_INVALID_SHARE = 7,
+  /**
+   * A share too damaged for Reed–Solomon to repair.
+   */
+  SSS_STATUS_UNCORRECTABLE_SHARE = 8,
+  /**
+   * A passphrase-protected share; unwrap it before decoding.
+   */
+  SSS_STATUS_WRAPPED_SHARE = 9,
+  /**
+   * A dealer-signed share; it needs the set's verification key.
+   */
+  SSS_STATUS_SIGNED_SHARE = 10,
+  /**
+   * A share expected to carry a dealer signature carries none.
+   */
+  SSS_STATUS_UNSIGNED_SHARE = 11,
+  /**
+   * A bug in the library; please report it.
+   */
+  SSS_STATUS_PANIC = 99,
+} SssStatus;
+
+/**
+ * An owned share. Create it with `sss_split` or `sss_share_decode`.
+ */
+typedef struct SssShare SssShare;
+
+/**
+ * Bytes owned by the library; release with `sss_buffer_free`.
+ */
+typedef struct SssBuffer {
+  uint8_t *data;
+  size_t len;
+} SssBuffer;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+/**
+ * Static, NUL-terminated description of `status`.
+ *
+ * Takes a plain `int` rather than `enum SssStatus` so that any value a
+ * caller passes is defined behaviour; unknown values get a generic message.
+ */
+const char *sss_status_message(int status);
+
+/**
+ * Split `secret` into `share_count` shares, any `threshold` of which
+ * reconstruct it. On success `out_shares[0..share_count]` receives one
+ * handle per share (x = 1, 2, ...), each to be freed with `sss_share_free`.
+ *
+ * # Safety
+ * `secret` must be valid for `secret_len` bytes and `out_shares` for
+ * `share_count` pointers.
+ */
+enum SssStatus sss_split(const uint8_t *secret,
+                         size_t secret_len,
+                         size_t threshold,
+                         size_t share_count,
+                         struct SssShare **out_shares);
+
+/**
+ * Rebuild the secret from the first `threshold` of `share_count` shares.
+ * On success `*out` holds the secret; free it with `sss_buffer_free`.
+ *
+ * # Safety
+ * `shares` must be valid for `share_count` non-NULL handles and `out` must
+ * be writable.
+ */
+enum SssStatus sss_reconstruct(struct SssShare *const *shares,
+                               size_t share_count,
+                               size_t threshold,
+                               struct SssBuffer *out);
+
+/**
+ * Encode `share` as text with `ecc_len` Reed–Solomon parity bytes (16 is a
+ * good default). `encoding` names one of "hex", "base32", "base58check",
+ * "base64url", "bech32m" or "words"; NULL means "hex". On success `*out`
+ * holds a NUL-terminated string; free it with `sss_string_free`.
+ *
+ * # Safety
+ * `share` must be a live handle, `encoding` NULL or a NUL-terminated string,
+ * and `out` writable.
+ */
+enum SssStatus sss_share_encode(const struct SssShare *share,
+                                size_t ecc_len,
+                                const char *encoding,
+                                char **out);
+
+/**
+ * Decode a share written in any supported encoding, repairing damage up to
+ * its Reed–Solomon capacity. On success `*out` is a new handle.
+ *
+ * # Safety
+ * `text` must be a NUL-terminated string and `out` writable.
+ */
+enum SssStatus sss_share_decode(const char *text, struct SssShare **out);
+
+/**
+ * The share's x-coordinate (1..=255), or 0 for a NULL handle.
+ *
+ * # Safety
+ * `share` must be NULL or a live handle.
+ */
+uint8_t sss_share_x(const struct SssShare *share);
+
+/**
+ * Length of the share's y bytes, which equals the secret length.
+ *
+ * # Safety
+ * `share` must be NULL or a live handle.
+ */
+size_t sss_share_len(const struct SssShare *share);
+
+/**
+ * Zeroize and free a share handle. NULL is ignored.
+ *
+ * # Safety
+ * `share` must be NULL or a handle not yet freed.
+ */
+void sss_share_free(struct SssShare *share);
+
+/**
+ * Zeroize and free a buffer from `sss_reconstruct`, then reset it to
+ * empty. NULL and already-empty buffers are ignored.
+ *
+ * # Safety
+ * `buffer` must be NULL or point to a buffer filled by this library.
+ */
+void sss_buffer_free(struct SssBuffer *buffer);
+
+/**
+ * Zeroize and free a string from `sss_share_encode`. NULL is ignored.
+ *
+ * # Safety
+ * `text` must be NULL or a string returned by this library, not yet freed.
+ */
+void sss_string_free(char *text);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* SHAMIR_GF256_H */
@@ -0,0 +1,363 @@
+// lib.rs
+//
+// C ABI for the library, for services that link it from Go, C or C++. The
+// header `include/shamir_gf256.h` is generated from this file by cbindgen
+// (`tests/c_api.rs` fails when it is stale).
+//
+// Conventions:
+// - every function returns an `SssStatus` and writes results through out
+//   pointers, which are left untouched on failure;
+// - shares are opaque `SssShare` handles, released with `sss_share_free`;
+// - secrets and share text are allocated here and must be released with
+//   `sss_buffer_free` / `sss_string_free`, which zeroize before freeing;
+// - panics never cross the boundary; they surface as `SSS_STATUS_PANIC`.
+
+use core::ffi::{CStr, c_char, c_int};
+use core::{ptr, slice};
+use shamir_gf256::encoding::{ENCODINGS, ShareEncoding};
+use shamir_gf256::{Share, ShareCodecError, ShareRef, SssError};
+use std::ffi::CString;
+use std::panic::{AssertUnwindSafe, catch_unwind};
+use zeroize::{Zeroize, Zeroizing};
+
+/// Result of every `sss_*` call.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SssStatus {
+    Ok = 0,
+    /// A required pointer argument was NULL.
+    NullPointer = 1,
+    /// Threshold or share count out of range, or an ECC length outside 2..=254.
+    InvalidParameters = 2,
+    /// Fewer shares than the threshold.
+    NotEnoughShares = 3,
+    /// Shares of different lengths.
+    LengthMismatch = 4,
+    /// Repeated or zero share x-coordinates.
+    InvalidX = 5,
+    /// Unknown encoding name, or text that is not UTF-8.
+    InvalidEncoding = 6,
+    /// Text that is not a well-formed share.
+    InvalidShare = 7,
+    /// A share too damaged for Reed–Solomon to repair.
+    UncorrectableShare = 8,
+    /// A passphrase-protected share; unwrap it before decoding.
+    WrappedShare = 9,
+    /// A dealer-signed share; it needs the set's verification key.
+    SignedShare = 10,
+    /// A share expected to carry a dealer signature carries none.
+    UnsignedShare = 11,
+    /// A bug in the library; please report it.
+    Panic = 99,
+}
+
+/// An owned share. Create it with `sss_split` or `sss_share_decode`.
+pub struct SssShare(Share);
+
+/// Bytes owned by the library; release with `sss_buffer_free`.
+#[repr(C)]
+pub struct SssBuffer {
+    pub data: *mut u8,
+    pub len: usize,
+}
+
+impl From<SssError> for SssStatus {
+    fn from(e: SssError) -> Self {
+        match e {
+            SssError::InvalidParameters | SssError::BufferLength => SssStatus::InvalidParameters,
+            SssError::NotEnoughShares => SssStatus::NotEnoughShares,
+            SssError::LengthMismatch => SssStatus::LengthMismatch,
+            SssError::InvalidX => SssStatus::InvalidX,
+        }
+    }
+}
+
+impl From<ShareCodecError> for SssStatus {
+    fn from(e: ShareCodecError) -> Self {
+        match e {
+            ShareCodecError::EccDecode => SssStatus::UncorrectableShare,
+            ShareCodecError::Wrapped => SssStatus::WrappedShare,
+            ShareCodecError::Signed => SssStatus::SignedShare,
+            ShareCodecError::Unsigned => SssStatus::UnsignedShare,
+            // Includes `UnknownEncoding`: text no encoding recognises.
+            _ => SssStatus::InvalidShare,
+        }
+    }
+}
+
+/// Run `f`, turning a panic into `SssStatus::Panic`.
+fn guard(f: impl FnOnce() -> Result<(), SssStatus>) -> SssStatus {
+    match catch_unwind(AssertUnwindSafe(f)) {
+        Ok(Ok(())) => SssStatus::Ok,
+        Ok(Err(status)) => status,
+        Err(_) => SssStatus::Panic,
+    }
+}
+
+/// # Safety
+/// `data` must be NULL only if `len` is 0, else valid for `len` bytes.
+unsafe fn bytes<'a>(data: *const u8, len: usize) -> Result<&'a [u8], SssStatus> {
+    if len == 0 {
+        Ok(&[])
+    } else if data.is_null() {
+        Err(SssStatus::NullPointer)
+    } else {
+        Ok(unsafe { slice::from_raw_parts(data, len) })
+    }
+}
+
+impl SssStatus {
+    const ALL: [SssStatus; 13] = [
+        SssStatus::Ok,
+        SssStatus::NullPointer,
+        SssStatus::InvalidParameters,
+        SssStatus::NotEnoughShares,
+        SssStatus::LengthMismatch,
+        SssStatus::InvalidX,
+        SssStatus::InvalidEncoding,
+        SssStatus::InvalidShare,
+        SssStatus::UncorrectableShare,
+        SssStatus::WrappedShare,
+        SssStatus::SignedShare,
+        SssStatus::UnsignedShare,
+        SssStatus::Panic,
+    ];
+
+    /// The status with this numeric value, if there is one.
+    fn from_raw(raw: c_int) -> Option<Self> {
+        Self::ALL.into_iter().find(|&s| s as c_int == raw)
+    }
+}
+
+/// Static, NUL-terminated description of `status`.
+///
+/// Takes a plain `int` rather than `enum SssStatus` so that any value a
+/// caller passes is defined behaviour; unknown values get a generic message.
+#[unsafe(no_mangle)]
+pub extern "C" fn sss_status_message(status: c_int) -> *const c_char {
+    let Some(status) = SssStatus::from_raw(status) else {
+        return c"unknown status".as_ptr();
+    };
+    let message: &'static CStr = match status {
+        SssStatus::Ok => c"ok",
+        SssStatus::NullPointer => c"a required pointer was NULL",
+        SssStatus::InvalidParameters => c"invalid threshold, share count or ECC length",
+        SssStatus::NotEnoughShares => c"not enough shares to reach the threshold",
+        SssStatus::LengthMismatch => c"shares have different lengths",
+        SssStatus::InvalidX => c"share x-coordinates must be distinct and non-zero",
+        SssStatus::InvalidEncoding => c"unknown encoding or text that is not UTF-8",
+        SssStatus::InvalidShare => c"not a well-formed share",
+        SssStatus::UncorrectableShare => c"share is too damaged to repair",
+        SssStatus::WrappedShare => c"share is passphrase-protected",
+        SssStatus::SignedShare => c"signed share; it needs the dealer verification key",
+        SssStatus::UnsignedShare => c"share carries no dealer signature",
+        SssStatus::Panic => c"internal error",
+    };
+    message.as_ptr()
+}
+
+/// Split `secret` into `share_count` shares, any `threshold` of which
+/// reconstruct it. On success `out_shares[0..share_count]` receives one
+/// handle per share (x = 1, 2, ...), each to be freed with `sss_share_free`.
+///
+/// # Safety
+/// `secret` must be valid for `secret_len` bytes and `out_shares` for
+/// `share_count` pointers.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn sss_split(
+    secret: *const u8,
+    secret_len: usize,
+    threshold: usize,
+    share_count: usize,
+    out_shares: *mut *mut SssShare,
+) -> SssStatus {
+    guard(|| {
+        let secret = unsafe { bytes(secret, secret_len) }?;
+        if out_shares.is_null() {
+            return Err(SssStatus::NullPointer);
+        }
+        // `split` asserts these; check first so C callers get a status.
+        if !(1..=255).contains(&threshold) || !(threshold..=255).contains(&share_count) {
+            return Err(SssStatus::InvalidParameters);
+        }
+        let shares = shamir_gf256::split(secret, threshold, share_count);
+        for (i, share) in shares.into_iter().enumerate() {
+            unsafe { *out_shares.add(i) = Box::into_raw(Box::new(SssShare(share))) };
+        }
+        Ok(())
+    })
+}
+
+/// Rebuild the secret from the first `threshold` of `share_count` shares.
+/// On success `*out` holds the secret; free it with `sss_buffer_free`.
+///
+/// # Safety
+/// `shares` must be valid for `share_count` non-NULL handles and `out` must
+/// be writable.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn sss_reconstruct(
+    shares: *const *mut SssShare,
+    share_count: usize,
+    threshold: usize,
+    out: *mut SssBuffer,
+) -> SssStatus {
+    guard(|| {
+        if shares.is_null() || out.is_null() {
+            return Err(SssStatus::NullPointer);
+        }
+        let handles = unsafe { slice::from_raw_parts(shares, share_count) };
+        if handles.iter().any(|h| h.is_null()) {
+            return Err(SssStatus::NullPointer);
+        }
+        let refs: Vec<ShareRef<'_>> = handles
+            .iter()
+            .map(|&h| unsafe { &*h }.0.as_share_ref())
+            .collect();
+        let len = refs.first().ok_or(SssStatus::NotEnoughShares)?.y.len();
+        let mut secret = vec![0u8; len].into_boxed_slice();
+        if let Err(e) = shamir_gf256::reconstruct_into(&refs, threshold, &mut secret) {
+            secret.zeroize();
+            return Err(e.into());
+        }
+        let len = secret.len();
+        let data = Box::into_raw(secret).cast::<u8>();
+        unsafe { out.write(SssBuffer { data, len }) };
+        Ok(())
+    })
+}
+
+/// Encode `share` as text with `ecc_len` Reed–Solomon parity bytes (16 is a
+/// good default). `encoding` names one of "hex", "base32", "base58check",
+/// "base64url", "bech32m" or "words"; NULL means "hex". On success `*out`
+/// holds a NUL-terminated string; free it with `sss_string_free`.
+///
+/// # Safety
+/// `share` must be a live handle, `encoding` NULL or a NUL-terminated string,
+/// and `out` writable.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn sss_share_encode(
+    share: *const SssShare,
+    ecc_len: usize,
+    encoding: *const c_char,
+    out: *mut *mut c_char,
+) -> SssStatus {
+    guard(|| {
+        if share.is_null() || out.is_null() {
+            return Err(SssStatus::NullPointer);
+        }
+        if !(2..=254).contains(&ecc_len) {
+            return Err(SssStatus::InvalidParameters);
+        }
+        let name = if encoding.is_null() {
+            "hex"
+        } else {
+            unsafe { CStr::from_ptr(encoding) }
+                .to_str()
+                .map_err(|_| SssStatus::InvalidEncoding)?
+        };
+        let encoding: &dyn ShareEncoding = *ENCODINGS
+            .iter()
+            .find(|e| e.name() == name)
+            .ok_or(SssStatus::InvalidEncoding)?;
+        let text = Zeroizing::new(shamir_gf256::share_encode(
+            &unsafe { &*share }.0,
+            ecc_len,
+            encoding,
+        )?);
+        // Add the NUL in a buffer sized exactly, so neither the push nor the
+        // conversion to a boxed `CString` reallocates and leaves a copy.
+        let mut bytes = Zeroizing::new(Vec::with_capacity(text.len() + 1));
+        bytes.extend_from_slice(text.as_bytes());
+        bytes.push(0);
+        // Encodings are ASCII or BIP-39 words, so there is no interior NUL.
+        let text = CString::from_vec_with_nul(core::mem::take(&mut *bytes))
+            .map_err(|_| SssStatus::Panic)?;
+        unsafe { out.write(text.into_raw()) };
+        Ok(())
+    })
+}
+
+/// Decode a share written in any supported encoding, repairing damage up to
+/// its Reed–Solomon capacity. On success `*out` is a new handle.
+///
+/// # Safety
+/// `text` must be a NUL-terminated string and `out` writable.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn sss_share_decode(
+    text: *const c_char,
+    out: *mut *mut SssShare,
+) -> SssStatus {
+    guard(|| {
+        if text.is_null() || out.is_null() {
+            return Err(SssStatus::NullPointer);
+        }
+        let text = unsafe { CStr::from_ptr(text) }
+            .to_str()
+            .map_err(|_| SssStatus::InvalidEncoding)?;
+        let decoded = shamir_gf256::share_decode(text)?;
+        unsafe { out.write(Box::into_raw(Box::new(SssShare(decoded.share)))) };
+        Ok(())
+    })
+}
+
+/// The share's x-coordinate (1..=255), or 0 for a NULL handle.
+///
+/// # Safety
+/// `share` must be NULL or a live handle.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn sss_share_x(share: *const SssShare) -> u8 {
+    unsafe { share.as_ref() }.map_or(0, |s| s.0.x.0)
+}
+
+/// Length of the share's y bytes, which equals the secret length.
+///
+/// # Safety
+/// `share` must be NULL or a live handle.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn sss_share_len(share: *const SssShare) -> usize {
+    unsafe { share.as_ref() }.map_or(0, |s| s.0.y.len())
+}
+
+/// Zeroize and free a share handle. NULL is ignored.
+///
+/// # Safety
+/// `share` must be NULL or a handle not yet freed.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn sss_share_free(share: *mut SssShare) {
+    if !share.is_null() {
+        // `Share` zeroizes itself on drop.
+        drop(unsafe { Box::from_raw(share) });
+    }
+}
+
+/// Zeroize and free a buffer from `sss_reconstruct`, then reset it to
+/// empty. NULL and already-empty buffers are ignored.
+///
+/// # Safety
+/// `buffer` must be NULL or point to a buffer filled by this library.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn sss_buffer_free(buffer: *mut SssBuffer) {
+    let Some(buffer) = (unsafe { buffer.as_mut() }) else {
+        return;
+    };
+    if !buffer.data.is_null() {
+        let mut bytes =
+            unsafe { Box::from_raw(ptr::slice_from_raw_parts_mut(buffer.data, buffer.len)) };
+        bytes.zeroize();
+    }
+    buffer.data = ptr::null_mut();
+    buffer.len = 0;
+}
+
+/// Zeroize and free a string from `sss_share_encode`. NULL is ignored.
+///
+/// # Safety
+/// `text` must be NULL or a string returned by this library, not yet freed.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn sss_string_free(text: *mut c_char) {
+    if !text.is_null() {
+        unsafe { CString::from_raw(text) }
+            .into_bytes_with_nul()
+            .zeroize();
+    }
+}
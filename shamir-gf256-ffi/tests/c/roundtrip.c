@@ -0,0 +1,118 @@
+/*
+ * roundtrip.c
+ *
+ * Exercises the C ABI the way a service would: split, encode every share,
+ * decode a damaged one, reconstruct, and check the error paths. Built and
+ * run by tests/c_api.rs; exits non-zero on the first failed check.
+ */
+
+#include <stdio.h>
+#include <stdlib.h>
+#include <string.h>
+
+#include "shamir_gf256.h"
+
+#define CHECK(cond)                                                    \
+    do {                                                               \
+        if (!(cond)) {                                                 \
+            fprintf(stderr, "%s:%d: check failed: %s\n", __FILE__,     \
+                    __LINE__, #cond);                                  \
+            exit(1);                                                   \
+        }                                                              \
+    } while (0)
+
+#define CHECK_STATUS(call, expected)                                   \
+    do {                                                               \
+        SssStatus status_ = (call);                                    \
+        if (status_ != (expected)) {                                   \
+            fprintf(stderr, "%s:%d: %s returned %d (%s)\n", __FILE__,  \
+                    __LINE__, #call, (int)status_,                     \
+                    sss_status_message(status_));                      \
+            exit(1);                                                   \
+        }                                                              \
+    } while (0)
+
+int main(void) {
+    static const char secret[] = "correct horse battery staple";
+    const size_t secret_len = strlen(secret);
+    SssShare *shares[5] = {0};
+
+    CHECK_STATUS(sss_split((const uint8_t *)secret, secret_len, 3, 5, shares),
+                 SSS_STATUS_OK);
+    for (int i = 0; i < 5; i++) {
+        CHECK(sss_share_x(shares[i]) == i + 1);
+        CHECK(sss_share_len(shares[i]) == secret_len);
+    }
+
+    /* Encode share 5 as hex and damage one character; decoding repairs it. */
+    char *text = NULL;
+    CHECK_STATUS(sss_share_encode(shares[4], 16, NULL, &text), SSS_STATUS_OK);
+    size_t text_len = strlen(text);
+    text[text_len - 1] = text[text_len - 1] == '0' ? '1' : '0';
+    SssShare *decoded = NULL;
+    CHECK_STATUS(sss_share_decode(text, &decoded), SSS_STATUS_OK);
+    CHECK(sss_share_x(decoded) == 5);
+    sss_string_free(text);
+
+    /* Another encoding round-trips too. */
+    char *words = NULL;
+    CHECK_STATUS(sss_share_encode(shares[1], 16, "words", &words), SSS_STATUS_OK);
+    SssShare *from_words = NULL;
+    CHECK_STATUS(sss_share_decode(words, &from_words), SSS_STATUS_OK);
+    CHECK(sss_share_x(from_words) == 2);
+    sss_string_free(words);
+
+    SssShare *subset[3] = {from_words, shares[2], decoded};
+    SssBuffer recovered = {0};
+    CHECK_STATUS(sss_reconstruct(subset, 3, 3, &recovered), SSS_STATUS_OK);
+    CHECK(recovered.len == secret_len);
+    CHECK(memcmp(recovered.data, secret, secret_len) == 0);
+    sss_buffer_free(&recovered);
+    CHECK(recovered.data == NULL && recovered.len == 0);
+    sss_buffer_free(&recovered); /* freeing twice is harmless */
+
+    /* Error paths report a status and leave out-parameters alone. */
+    SssShare *none[2] = {0};
+    CHECK_STATUS(sss_split((const uint8_t *)secret, secret_len, 4, 3, none),
+                 SSS_STATUS_INVALID_PARAMETERS);
+    CHECK(none[0] == NULL);
+    CHECK_STATUS(sss_reconstruct(subset, 2, 3, &recovered),
+                 SSS_STATUS_NOT_ENOUGH_SHARES);
+    CHECK(recovered.data == NULL);
+    SssShare *twice[2] = {shares[0], shares[0]};
+    CHECK_STATUS(sss_reconstruct(twice, 2, 2, &recovered), SSS_STATUS_INVALID_X);
+    CHECK_STATUS(sss_share_encode(shares[0], 1, NULL, &text),
+                 SSS_STATUS_INVALID_PARAMETERS);
+    CHECK_STATUS(sss_share_encode(shares[0], 16, "rot13", &text),
+                 SSS_STATUS_INVALID_ENCODING);
+    CHECK_STATUS(sss_share_decode("not a share", &decoded),
+                 SSS_STATUS_INVALID_SHARE);
+    CHECK_STATUS(sss_share_decode(NULL, &decoded), SSS_STATUS_NULL_POINTER);
+
+    /* Shares that need more than the text are told apart from garbage. */
+    CHECK_STATUS(sss_share_decode("53485731" "0000000000000000", &decoded),
+                 SSS_STATUS_WRAPPED_SHARE);
+    char *plain = NULL;
+    CHECK_STATUS(sss_share_encode(shares[0], 16, NULL, &plain), SSS_STATUS_OK);
+    size_t plain_len = strlen(plain);
+    char *signed_text = malloc(plain_len + 2 * 64 + 1);
+    CHECK(signed_text != NULL);
+    memcpy(signed_text, plain, plain_len);
+    memset(signed_text + plain_len, '0', 2 * 64); /* a 64-byte signature */
+    signed_text[plain_len + 2 * 64] = '\0';
+    CHECK_STATUS(sss_share_decode(signed_text, &decoded), SSS_STATUS_SIGNED_SHARE);
+    free(signed_text);
+    sss_string_free(plain);
+    CHECK(strcmp(sss_status_message(SSS_STATUS_OK), "ok") == 0);
+    CHECK(strcmp(sss_status_message(12345), "unknown status") == 0);
+    CHECK(strcmp(sss_status_message(-1), "unknown status") == 0);
+
+    for (int i = 0; i < 5; i++) {
+        sss_share_free(shares[i]);
+    }
+    sss_share_free(decoded);
+    sss_share_free(from_words);
+    sss_share_free(NULL);
+    puts("roundtrip.c: ok");
+    return 0;
+}
@@ -0,0 +1,79 @@
+// c_api.rs
+//
+// Keeps the checked-in header in sync with the Rust source, then compiles
+// `tests/c/roundtrip.c` against that header and the freshly built cdylib and
+// runs it. The C compiler is `$CC`, or `cc` if unset.
+
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+const HEADER: &str = "include/shamir_gf256.h";
+
+fn crate_dir() -> &'static Path {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+}
+
+#[test]
+fn header_is_current() {
+    let config = cbindgen::Config::from_file(crate_dir().join("cbindgen.toml")).unwrap();
+    let mut generated = Vec::new();
+    cbindgen::Builder::new()
+        .with_crate(crate_dir())
+        .with_config(config)
+        .generate()
+        .expect("cbindgen failed")
+        .write(&mut generated);
+    let path = crate_dir().join(HEADER);
+
+    if std::env::var_os("UPDATE_HEADER").is_some() {
+        std::fs::write(&path, &generated).unwrap();
+        return;
+    }
+    let checked_in = std::fs::read(&path).unwrap_or_default();
+    assert!(
+        checked_in == generated,
+        "{HEADER} is stale; rerun with UPDATE_HEADER=1 to regenerate it"
+    );
+}
+
+/// Directory holding the cdylib that `cargo test` built alongside this test.
+fn library_dir() -> PathBuf {
+    // The test binary lives in `target/<profile>/deps`.
+    let exe = std::env::current_exe().unwrap();
+    exe.parent().unwrap().to_path_buf()
+}
+
+#[cfg(unix)]
+#[test]
+fn c_program_round_trips() {
+    let out = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("sss_c_roundtrip");
+    let lib_dir = library_dir();
+    let cc = std::env::var("CC").unwrap_or_else(|_| "cc".into());
+    let status = Command::new(&cc)
+        .args(["-std=c99", "-Wall", "-Wextra", "-Werror"])
+        .arg("-I")
+        .arg(crate_dir().join("include"))
+        .arg(crate_dir().join("tests/c/roundtrip.c"))
+        .arg("-o")
+        .arg(&out)
+        .arg("-L")
+        .arg(&lib_dir)
+        .arg(format!("-Wl,-rpath,{}", lib_dir.display()))
+        .arg("-lshamir_gf256_ffi")
+        .status()
+        .unwrap_or_else(|e| panic!("cannot run {cc}: {e}"));
+    assert!(status.success(), "compiling roundtrip.c failed");
+
+    // Cargo's library path can hold a stale copy of the cdylib in
+    // `target/<profile>`; rely on the rpath to `deps` instead.
+    let output = Command::new(&out)
+        .env_remove("LD_LIBRARY_PATH")
+        .env_remove("DYLD_LIBRARY_PATH")
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "roundtrip.c failed:\n{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
@@ -0,0 +1,40 @@
+"""Shamir's secret sharing over GF(256) with Reed–Solomon protected shares."""
+
+__version__: str
+
+class SssError(ValueError):
+    """Invalid split or reconstruction parameters, or shares that do not fit together."""
+
+class ShareCodecError(ValueError):
+    """A share could not be encoded or decoded."""
+
+class InvalidShareError(ShareCodecError):
+    """The text is not a well-formed share (bad hex, header, layout or checksum)."""
+
+class UncorrectableShareError(ShareCodecError):
+    """The share is too damaged for Reed–Solomon to repair."""
+
+class Share:
+    """One share: a non-zero x-coordinate and one y byte per secret byte."""
+
+    def __init__(self, x: int, y: bytes) -> None: ...
+    @property
+    def x(self) -> int: ...
+    @property
+    def y(self) -> bytes: ...
+    def to_hex(self, ecc_len: int = 16) -> str: ...
+    @staticmethod
+    def from_hex(text: str) -> Share: ...
+    def __eq__(self, other: object) -> bool: ...
+
+def split(secret: bytes, threshold: int, share_count: int) -> list[Share]:
+    """Split `secret` into `share_count` shares, any `threshold` of which reconstruct it."""
+
+def reconstruct(shares: list[Share], threshold: int | None = None) -> bytes:
+    """Rebuild the secret from the first `threshold` shares (all of them by default)."""
+
+def share_to_hex(share: Share, ecc_len: int = 16) -> str:
+    """Hex text for `share` with `ecc_len` Reed–Solomon parity bytes."""
+
+def share_from_hex(text: str) -> Share:
+    """Parse hex text, repairing damage up to the share's RS capacity."""
@@ -0,0 +1,180 @@
+// lib.rs
+//
+// Python bindings (`import shamir_gf256`) for splitting, reconstruction and
+// the hex share codec. Build a wheel with `maturin build --release`; the type
+// stubs live in `shamir_gf256.pyi` next to this crate's manifest.
+
+use pyo3::create_exception;
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3::types::PyBytes;
+use zeroize::Zeroizing;
+
+create_exception!(
+    shamir_gf256,
+    SssError,
+    PyValueError,
+    "Invalid split or reconstruction parameters, or shares that do not fit together."
+);
+create_exception!(
+    shamir_gf256,
+    ShareCodecError,
+    PyValueError,
+    "A share could not be encoded or decoded."
+);
+create_exception!(
+    shamir_gf256,
+    InvalidShareError,
+    ShareCodecError,
+    "The text is not a well-formed share (bad hex, header, layout or checksum)."
+);
+create_exception!(
+    shamir_gf256,
+    UncorrectableShareError,
+    ShareCodecError,
+    "The share is too damaged for Reed–Solomon to repair."
+);
+
+fn codec_error(e: gf256::ShareCodecError) -> PyErr {
+    use gf256::ShareCodecError as E;
+    let msg = e.to_string();
+    match e {
+        E::Hex(_)
+        | E::BadMagic
+        | E::Truncated
+        | E::Overflow
+        | E::BadLayout
+        | E::ErasedHeader
+        | E::Encoding(_)
+        | E::Checksum(_)
+        | E::UnknownEncoding
+        | E::Armor(_)
+        | E::Cbor(_) => InvalidShareError::new_err(msg),
+        E::EccDecode => UncorrectableShareError::new_err(msg),
+        _ => ShareCodecError::new_err(msg),
+    }
+}
+
+fn sss_error(e: gf256::SssError) -> PyErr {
+    SssError::new_err(e.to_string())
+}
+
+fn check_ecc_len(ecc_len: usize) -> PyResult<()> {
+    if (2..=254).contains(&ecc_len) {
+        Ok(())
+    } else {
+        Err(PyValueError::new_err("ecc_len must be in 2..=254"))
+    }
+}
+
+/// One share: a non-zero x-coordinate and one y byte per secret byte.
+#[pyclass(name = "Share", module = "shamir_gf256", frozen, eq)]
+#[derive(PartialEq)]
+struct Share(gf256::Share);
+
+#[pymethods]
+impl Share {
+    #[new]
+    fn new(x: u8, y: &[u8]) -> PyResult<Self> {
+        if x == 0 {
+            return Err(SssError::new_err("share x-coordinate must be non-zero"));
+        }
+        Ok(Share(gf256::Share {
+            x: gf256::GF256(x),
+            y: y.to_vec(),
+        }))
+    }
+
+    #[getter]
+    fn x(&self) -> u8 {
+        self.0.x.0
+    }
+
+    /// A copy of the y bytes; Python cannot wipe it, so keep it short-lived.
+    #[getter]
+    fn y<'py>(&self, py: Python<'py>) -> Bound<'py, PyBytes> {
+        PyBytes::new(py, &self.0.y)
+    }
+
+    #[pyo3(signature = (ecc_len = 16))]
+    fn to_hex(&self, ecc_len: usize) -> PyResult<String> {
+        check_ecc_len(ecc_len)?;
+        Ok(gf256::share_to_hex(&self.0, ecc_len))
+    }
+
+    #[staticmethod]
+    fn from_hex(text: &str) -> PyResult<Self> {
+        gf256::share_from_hex(text).map(Share).map_err(codec_error)
+    }
+
+    // The y bytes are share material; keep them out of logs and tracebacks.
+    fn __repr__(&self) -> String {
+        format!("Share(x={}, len={})", self.0.x.0, self.0.y.len())
+    }
+}
+
+/// Split `secret` into `share_count` shares, any `threshold` of which
+/// reconstruct it.
+#[pyfunction]
+fn split(secret: &[u8], threshold: usize, share_count: usize) -> PyResult<Vec<Share>> {
+    if !(1..=255).contains(&threshold) || share_count < threshold || share_count > 255 {
+        return Err(sss_error(gf256::SssError::InvalidParameters));
+    }
+    let secret = Zeroizing::new(secret.to_vec());
+    Ok(gf256::split(&secret, threshold, share_count)
+        .into_iter()
+        .map(Share)
+        .collect())
+}
+
+/// Rebuild the secret from the first `threshold` shares (all of them by
+/// default).
+#[pyfunction]
+#[pyo3(signature = (shares, threshold = None))]
+fn reconstruct<'py>(
+    py: Python<'py>,
+    shares: Vec<PyRef<'py, Share>>,
+    threshold: Option<usize>,
+) -> PyResult<Bound<'py, PyBytes>> {
+    let threshold = threshold.unwrap_or(shares.len());
+    let refs: Vec<gf256::ShareRef<'_>> = shares.iter().map(|s| s.0.as_share_ref()).collect();
+    let Some(first) = refs.first() else {
+        return Err(sss_error(gf256::SssError::NotEnoughShares));
+    };
+    let mut secret = Zeroizing::new(vec![0u8; first.y.len()]);
+    gf256::reconstruct_into(&refs, threshold, &mut secret).map_err(sss_error)?;
+    Ok(PyBytes::new(py, &secret))
+}
+
+/// Hex text for `share` with `ecc_len` Reed–Solomon parity bytes.
+#[pyfunction]
+#[pyo3(signature = (share, ecc_len = 16))]
+fn share_to_hex(share: &Share, ecc_len: usize) -> PyResult<String> {
+    share.to_hex(ecc_len)
+}
+
+/// Parse hex text, repairing damage up to the share's RS capacity.
+#[pyfunction]
+fn share_from_hex(text: &str) -> PyResult<Share> {
+    Share::from_hex(text)
+}
+
+#[pymodule]
+#[pyo3(name = "shamir_gf256")]
+fn shamir_gf256(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    let py = m.py();
+    m.add_class::<Share>()?;
+    m.add_function(wrap_pyfunction!(split, m)?)?;
+    m.add_function(wrap_pyfunction!(reconstruct, m)?)?;
+    m.add_function(wrap_pyfunction!(share_to_hex, m)?)?;
+    m.add_function(wrap_pyfunction!(share_from_hex, m)?)?;
+    m.add("SssError", py.get_type::<SssError>())?;
+    m.add("ShareCodecError", py.get_type::<ShareCodecError>())?;
+    m.add("InvalidShareError", py.get_type::<InvalidShareError>())?;
+    m.add(
+        "UncorrectableShareError",
+        py.get_type::<UncorrectableShareError>(),
+    )?;
+    m.add("__version__", env!("CARGO_PKG_VERSION"))?;
+    Ok(())
+}
@@ -0,0 +1,62 @@
+# Run with `maturin develop && pytest` from shamir-gf256-py/.
+
+import pytest
+
+import shamir_gf256 as sss
+
+
+def test_split_and_reconstruct_any_threshold_subset():
+    shares = sss.split(b"correct horse", 3, 5)
+    assert len(shares) == 5
+    assert [s.x for s in shares] == [1, 2, 3, 4, 5]
+    assert sss.reconstruct(shares[2:]) == b"correct horse"
+    assert sss.reconstruct(shares, threshold=3) == b"correct horse"
+
+
+def test_too_few_shares_is_an_sss_error():
+    shares = sss.split(b"secret", 3, 5)
+    with pytest.raises(sss.SssError):
+        sss.reconstruct(shares[:2], threshold=3)
+    with pytest.raises(sss.SssError):
+        sss.reconstruct([])
+
+
+def test_bad_parameters_raise_instead_of_crashing():
+    with pytest.raises(sss.SssError):
+        sss.split(b"secret", 4, 3)
+    with pytest.raises(sss.SssError):
+        sss.split(b"secret", 0, 3)
+    with pytest.raises(sss.SssError):
+        sss.Share(0, b"\x01")
+    with pytest.raises(ValueError):
+        sss.split(b"secret", 2, 3)[0].to_hex(ecc_len=1)
+
+
+def test_hex_round_trip_and_repair():
+    share = sss.split(b"secret", 2, 3)[1]
+    text = sss.share_to_hex(share)
+    assert share.to_hex() == text
+    assert sss.share_from_hex(text) == share
+    assert sss.Share.from_hex(text.lower()) == share
+
+    damaged = text[:-4] + ("0000" if text[-4:] != "0000" else "FFFF")
+    assert sss.share_from_hex(damaged) == share
+
+
+def test_codec_errors_map_to_subclasses():
+    with pytest.raises(sss.InvalidShareError):
+        sss.share_from_hex("not hex")
+    share = sss.split(b"secret", 2, 3)[0]
+    text = share.to_hex(ecc_len=2)
+    wrecked = text[:-16] + "0123456789ABCDEF"
+    with pytest.raises(sss.UncorrectableShareError):
+        sss.share_from_hex(wrecked)
+    assert issubclass(sss.UncorrectableShareError, sss.ShareCodecError)
+    assert issubclass(sss.InvalidShareError, sss.ShareCodecError)
+    assert issubclass(sss.ShareCodecError, ValueError)
+
+
+def test_repr_does_not_leak_share_bytes():
+    share = sss.Share(7, b"\xaa\xbb")
+    assert repr(share) == "Share(x=7, len=2)"
+    assert share.y == b"\xaa\xbb"
@@ -0,0 +1,190 @@
+// lib.rs
+//
+// wasm-bindgen wrapper around `shamir-gf256` for other web apps, without the
+// Yew UI. `wasm-pack build` turns it into an npm package; the `.d.ts` is
+// generated from the signatures and doc comments below.
+//
+// Secrets cross the boundary as `Uint8Array`. Inputs are taken as owned
+// buffers and outputs built from `Zeroizing` ones, so the copies inside wasm
+// memory are wiped once the call returns. Share text is handed over as a
+// `JsString` copied out of a `Zeroizing<String>` for the same reason.
+
+use js_sys::{JsString, Uint8Array};
+use shamir_gf256::encoding::{ENCODINGS, ShareEncoding};
+use shamir_gf256::{GF256, ShareRef, SssError, inconsistent_shares, parse_any_share};
+use wasm_bindgen::prelude::*;
+use zeroize::Zeroizing;
+
+/// Parity bytes per share unless the caller asks otherwise, as in the web UI.
+const DEFAULT_ECC_LEN: usize = 16;
+
+#[wasm_bindgen(typescript_custom_section)]
+const TS_ENCODINGS: &str = r#"
+/** Text encodings accepted by `shareEncode` and `Share.encode`. */
+export type ShareEncodingName = "hex" | "base32" | "base58check" | "base64url" | "bech32m" | "words";
+"#;
+
+fn ecc_len(ecc_len: Option<usize>) -> Result<usize, JsError> {
+    match ecc_len.unwrap_or(DEFAULT_ECC_LEN) {
+        n @ 2..=254 => Ok(n),
+        _ => Err(JsError::new("eccLen must be between 2 and 254")),
+    }
+}
+
+/// Copy share text into a JS string, wiping the wasm-side buffer.
+fn js_text(text: String) -> JsString {
+    let text = Zeroizing::new(text);
+    JsString::from(text.as_str())
+}
+
+/// One share: a non-zero x-coordinate and one y byte per secret byte.
+/// Call `free()` when done to wipe it without waiting for the GC.
+#[wasm_bindgen]
+pub struct Share(shamir_gf256::Share);
+
+#[wasm_bindgen]
+impl Share {
+    #[wasm_bindgen(constructor)]
+    pub fn new(x: u8, y: Box<[u8]>) -> Result<Share, JsError> {
+        let y = Zeroizing::new(y);
+        if x == 0 {
+            return Err(JsError::new("share x-coordinate must be non-zero"));
+        }
+        Ok(Share(shamir_gf256::Share {
+            x: GF256(x),
+            y: y.to_vec(),
+        }))
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn x(&self) -> u8 {
+        self.0.x.0
+    }
+
+    /// A copy of the y bytes.
+    #[wasm_bindgen(getter)]
+    pub fn y(&self) -> Uint8Array {
+        Uint8Array::from(&self.0.y[..])
+    }
+
+    /// Hex text with `eccLen` Reed–Solomon parity bytes (default 16).
+    #[wasm_bindgen(js_name = toHex)]
+    pub fn to_hex(
+        &self,
+        #[wasm_bindgen(js_name = eccLen)] ecc_len: Option<usize>,
+    ) -> Result<JsString, JsError> {
+        share_to_hex(self, ecc_len)
+    }
+
+    /// Text in the named encoding with `eccLen` parity bytes (default 16).
+    pub fn encode(
+        &self,
+        #[wasm_bindgen(unchecked_param_type = "ShareEncodingName")] encoding: &str,
+        #[wasm_bindgen(js_name = eccLen)] ecc_len: Option<usize>,
+    ) -> Result<JsString, JsError> {
+        share_encode(self, encoding, ecc_len)
+    }
+}
+
+/// Split `secret` into `n` hex shares, any `t` of which recombine it. Each
+/// share carries `eccLen` Reed–Solomon parity bytes (default 16).
+#[wasm_bindgen]
+pub fn split(
+    secret: Box<[u8]>,
+    t: usize,
+    n: usize,
+    #[wasm_bindgen(js_name = eccLen)] ecc_len: Option<usize>,
+) -> Result<Vec<JsString>, JsError> {
+    let secret = Zeroizing::new(secret);
+    let ecc_len = self::ecc_len(ecc_len)?;
+    // `shamir_gf256::split` asserts these; JS callers get an exception instead.
+    if !(1..=255).contains(&t) || !(t..=255).contains(&n) {
+        return Err(JsError::from(SssError::InvalidParameters));
+    }
+    Ok(shamir_gf256::split(&secret, t, n)
+        .iter()
+        .map(|share| js_text(shamir_gf256::share_to_hex(share, ecc_len)))
+        .collect())
+}
+
+/// Recombine the secret from shares in any supported text format.
+///
+/// `threshold` defaults to the one recorded in armored shares, else to the
+/// number of shares given. Shares beyond the threshold must lie on the same
+/// polynomial as the rest; a share that does not is reported, never used.
+#[wasm_bindgen]
+pub fn combine(shares: Vec<String>, threshold: Option<usize>) -> Result<Uint8Array, JsError> {
+    let shares: Vec<Zeroizing<String>> = shares.into_iter().map(Zeroizing::new).collect();
+    let mut parsed = Vec::with_capacity(shares.len());
+    for (i, text) in shares.iter().enumerate() {
+        let share =
+            parse_any_share(text).map_err(|e| JsError::new(&format!("share {}: {e}", i + 1)))?;
+        parsed.push(share);
+    }
+    let threshold = threshold
+        .or_else(|| {
+            parsed
+                .iter()
+                .find_map(|p| p.headers.as_ref()?.meta)
+                .map(|m| usize::from(m.threshold))
+        })
+        .unwrap_or(parsed.len());
+    let refs: Vec<ShareRef<'_>> = parsed.iter().map(|p| p.share.as_share_ref()).collect();
+    let first = refs.first().ok_or(SssError::NotEnoughShares)?;
+    if refs.len() > threshold {
+        let bad = inconsistent_shares(&refs, threshold)?;
+        if !bad.is_empty() {
+            let xs: Vec<String> = bad.iter().map(|x| x.0.to_string()).collect();
+            return Err(JsError::new(&format!(
+                "share(s) {} do not lie on the polynomial of the others; nothing was reconstructed",
+                xs.join(", ")
+            )));
+        }
+    }
+    let mut secret = Zeroizing::new(vec![0u8; first.y.len()]);
+    shamir_gf256::reconstruct_into(&refs, threshold, &mut secret)?;
+    Ok(Uint8Array::from(&secret[..]))
+}
+
+/// Hex text for `share` with `eccLen` parity bytes (default 16).
+#[wasm_bindgen(js_name = shareToHex)]
+pub fn share_to_hex(
+    share: &Share,
+    #[wasm_bindgen(js_name = eccLen)] ecc_len: Option<usize>,
+) -> Result<JsString, JsError> {
+    Ok(js_text(shamir_gf256::share_to_hex(
+        &share.0,
+        self::ecc_len(ecc_len)?,
+    )))
+}
+
+/// Parse hex text, repairing damage up to the share's RS capacity.
+#[wasm_bindgen(js_name = shareFromHex)]
+pub fn share_from_hex(text: &str) -> Result<Share, JsError> {
+    Ok(Share(shamir_gf256::share_from_hex(text)?))
+}
+
+/// Text for `share` in the named encoding with `eccLen` parity bytes
+/// (default 16).
+#[wasm_bindgen(js_name = shareEncode)]
+pub fn share_encode(
+    share: &Share,
+    #[wasm_bindgen(unchecked_param_type = "ShareEncodingName")] encoding: &str,
+    #[wasm_bindgen(js_name = eccLen)] ecc_len: Option<usize>,
+) -> Result<JsString, JsError> {
+    let ecc_len = self::ecc_len(ecc_len)?;
+    let encoding: &dyn ShareEncoding = *ENCODINGS
+        .iter()
+        .find(|e| e.name() == encoding)
+        .ok_or_else(|| JsError::new(&format!("unknown encoding {encoding:?}")))?;
+    Ok(js_text(shamir_gf256::share_encode(
+        &share.0, ecc_len, encoding,
+    )?))
+}
+
+/// Decode a share in any supported encoding, detecting which one and
+/// repairing damage up to its RS capacity.
+#[wasm_bindgen(js_name = shareDecode)]
+pub fn share_decode(text: &str) -> Result<Share, JsError> {
+    Ok(Share(shamir_gf256::share_decode(text)?.share))
+}
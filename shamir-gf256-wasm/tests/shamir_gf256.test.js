@@ -0,0 +1,69 @@
+// Run from shamir-gf256-wasm/:
+//   wasm-pack build --target nodejs && node --test tests/
+
+const test = require("node:test");
+const assert = require("node:assert/strict");
+const sss = require("../pkg/shamir_gf256_wasm.js");
+
+const secret = new TextEncoder().encode("correct horse battery staple");
+
+test("split and combine with any threshold subset", () => {
+  const shares = sss.split(secret, 3, 5);
+  assert.equal(shares.length, 5);
+  assert.ok(shares.every((s) => /^[0-9a-f]+$/.test(s)));
+  assert.deepEqual(sss.combine(shares.slice(2)), secret);
+  assert.deepEqual(sss.combine([shares[4], shares[0], shares[2]]), secret);
+  // More shares than the threshold still lie on the same polynomial.
+  assert.deepEqual(sss.combine(shares), secret);
+  assert.deepEqual(sss.combine(shares, 3), secret);
+});
+
+test("a surplus share from another split is reported, not used", () => {
+  const ours = sss.split(secret, 2, 4);
+  const theirs = sss.split(secret, 2, 4);
+  assert.deepEqual(sss.combine([ours[0], ours[1], ours[2]], 2), secret);
+  assert.throws(
+    () => sss.combine([ours[0], ours[1], theirs[3]], 2),
+    /share\(s\) 4 do not lie on the polynomial/,
+  );
+});
+
+test("combine reads every share format and repairs damage", () => {
+  const shares = sss.split(secret, 2, 3);
+  const words = sss.shareDecode(shares[1]).encode("words");
+  const last = shares[0].length - 1;
+  const damaged = shares[0].slice(0, last) + (shares[0][last] === "0" ? "1" : "0");
+  assert.deepEqual(sss.combine([damaged, words]), secret);
+});
+
+test("bad parameters and shares throw", () => {
+  assert.throws(() => sss.split(secret, 4, 3));
+  assert.throws(() => sss.split(secret, 0, 3));
+  assert.throws(() => sss.split(secret, 2, 3, 1), /eccLen/);
+  assert.throws(() => sss.combine([]));
+  assert.throws(() => sss.combine(["not a share"]), /share 1/);
+  const [a] = sss.split(secret, 2, 3);
+  assert.throws(() => sss.combine([a, a]));
+  assert.throws(() => new sss.Share(0, new Uint8Array([1])));
+});
+
+test("codec functions round-trip a Share", () => {
+  const share = new sss.Share(7, new Uint8Array([0xaa, 0xbb, 0xcc]));
+  assert.equal(share.x, 7);
+  assert.deepEqual(share.y, new Uint8Array([0xaa, 0xbb, 0xcc]));
+
+  const hex = sss.shareToHex(share);
+  assert.equal(share.toHex(), hex);
+  assert.equal(sss.shareToHex(share, 32).length > hex.length, true);
+  const back = sss.shareFromHex(hex);
+  assert.equal(back.x, 7);
+  assert.deepEqual(back.y, share.y);
+
+  for (const encoding of ["hex", "base32", "base58check", "base64url", "bech32m", "words"]) {
+    const text = sss.shareEncode(share, encoding);
+    assert.deepEqual(sss.shareDecode(text).y, share.y, encoding);
+  }
+  assert.throws(() => sss.shareEncode(share, "rot13"), /unknown encoding/);
+  assert.throws(() => sss.shareFromHex("zz"));
+  share.free();
+});
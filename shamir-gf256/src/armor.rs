@@ -0,0 +1,348 @@
+/*
+PEM/PGP-style ASCII armor for a single share, for email and ticket attachments.
+
+  -----BEGIN SSS SHARE-----
+  Set-Id: 7f3c2a
+  Share-Index: 3
+  Threshold: 3
+  Share-Count: 5
+  Created-At: 2026-10-18T09:30:00Z
+  Custodian: Alice (ops)
+
+  U0hSMQMQBQAAAGhlbGxv...              // base64 of the SHR1/SHR2 frame, 64 cols
+  =kYx3                                 // base64 of the CRC-24 of the frame
+  -----END SSS SHARE-----
+
+Headers are informational and not covered by the CRC; `Share-Index` is checked
+against the decoded x. The parser skips any prose around the block and strips
+email quoting (`>`, `|`) and indentation from every line.
+*/
+
+use crate::share_codec::{CorrectionReport, ShareCodecError, decode_frame, encode_frame};
+use crate::{Share, ShareSetMeta};
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use zeroize::Zeroizing;
+
+const BEGIN: &str = "-----BEGIN SSS SHARE-----";
+const END: &str = "-----END SSS SHARE-----";
+const LINE_WIDTH: usize = 64;
+
+/// Readable header lines carried by an armored share.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ArmorHeaders {
+    /// Identifier shared by all shares of one split.
+    pub set_id: Option<String>,
+    /// `Threshold` / `Share-Count`.
+    pub meta: Option<ShareSetMeta>,
+    /// Creation time; RFC 3339 is recommended but not enforced.
+    pub created_at: Option<String>,
+    /// Who holds this share.
+    pub custodian: Option<String>,
+    /// Any other `Key: Value` lines, in order.
+    pub other: Vec<(String, String)>,
+}
+
+/// Frame `share` with `ecc_len` RS parity bytes and wrap it in armor.
+///
+/// Fails if a header value contains a line break or a key is not a plain token.
+pub fn share_to_armor(
+    share: &Share,
+    ecc_len: usize,
+    headers: &ArmorHeaders,
+) -> Result<String, ShareCodecError> {
+    let frame = encode_frame(share, ecc_len);
+
+    let mut lines: Vec<(String, String)> = Vec::new();
+    if let Some(id) = &headers.set_id {
+        lines.push(("Set-Id".into(), id.clone()));
+    }
+    lines.push(("Share-Index".into(), share.x.0.to_string()));
+    if let Some(meta) = headers.meta {
+        lines.push(("Threshold".into(), meta.threshold.to_string()));
+        lines.push(("Share-Count".into(), meta.share_count.to_string()));
+    }
+    if let Some(t) = &headers.created_at {
+        lines.push(("Created-At".into(), t.clone()));
+    }
+    if let Some(c) = &headers.custodian {
+        lines.push(("Custodian".into(), c.clone()));
+    }
+    lines.extend(headers.other.iter().cloned());
+
+    let mut out = String::new();
+    out.push_str(BEGIN);
+    out.push('\n');
+    for (key, value) in &lines {
+        let key_ok = !key.is_empty() && key.bytes().all(|b| b.is_ascii_alphanumeric() || b == b'-');
+        if !key_ok || value.chars().any(char::is_control) {
+            return Err(ShareCodecError::Armor(
+                "header must be a single `Key: Value` line",
+            ));
+        }
+        out.push_str(&format!("{key}: {}\n", value.trim()));
+    }
+    out.push('\n');
+
+    let body = Zeroizing::new(STANDARD.encode(&frame[..]));
+    for chunk in body.as_bytes().chunks(LINE_WIDTH) {
+        out.push_str(core::str::from_utf8(chunk).expect("base64 is ASCII"));
+        out.push('\n');
+    }
+    out.push('=');
+    out.push_str(&STANDARD.encode(&crc24(&frame).to_be_bytes()[1..]));
+    out.push('\n');
+    out.push_str(END);
+    out.push('\n');
+    Ok(out)
+}
+
+/// Decode the first armored share found in `text`.
+pub fn share_from_armor(text: &str) -> Result<(Share, ArmorHeaders), ShareCodecError> {
+    share_from_armor_with_report(text).map(|(share, headers, _)| (share, headers))
+}
+
+/// Like [`share_from_armor`], but also reports RS repairs.
+pub fn share_from_armor_with_report(
+    text: &str,
+) -> Result<(Share, ArmorHeaders, CorrectionReport), ShareCodecError> {
+    let mut lines = text.lines().map(unquote);
+    if !lines.by_ref().any(|l| l == BEGIN) {
+        return Err(ShareCodecError::Armor("missing BEGIN SSS SHARE line"));
+    }
+
+    // Header block: `Key: Value` lines up to the first blank line. A block
+    // without headers starts directly with base64, which never contains `:`.
+    let mut headers = ArmorHeaders::default();
+    let mut index: Option<u8> = None;
+    let mut threshold: Option<u8> = None;
+    let mut share_count: Option<u8> = None;
+    let mut body = Zeroizing::new(String::new());
+    for line in lines.by_ref() {
+        if line.is_empty() {
+            break;
+        }
+        let Some((key, value)) = line.split_once(':') else {
+            body.push_str(line);
+            break;
+        };
+        let value = value.trim().to_string();
+        let number = |v: &str| {
+            v.parse::<u8>()
+                .map_err(|_| ShareCodecError::Armor("header is not a number in 0..=255"))
+        };
+        match key.trim() {
+            "Set-Id" => headers.set_id = Some(value),
+            "Share-Index" => index = Some(number(&value)?),
+            "Threshold" => threshold = Some(number(&value)?),
+            "Share-Count" => share_count = Some(number(&value)?),
+            "Created-At" => headers.created_at = Some(value),
+            "Custodian" => headers.custodian = Some(value),
+            other => headers.other.push((other.to_string(), value)),
+        }
+    }
+    headers.meta = match (threshold, share_count) {
+        (None, None) => None,
+        (Some(t), Some(n)) => Some(
+            ShareSetMeta::new(t, n)
+                .ok_or(ShareCodecError::Armor("threshold exceeds share count"))?,
+        ),
+        _ => {
+            return Err(ShareCodecError::Armor(
+                "Threshold and Share-Count go together",
+            ));
+        }
+    };
+
+    // Body lines, then `=CRC`, then END.
+    let mut crc_line: Option<&str> = None;
+    let mut ended = false;
+    for line in lines {
+        if line == END {
+            ended = true;
+            break;
+        }
+        if let Some(crc) = line.strip_prefix('=') {
+            crc_line = Some(crc);
+        } else if crc_line.is_none() {
+            body.push_str(line);
+        } else if !line.is_empty() {
+            return Err(ShareCodecError::Armor("data after the CRC line"));
+        }
+    }
+    if !ended {
+        return Err(ShareCodecError::Armor("missing END SSS SHARE line"));
+    }
+
+    let frame = Zeroizing::new(
+        STANDARD
+            .decode(body.as_bytes())
+            .map_err(|_| ShareCodecError::Encoding("armor base64"))?,
+    );
+    let crc_line = crc_line.ok_or(ShareCodecError::Armor("missing CRC line"))?;
+    let crc = STANDARD
+        .decode(crc_line)
+        .ok()
+        .filter(|c| c.len() == 3)
+        .ok_or(ShareCodecError::Armor("malformed CRC line"))?;
+    let crc = u32::from_be_bytes([0, crc[0], crc[1], crc[2]]);
+    let crc_ok = crc == crc24(&frame);
+
+    // RS restores the bytes; the CRC then vouches for the result. Damage past
+    // the RS capacity can "correct" to a different share, so a repaired frame
+    // must re-encode to one that matches the CRC.
+    let (share, report) = match decode_frame(&frame, &[]) {
+        Ok(decoded) => decoded,
+        Err(_) if !crc_ok => return Err(ShareCodecError::Checksum("armor crc24")),
+        Err(e) => return Err(e),
+    };
+    if !crc_ok && crc24(&encode_frame(&share, report.ecc_len)) != crc {
+        return Err(ShareCodecError::Checksum("armor crc24"));
+    }
+    if index.is_some_and(|i| i != share.x.0) {
+        return Err(ShareCodecError::Armor(
+            "Share-Index does not match the share",
+        ));
+    }
+    Ok((share, headers, report))
+}
+
+/// Strip email quoting (`>`, `|`) and surrounding whitespace from a line.
+fn unquote(line: &str) -> &str {
+    line.trim_start_matches(|c: char| c == '>' || c == '|' || c.is_whitespace())
+        .trim_end()
+}
+
+/// CRC-24 as used by OpenPGP armor (RFC 4880 §6.1).
+fn crc24(data: &[u8]) -> u32 {
+    const INIT: u32 = 0x00B7_04CE;
+    const POLY: u32 = 0x0186_4CFB;
+    let mut crc = INIT;
+    for &byte in data {
+        crc ^= (byte as u32) << 16;
+        for _ in 0..8 {
+            crc <<= 1;
+            if crc & 0x0100_0000 != 0 {
+                crc ^= POLY;
+            }
+        }
+    }
+    crc & 0x00FF_FFFF
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{GF256, split};
+
+    fn headers() -> ArmorHeaders {
+        ArmorHeaders {
+            set_id: Some("7f3c2a".into()),
+            meta: ShareSetMeta::new(3, 5),
+            created_at: Some("2026-10-18T09:30:00Z".into()),
+            custodian: Some("Alice (ops)".into()),
+            other: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn crc24_reference_value() {
+        // RFC 4880 CRC-24 of "123456789"
+        assert_eq!(crc24(b"123456789"), 0x21CF02);
+    }
+
+    #[test]
+    fn armor_round_trip() {
+        let shares = split(&[0x42; 200], 3, 5);
+        let text = share_to_armor(&shares[2], 16, &headers()).unwrap();
+        assert!(text.starts_with(BEGIN));
+        assert!(text.contains("\nShare-Index: 3\n"));
+        assert!(
+            text.lines()
+                .all(|l| l.len() <= LINE_WIDTH || l.starts_with("-----"))
+        );
+
+        let (share, parsed) = share_from_armor(&text).unwrap();
+        assert_eq!(share, shares[2]);
+        assert_eq!(parsed, headers());
+    }
+
+    #[test]
+    fn tolerates_email_quoting_and_prose() {
+        let sh = Share {
+            x: GF256(4),
+            y: b"quoted reply".to_vec(),
+        };
+        let text = share_to_armor(&sh, 8, &headers()).unwrap();
+        let quoted: String = text.lines().map(|l| format!(">  > {l}\r\n")).collect();
+        let email =
+            format!("Hi team,\n\nhere is my share as requested:\n\n{quoted}\nThanks,\nBob\n");
+        let (share, parsed) = share_from_armor(&email).unwrap();
+        assert_eq!(share, sh);
+        assert_eq!(parsed.custodian.as_deref(), Some("Alice (ops)"));
+    }
+
+    #[test]
+    fn crc_and_header_checks() {
+        let sh = Share {
+            x: GF256(1),
+            y: b"crc".to_vec(),
+        };
+        let text = share_to_armor(&sh, 4, &ArmorHeaders::default()).unwrap();
+
+        let wrong_index = text.replace("Share-Index: 1", "Share-Index: 2");
+        assert!(matches!(
+            share_from_armor(&wrong_index),
+            Err(ShareCodecError::Armor(_))
+        ));
+
+        let bare: String = text
+            .lines()
+            .filter(|l| !l.contains(": ") && !l.is_empty())
+            .map(|l| format!("{l}\n"))
+            .collect();
+        assert_eq!(share_from_armor(&bare).unwrap().0, sh);
+
+        let no_end = text.replace(END, "");
+        assert!(share_from_armor(&no_end).is_err());
+
+        let bad = ArmorHeaders {
+            custodian: Some("two\nlines".into()),
+            ..ArmorHeaders::default()
+        };
+        assert!(share_to_armor(&sh, 4, &bad).is_err());
+    }
+
+    #[test]
+    fn repairs_are_checked_against_the_crc() {
+        let sh = Share {
+            x: GF256(1),
+            y: b"crc".to_vec(),
+        };
+        let text = share_to_armor(&sh, 4, &ArmorHeaders::default()).unwrap();
+        let body = STANDARD.encode(encode_frame(&sh, 4));
+
+        // Within the RS budget the repaired frame matches the CRC again.
+        let mut frame = encode_frame(&sh, 4);
+        frame[11] ^= 0x5a;
+        let repaired = text.replace(&body, &STANDARD.encode(&frame));
+        assert_eq!(share_from_armor(&repaired).unwrap().0, sh);
+
+        // Damage past the budget that lands one repair away from another
+        // share: RS "corrects" to that share, the CRC refuses it.
+        let other = Share {
+            x: GF256(1),
+            y: b"CRC".to_vec(),
+        };
+        let mut frame = encode_frame(&other, 4);
+        frame[11] ^= 0x5a;
+        let miscorrected = text.replace(&body, &STANDARD.encode(&frame));
+        assert!(matches!(
+            share_from_armor(&miscorrected),
+            Err(ShareCodecError::Checksum("armor crc24"))
+        ));
+    }
+}
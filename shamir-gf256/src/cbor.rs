@@ -0,0 +1,261 @@
+/*
+CBOR encoding of a share frame (behind the `cbor` feature), for NFC/QR-sized
+payloads and machine-to-machine transport. A definite-length map with integer
+keys, so it can be used directly as a COSE payload:
+
+   1: x            uint
+   2: ecc_len      uint
+   3: blocks       uint    // only for SHR2 frames
+   4: y_len        uint
+   5: code         bstr    // RS code region, exactly as in the SHR1/SHR2 frame
+  10: set_id       tstr    // optional metadata, same fields as the armor headers
+  11: threshold    uint
+  12: share_count  uint
+  13: created_at   tstr
+  14: custodian    tstr
+  "<key>": tstr            // other headers
+
+Keys 1-5 carry the frame losslessly: `frame_to_cbor` and `cbor_to_frame`
+convert existing SHR1/SHR2 bytes without RS-decoding them. Unknown integer keys
+are ignored so later versions can add fields.
+*/
+
+use crate::share_codec::{
+    ArmorHeaders, Frame, ShareCodecError, decode_frame, encode_frame, parse_frame,
+};
+use crate::{GF256, Share, ShareSetMeta};
+use alloc::string::String;
+use alloc::vec::Vec;
+use ciborium::value::{Integer, Value};
+use zeroize::Zeroizing;
+
+const KEY_X: u8 = 1;
+const KEY_ECC: u8 = 2;
+const KEY_BLOCKS: u8 = 3;
+const KEY_Y_LEN: u8 = 4;
+const KEY_CODE: u8 = 5;
+const KEY_SET_ID: u8 = 10;
+const KEY_THRESHOLD: u8 = 11;
+const KEY_SHARE_COUNT: u8 = 12;
+const KEY_CREATED_AT: u8 = 13;
+const KEY_CUSTODIAN: u8 = 14;
+
+/// Frame `share` with `ecc_len` RS parity bytes and encode it as CBOR.
+pub fn share_to_cbor(share: &Share, ecc_len: usize, headers: &ArmorHeaders) -> Vec<u8> {
+    frame_to_cbor(&encode_frame(share, ecc_len), headers).expect("freshly encoded frame")
+}
+
+/// Decode and RS-correct a CBOR share.
+pub fn share_from_cbor(bytes: &[u8]) -> Result<(Share, ArmorHeaders), ShareCodecError> {
+    let (frame, headers) = cbor_to_frame(bytes)?;
+    let (share, _) = decode_frame(&frame, &[])?;
+    Ok((share, headers))
+}
+
+/// Re-encode existing `SHR1`/`SHR2` frame bytes as CBOR.
+pub fn frame_to_cbor(frame: &[u8], headers: &ArmorHeaders) -> Result<Vec<u8>, ShareCodecError> {
+    let f = parse_frame(frame)?;
+
+    let mut map: Vec<(Value, Value)> = Vec::new();
+    let mut put = |key: u8, value: Value| map.push((Value::Integer(key.into()), value));
+    put(KEY_X, Value::Integer(f.x.0.into()));
+    put(KEY_ECC, Value::Integer((f.ecc_len as u8).into()));
+    if f.interleaved {
+        put(KEY_BLOCKS, Value::Integer((f.blocks as u32).into()));
+    }
+    put(KEY_Y_LEN, Value::Integer((f.y_len as u32).into()));
+    put(KEY_CODE, Value::Bytes(f.code.to_vec()));
+    if let Some(id) = &headers.set_id {
+        put(KEY_SET_ID, Value::Text(id.clone()));
+    }
+    if let Some(meta) = headers.meta {
+        put(KEY_THRESHOLD, Value::Integer(meta.threshold.into()));
+        put(KEY_SHARE_COUNT, Value::Integer(meta.share_count.into()));
+    }
+    if let Some(t) = &headers.created_at {
+        put(KEY_CREATED_AT, Value::Text(t.clone()));
+    }
+    if let Some(c) = &headers.custodian {
+        put(KEY_CUSTODIAN, Value::Text(c.clone()));
+    }
+    for (k, v) in &headers.other {
+        map.push((Value::Text(k.clone()), Value::Text(v.clone())));
+    }
+
+    let mut value = Value::Map(map);
+    let mut out = Vec::new();
+    let written = ciborium::into_writer(&value, &mut out);
+    wipe_code(&mut value);
+    written.map_err(|_| ShareCodecError::Cbor("serialization failed"))?;
+    Ok(out)
+}
+
+/// Convert a CBOR share back into `SHR1`/`SHR2` frame bytes plus metadata.
+pub fn cbor_to_frame(bytes: &[u8]) -> Result<(Zeroizing<Vec<u8>>, ArmorHeaders), ShareCodecError> {
+    let mut value: Value =
+        ciborium::from_reader(bytes).map_err(|_| ShareCodecError::Cbor("not valid CBOR"))?;
+    let result = frame_from_value(&value);
+    wipe_code(&mut value);
+    result
+}
+
+fn frame_from_value(value: &Value) -> Result<(Zeroizing<Vec<u8>>, ArmorHeaders), ShareCodecError> {
+    let map = value
+        .as_map()
+        .ok_or(ShareCodecError::Cbor("expected a map"))?;
+
+    let mut x = None;
+    let mut ecc_len = None;
+    let mut blocks = None;
+    let mut y_len = None;
+    let mut code: Option<&[u8]> = None;
+    let mut threshold = None;
+    let mut share_count = None;
+    let mut headers = ArmorHeaders::default();
+
+    for (k, v) in map {
+        if let Some(key) = k.as_text() {
+            let v = v
+                .as_text()
+                .ok_or(ShareCodecError::Cbor("header values must be text"))?;
+            headers.other.push((key.into(), v.into()));
+            continue;
+        }
+        let Some(key) = k.as_integer().and_then(|i| u8::try_from(i).ok()) else {
+            continue;
+        };
+        match key {
+            KEY_X => x = Some(uint::<u8>(v)?),
+            KEY_ECC => ecc_len = Some(uint::<u8>(v)?),
+            KEY_BLOCKS => blocks = Some(uint::<u32>(v)?),
+            KEY_Y_LEN => y_len = Some(uint::<u32>(v)?),
+            KEY_CODE => {
+                code = Some(
+                    v.as_bytes()
+                        .ok_or(ShareCodecError::Cbor("code must be bytes"))?,
+                )
+            }
+            KEY_SET_ID => headers.set_id = Some(text(v)?),
+            KEY_THRESHOLD => threshold = Some(uint::<u8>(v)?),
+            KEY_SHARE_COUNT => share_count = Some(uint::<u8>(v)?),
+            KEY_CREATED_AT => headers.created_at = Some(text(v)?),
+            KEY_CUSTODIAN => headers.custodian = Some(text(v)?),
+            _ => {}
+        }
+    }
+
+    headers.meta = match (threshold, share_count) {
+        (None, None) => None,
+        (Some(t), Some(n)) => Some(
+            ShareSetMeta::new(t, n)
+                .ok_or(ShareCodecError::Cbor("threshold exceeds share count"))?,
+        ),
+        _ => {
+            return Err(ShareCodecError::Cbor(
+                "threshold and share_count go together",
+            ));
+        }
+    };
+
+    let code = code.ok_or(ShareCodecError::Cbor("missing code"))?;
+    let frame = Frame {
+        interleaved: blocks.is_some(),
+        x: GF256(x.ok_or(ShareCodecError::Cbor("missing x"))?),
+        ecc_len: ecc_len.ok_or(ShareCodecError::Cbor("missing ecc_len"))? as usize,
+        blocks: blocks.unwrap_or(1) as usize,
+        y_len: y_len.ok_or(ShareCodecError::Cbor("missing y_len"))? as usize,
+        code_start: 0,
+        code,
+    }
+    .to_bytes();
+
+    // Validates the header fields against the code length.
+    parse_frame(&frame)?;
+    Ok((frame, headers))
+}
+
+fn uint<T: TryFrom<Integer>>(v: &Value) -> Result<T, ShareCodecError> {
+    v.as_integer()
+        .and_then(|i| T::try_from(i).ok())
+        .ok_or(ShareCodecError::Cbor("integer out of range"))
+}
+
+fn text(v: &Value) -> Result<String, ShareCodecError> {
+    v.as_text()
+        .map(String::from)
+        .ok_or(ShareCodecError::Cbor("expected text"))
+}
+
+/// Zeroize the code bytes held by a parsed or built map.
+fn wipe_code(value: &mut Value) {
+    use zeroize::Zeroize;
+    if let Value::Map(entries) = value {
+        for (_, v) in entries {
+            if let Value::Bytes(b) = v {
+                b.zeroize();
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{share_to_bytes, split};
+
+    #[test]
+    fn round_trips_shr1_frames() {
+        let shares = split(b"nfc tag", 2, 3);
+        let headers = ArmorHeaders {
+            set_id: Some("set-1".into()),
+            meta: ShareSetMeta::new(2, 3),
+            custodian: Some("Carol".into()),
+            other: alloc::vec![("Site".into(), "Vault B".into())],
+            ..ArmorHeaders::default()
+        };
+        for sh in &shares {
+            let frame = share_to_bytes(sh, 8);
+            let bare = frame_to_cbor(&frame, &ArmorHeaders::default()).unwrap();
+            assert!(bare.len() <= frame.len() + 8);
+
+            let cbor = frame_to_cbor(&frame, &headers).unwrap();
+
+            let (back, parsed) = cbor_to_frame(&cbor).unwrap();
+            assert_eq!(&back[..], &frame[..]);
+            assert_eq!(parsed, headers);
+
+            let (decoded, _) = share_from_cbor(&share_to_cbor(sh, 8, &headers)).unwrap();
+            assert_eq!(&decoded, sh);
+        }
+    }
+
+    #[test]
+    fn round_trips_shr2_frames() {
+        let sh = Share {
+            x: GF256(5),
+            y: alloc::vec![7; 600],
+        };
+        let frame = share_to_bytes(&sh, 16);
+        assert_eq!(&frame[..4], b"SHR2");
+        let cbor = frame_to_cbor(&frame, &ArmorHeaders::default()).unwrap();
+        assert_eq!(&cbor_to_frame(&cbor).unwrap().0[..], &frame[..]);
+    }
+
+    #[test]
+    fn rejects_inconsistent_maps() {
+        let sh = Share {
+            x: GF256(1),
+            y: b"abc".to_vec(),
+        };
+        let mut value: Value =
+            ciborium::from_reader(&share_to_cbor(&sh, 4, &ArmorHeaders::default())[..]).unwrap();
+        if let Value::Map(entries) = &mut value {
+            entries.retain(|(k, _)| k.as_integer() != Some(KEY_Y_LEN.into()));
+            entries.push((Value::Integer(KEY_Y_LEN.into()), Value::Integer(9.into())));
+        }
+        let mut bytes = Vec::new();
+        ciborium::into_writer(&value, &mut bytes).unwrap();
+        assert!(share_from_cbor(&bytes).is_err());
+        assert!(share_from_cbor(b"\x01").is_err());
+    }
+}
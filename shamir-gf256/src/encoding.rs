@@ -0,0 +1,505 @@
+/*
+Text encodings for share frames (the `SHR1`/`SHR2` bytes built by `share_codec`).
+
+  Hex             lowercase hex; `?` marks an unreadable digit (RS erasure)
+  CrockfordBase32 uppercase, no padding; decoding ignores case and `-`,
+                  and reads I/L as 1 and O as 0
+  Base58Check     Bitcoin alphabet + 4-byte double-SHA-256 checksum
+  Base64Url       URL-safe alphabet, no padding (QR / URL friendly)
+  Bech32m         `sss1...`; BCH checksum detects typos (max 1023 chars)
+  Words           BIP-39 English words, 11 bits each; `?` or an unknown word
+                  marks the bytes it covers as RS erasures
+
+`share_decode` detects the encoding: it tries each known encoding in turn and
+takes the first one whose output starts with a frame magic (`SHR1`/`SHR2`).
+*/
+
+use crate::Share;
+use crate::share_codec::{
+    CorrectionReport, ShareCodecError, decode_frame, decode_hex_with_erasures, encode_frame,
+    frame_len, is_frame, is_wrapped,
+};
+use alloc::string::String;
+use alloc::vec::Vec;
+use base64::Engine;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use bech32::primitives::decode::CheckedHrpstring;
+use bech32::{Bech32m as Bech32mChecksum, Hrp};
+use bip39::Language;
+use zeroize::{Zeroize, Zeroizing};
+
+/// A reversible text form for share frames.
+pub trait ShareEncoding {
+    /// Short, stable name (e.g. `"hex"`), used in reports and errors.
+    fn name(&self) -> &'static str;
+
+    /// Encode raw frame bytes as text.
+    fn encode(&self, frame: &[u8]) -> Result<String, ShareCodecError>;
+
+    /// Decode text back into frame bytes.
+    fn decode(&self, s: &str) -> Result<Zeroizing<Vec<u8>>, ShareCodecError>;
+
+    /// Decode, also returning frame byte indices known to be unreadable.
+    /// Encodings without an erasure syntax report none.
+    fn decode_with_erasures(
+        &self,
+        s: &str,
+    ) -> Result<(Zeroizing<Vec<u8>>, Vec<usize>), ShareCodecError> {
+        Ok((self.decode(s)?, Vec::new()))
+    }
+}
+
+/// Lowercase hex, the format produced by `share_to_hex`.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Hex;
+
+/// Crockford base32 (`0-9A-Z` without I, L, O, U).
+#[derive(Clone, Copy, Debug, Default)]
+pub struct CrockfordBase32;
+
+/// Base58 with a 4-byte double-SHA-256 checksum.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Base58Check;
+
+/// URL-safe base64 without padding.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Base64Url;
+
+/// Bech32m with the `sss` human-readable prefix.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Bech32m;
+
+/// Space-separated BIP-39 English words, for reading aloud or writing by hand.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Words;
+
+/// Every built-in encoding, in auto-detection order.
+pub const ENCODINGS: &[&dyn ShareEncoding] = &[
+    &Bech32m,
+    &Hex,
+    &CrockfordBase32,
+    &Base64Url,
+    &Base58Check,
+    &Words,
+];
+
+const CROCKFORD: &[u8; 32] = b"0123456789ABCDEFGHJKMNPQRSTVWXYZ";
+const BECH32_HRP: &str = "sss";
+
+impl ShareEncoding for Hex {
+    fn name(&self) -> &'static str {
+        "hex"
+    }
+
+    fn encode(&self, frame: &[u8]) -> Result<String, ShareCodecError> {
+        Ok(hex::encode(frame))
+    }
+
+    fn decode(&self, s: &str) -> Result<Zeroizing<Vec<u8>>, ShareCodecError> {
+        Ok(Zeroizing::new(hex::decode(s)?))
+    }
+
+    fn decode_with_erasures(
+        &self,
+        s: &str,
+    ) -> Result<(Zeroizing<Vec<u8>>, Vec<usize>), ShareCodecError> {
+        decode_hex_with_erasures(s)
+    }
+}
+
+impl ShareEncoding for CrockfordBase32 {
+    fn name(&self) -> &'static str {
+        "base32"
+    }
+
+    fn encode(&self, frame: &[u8]) -> Result<String, ShareCodecError> {
+        let mut out = String::with_capacity(frame.len().div_ceil(5) * 8);
+        let mut acc: u16 = 0;
+        let mut bits = 0;
+        for &byte in frame {
+            acc = (acc << 8) | byte as u16;
+            bits += 8;
+            while bits >= 5 {
+                bits -= 5;
+                out.push(CROCKFORD[((acc >> bits) & 0x1F) as usize] as char);
+            }
+        }
+        if bits > 0 {
+            out.push(CROCKFORD[((acc << (5 - bits)) & 0x1F) as usize] as char);
+        }
+        acc.zeroize();
+        Ok(out)
+    }
+
+    fn decode(&self, s: &str) -> Result<Zeroizing<Vec<u8>>, ShareCodecError> {
+        let mut out = Zeroizing::new(Vec::with_capacity(s.len() * 5 / 8));
+        let mut acc: u16 = 0;
+        let mut bits = 0;
+        for c in s.bytes() {
+            let value = match c.to_ascii_uppercase() {
+                b'-' => continue,
+                b'O' => 0,
+                b'I' | b'L' => 1,
+                c => CROCKFORD
+                    .iter()
+                    .position(|&a| a == c)
+                    .ok_or(ShareCodecError::Encoding("base32"))? as u16,
+            };
+            acc = (acc << 5) | value;
+            bits += 5;
+            if bits >= 8 {
+                bits -= 8;
+                out.push((acc >> bits) as u8);
+            }
+        }
+        // Leftover bits are padding and must be zero.
+        let leftover = acc & ((1 << bits) - 1);
+        acc.zeroize();
+        if bits >= 5 || leftover != 0 {
+            return Err(ShareCodecError::Encoding("base32"));
+        }
+        Ok(out)
+    }
+}
+
+impl ShareEncoding for Base58Check {
+    fn name(&self) -> &'static str {
+        "base58check"
+    }
+
+    fn encode(&self, frame: &[u8]) -> Result<String, ShareCodecError> {
+        Ok(bs58::encode(frame).with_check().into_string())
+    }
+
+    fn decode(&self, s: &str) -> Result<Zeroizing<Vec<u8>>, ShareCodecError> {
+        match bs58::decode(s).with_check(None).into_vec() {
+            Ok(v) => Ok(Zeroizing::new(v)),
+            Err(bs58::decode::Error::InvalidChecksum { .. }) => {
+                Err(ShareCodecError::Checksum("base58check"))
+            }
+            Err(_) => Err(ShareCodecError::Encoding("base58check")),
+        }
+    }
+}
+
+impl ShareEncoding for Base64Url {
+    fn name(&self) -> &'static str {
+        "base64url"
+    }
+
+    fn encode(&self, frame: &[u8]) -> Result<String, ShareCodecError> {
+        Ok(URL_SAFE_NO_PAD.encode(frame))
+    }
+
+    fn decode(&self, s: &str) -> Result<Zeroizing<Vec<u8>>, ShareCodecError> {
+        URL_SAFE_NO_PAD
+            .decode(s)
+            .map(Zeroizing::new)
+            .map_err(|_| ShareCodecError::Encoding("base64url"))
+    }
+}
+
+impl ShareEncoding for Bech32m {
+    fn name(&self) -> &'static str {
+        "bech32m"
+    }
+
+    fn encode(&self, frame: &[u8]) -> Result<String, ShareCodecError> {
+        let hrp = Hrp::parse_unchecked(BECH32_HRP);
+        bech32::encode_lower::<Bech32mChecksum>(hrp, frame).map_err(|_| ShareCodecError::Overflow)
+    }
+
+    fn decode(&self, s: &str) -> Result<Zeroizing<Vec<u8>>, ShareCodecError> {
+        let checked = CheckedHrpstring::new::<Bech32mChecksum>(s).map_err(|e| {
+            use bech32::primitives::decode::CheckedHrpstringError;
+            match e {
+                CheckedHrpstringError::Checksum(_) => ShareCodecError::Checksum("bech32m"),
+                _ => ShareCodecError::Encoding("bech32m"),
+            }
+        })?;
+        if !checked.hrp().as_str().eq_ignore_ascii_case(BECH32_HRP) {
+            return Err(ShareCodecError::Encoding("bech32m"));
+        }
+        Ok(Zeroizing::new(checked.byte_iter().collect()))
+    }
+}
+
+impl ShareEncoding for Words {
+    fn name(&self) -> &'static str {
+        "words"
+    }
+
+    fn encode(&self, frame: &[u8]) -> Result<String, ShareCodecError> {
+        let list = Language::English.word_list();
+        let mut out = String::new();
+        let mut acc: u32 = 0;
+        let mut bits = 0;
+        let push = |out: &mut String, idx: u32| {
+            if !out.is_empty() {
+                out.push(' ');
+            }
+            out.push_str(list[idx as usize & 0x7FF]);
+        };
+        for &byte in frame {
+            acc = (acc << 8) | byte as u32;
+            bits += 8;
+            if bits >= 11 {
+                bits -= 11;
+                push(&mut out, acc >> bits);
+            }
+        }
+        if bits > 0 {
+            push(&mut out, acc << (11 - bits));
+        }
+        acc.zeroize();
+        Ok(out)
+    }
+
+    fn decode(&self, s: &str) -> Result<Zeroizing<Vec<u8>>, ShareCodecError> {
+        let (bytes, erasures) = self.decode_with_erasures(s)?;
+        if !erasures.is_empty() {
+            return Err(ShareCodecError::Encoding("words"));
+        }
+        Ok(bytes)
+    }
+
+    fn decode_with_erasures(
+        &self,
+        s: &str,
+    ) -> Result<(Zeroizing<Vec<u8>>, Vec<usize>), ShareCodecError> {
+        let tokens: Vec<&str> = s.split_whitespace().collect();
+        if tokens.len() < 2 {
+            return Err(ShareCodecError::Encoding("words"));
+        }
+
+        let mut out = Zeroizing::new(Vec::with_capacity(tokens.len() * 11 / 8));
+        let mut erasures: Vec<usize> = Vec::new();
+        let mut acc: u32 = 0;
+        let mut bits = 0;
+        for (i, token) in tokens.iter().enumerate() {
+            let index = find_word(token);
+            if index.is_none() {
+                // This word covers bits 11*i .. 11*i+11 of the frame.
+                let first = 11 * i / 8;
+                let last = (11 * i + 10) / 8;
+                for b in first..=last {
+                    if erasures.last() != Some(&b) {
+                        erasures.push(b);
+                    }
+                }
+            }
+            acc = (acc << 11) | index.unwrap_or(0) as u32;
+            bits += 11;
+            while bits >= 8 {
+                bits -= 8;
+                out.push((acc >> bits) as u8);
+            }
+        }
+        acc.zeroize();
+
+        // The last word may carry 8..=10 padding bits, which decode to one
+        // surplus zero byte; the frame header says where the frame ends.
+        if let Some(len) = frame_len(&out)
+            && len + 1 == out.len()
+        {
+            out.truncate(len);
+            erasures.retain(|&e| e < len);
+        }
+        erasures.retain(|&e| e < out.len());
+        Ok((out, erasures))
+    }
+}
+
+/// Look up a BIP-39 word, accepting any unique prefix of at least 4 letters
+/// (every English BIP-39 word is determined by its first four).
+fn find_word(token: &str) -> Option<u16> {
+    if token == "?" {
+        return None;
+    }
+    let word = token.to_ascii_lowercase();
+    let english = Language::English;
+    english.find_word(&word).or_else(|| {
+        if word.len() < 4 {
+            return None;
+        }
+        match english.words_by_prefix(&word[..4]) {
+            [only] => english.find_word(only),
+            _ => None,
+        }
+    })
+}
+
+/// Result of [`share_decode`].
+#[derive(Clone, Debug)]
+pub struct DecodedShare {
+    pub share: Share,
+    /// Name of the detected encoding (see [`ShareEncoding::name`]).
+    pub encoding: &'static str,
+    pub report: CorrectionReport,
+}
+
+/// Frame `share` with `ecc_len` RS parity bytes and write it using `encoding`.
+pub fn share_encode(
+    share: &Share,
+    ecc_len: usize,
+    encoding: &dyn ShareEncoding,
+) -> Result<String, ShareCodecError> {
+    encoding.encode(&encode_frame(share, ecc_len))
+}
+
+/// Decode a share written with any built-in encoding, detecting which one.
+pub fn share_decode(s: &str) -> Result<DecodedShare, ShareCodecError> {
+    let s = s.trim();
+    for &encoding in ENCODINGS {
+        if let Ok((bytes, erasures)) = encoding.decode_with_erasures(s)
+            && (is_frame(&bytes) || is_wrapped(&bytes))
+        {
+            let (share, report) = decode_frame(&bytes, &erasures)?;
+            return Ok(DecodedShare {
+                share,
+                encoding: encoding.name(),
+                report,
+            });
+        }
+    }
+
+    // Surface a checksum failure rather than "unknown" when the string
+    // clearly belongs to a checksummed encoding.
+    for &encoding in ENCODINGS {
+        if let Err(e @ ShareCodecError::Checksum(_)) = encoding.decode(s) {
+            return Err(e);
+        }
+    }
+    Err(ShareCodecError::UnknownEncoding)
+}
+
+/// Decode a share written with a specific `encoding`.
+pub fn share_decode_with(
+    s: &str,
+    encoding: &dyn ShareEncoding,
+) -> Result<(Share, CorrectionReport), ShareCodecError> {
+    let (bytes, erasures) = encoding.decode_with_erasures(s.trim())?;
+    decode_frame(&bytes, &erasures)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{GF256, split};
+
+    #[test]
+    fn every_encoding_round_trips_and_is_detected() {
+        let shares = split(b"pluggable encodings", 3, 5);
+        for &encoding in ENCODINGS {
+            for sh in &shares {
+                let text = share_encode(sh, 8, encoding).unwrap();
+                let (back, _) = share_decode_with(&text, encoding).unwrap();
+                assert_eq!(&back, sh, "{}", encoding.name());
+
+                let detected = share_decode(&text).unwrap();
+                assert_eq!(detected.encoding, encoding.name());
+                assert_eq!(&detected.share, sh);
+            }
+        }
+    }
+
+    #[test]
+    fn hex_matches_share_to_hex() {
+        let sh = Share {
+            x: GF256(4),
+            y: b"abc".to_vec(),
+        };
+        assert_eq!(
+            share_encode(&sh, 4, &Hex).unwrap(),
+            crate::share_to_hex(&sh, 4)
+        );
+    }
+
+    #[test]
+    fn crockford_is_forgiving_on_input() {
+        let sh = Share {
+            x: GF256(1),
+            y: b"handwritten".to_vec(),
+        };
+        let text = share_encode(&sh, 4, &CrockfordBase32).unwrap();
+        assert!(text.bytes().all(|c| CROCKFORD.contains(&c)));
+
+        let sloppy: String = text
+            .to_ascii_lowercase()
+            .replace('0', "o")
+            .replace('1', "l")
+            .chars()
+            .enumerate()
+            .flat_map(|(i, c)| (i > 0 && i % 4 == 0).then_some('-').into_iter().chain([c]))
+            .collect();
+        assert_eq!(share_decode(&sloppy).unwrap().share, sh);
+    }
+
+    #[test]
+    fn bech32m_uses_sss_prefix_and_detects_typos() {
+        let sh = Share {
+            x: GF256(2),
+            y: b"typo".to_vec(),
+        };
+        let text = share_encode(&sh, 4, &Bech32m).unwrap();
+        assert!(text.starts_with("sss1"));
+        assert_eq!(share_decode(&text.to_ascii_uppercase()).unwrap().share, sh);
+
+        let mut typo = text.into_bytes();
+        let last = typo.len() - 3;
+        typo[last] = if typo[last] == b'q' { b'p' } else { b'q' };
+        let typo = String::from_utf8(typo).unwrap();
+        assert!(matches!(
+            share_decode(&typo),
+            Err(ShareCodecError::Checksum("bech32m"))
+        ));
+    }
+
+    #[test]
+    fn base58check_rejects_bad_checksum() {
+        let sh = Share {
+            x: GF256(2),
+            y: b"check".to_vec(),
+        };
+        let text = share_encode(&sh, 4, &Base58Check).unwrap();
+        let mut bad = text.into_bytes();
+        bad[10] = if bad[10] == b'2' { b'3' } else { b'2' };
+        let bad = String::from_utf8(bad).unwrap();
+        assert!(matches!(
+            share_decode_with(&bad, &Base58Check),
+            Err(ShareCodecError::Checksum("base58check"))
+        ));
+    }
+
+    #[test]
+    fn words_tolerate_smudges_and_abbreviations() {
+        let sh = Share {
+            x: GF256(6),
+            y: b"read it aloud".to_vec(),
+        };
+        for ecc in [4, 5, 6, 7, 8, 9, 10, 11] {
+            let text = share_encode(&sh, ecc, &Words).unwrap();
+            assert_eq!(share_decode(&text).unwrap().share, sh, "ecc {ecc}");
+        }
+
+        let text = share_encode(&sh, 8, &Words).unwrap();
+        let mut words: Vec<String> = text.split(' ').map(String::from).collect();
+        // Abbreviate one word to four letters, smudge another.
+        let n = words.len();
+        words[n - 3].truncate(4);
+        words[n - 5] = "?".into();
+        let damaged = words.join("  ").to_ascii_uppercase();
+        let decoded = share_decode(&damaged).unwrap();
+        assert_eq!(decoded.encoding, "words");
+        assert_eq!(decoded.share, sh);
+        assert!(decoded.report.erasures > 0);
+    }
+
+    #[test]
+    fn unknown_input_is_rejected() {
+        assert!(matches!(
+            share_decode("definitely not a share"),
+            Err(ShareCodecError::UnknownEncoding)
+        ));
+    }
+}
@@ -0,0 +1,128 @@
+// field.rs
+//
+// The arithmetic Shamir's scheme needs, abstracted over the field.
+//
+// `GF256` is what `split` / `reconstruct` use; other fields (the Ristretto255
+// scalar field behind the `vss` feature) plug into the same polynomial and
+// Lagrange helpers. The byte-wise GF(256) code in `lib.rs` keeps its own
+// allocation-free loops; these generic helpers allocate.
+
+use crate::GF256;
+use alloc::vec::Vec;
+use core::ops::{Add, Mul, Sub};
+
+/// A finite field usable for secret sharing.
+pub trait Field: Copy + Eq + Add<Output = Self> + Sub<Output = Self> + Mul<Output = Self> {
+    const ZERO: Self;
+    const ONE: Self;
+
+    /// Multiplicative inverse. Panics on zero.
+    fn invert(self) -> Self;
+
+    /// Embed a share index (1..=255) as an x-coordinate.
+    fn from_index(index: u8) -> Self;
+}
+
+impl Field for GF256 {
+    const ZERO: Self = GF256::ZERO;
+    const ONE: Self = GF256::ONE;
+
+    fn invert(self) -> Self {
+        self.inv()
+    }
+
+    fn from_index(index: u8) -> Self {
+        GF256(index)
+    }
+}
+
+#[cfg(feature = "vss")]
+impl Field for curve25519_dalek::Scalar {
+    const ZERO: Self = curve25519_dalek::Scalar::ZERO;
+    const ONE: Self = curve25519_dalek::Scalar::ONE;
+
+    fn invert(self) -> Self {
+        assert!(self != Self::ZERO, "attempted inversion of zero");
+        curve25519_dalek::Scalar::invert(&self)
+    }
+
+    fn from_index(index: u8) -> Self {
+        curve25519_dalek::Scalar::from(index)
+    }
+}
+
+/// Evaluate `coeffs[0] + coeffs[1]·x + …` at `x` (Horner's rule).
+pub fn eval_poly<F: Field>(coeffs: &[F], x: F) -> F {
+    coeffs.iter().rev().fold(F::ZERO, |acc, &c| acc * x + c)
+}
+
+/// Lagrange basis values at x = 0 for the given x-coordinates, so that
+/// `f(0) = Σ weights[i] · f(xs[i])`.
+///
+/// Returns `None` if an x-coordinate is zero or repeated.
+pub fn lagrange_weights_at_zero<F: Field>(xs: &[F]) -> Option<Vec<F>> {
+    if xs.contains(&F::ZERO) {
+        return None;
+    }
+    lagrange_weights_at(xs, F::ZERO)
+}
+
+/// Lagrange basis values at `at`, so that `f(at) = Σ weights[i] · f(xs[i])`
+/// for any polynomial of degree below `xs.len()`.
+///
+/// Returns `None` if an x-coordinate is repeated.
+pub fn lagrange_weights_at<F: Field>(xs: &[F], at: F) -> Option<Vec<F>> {
+    let mut weights = Vec::with_capacity(xs.len());
+    for (i, &xi) in xs.iter().enumerate() {
+        let mut num = F::ONE;
+        let mut den = F::ONE;
+        for (j, &xj) in xs.iter().enumerate() {
+            if i == j {
+                continue;
+            }
+            if xj == xi {
+                return None;
+            }
+            num = num * (at - xj);
+            den = den * (xi - xj);
+        }
+        weights.push(num * den.invert());
+    }
+    Some(weights)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{reconstruct, split};
+
+    #[test]
+    fn generic_helpers_match_gf256_reconstruct() {
+        let shares = split(b"field", 3, 5);
+        let used = &shares[1..4];
+        let xs: Vec<GF256> = used.iter().map(|s| s.x).collect();
+        let weights = lagrange_weights_at_zero(&xs).unwrap();
+        let secret: Vec<u8> = (0..5)
+            .map(|b| {
+                used.iter()
+                    .zip(&weights)
+                    .fold(GF256::ZERO, |acc, (s, &w)| acc + GF256(s.y[b]) * w)
+                    .0
+            })
+            .collect();
+        assert_eq!(secret, reconstruct(used, 3));
+
+        let coeffs = [GF256(7), GF256(3), GF256(1)];
+        // 7 + 3·2 + 1·4 in GF(256): 7 ^ 6 ^ 4
+        assert_eq!(eval_poly(&coeffs, GF256(2)), GF256(7 ^ 6 ^ 4));
+        assert!(lagrange_weights_at_zero(&[GF256(1), GF256(1)]).is_none());
+
+        // Weights at another share's x predict that share's y.
+        let predict = lagrange_weights_at(&xs, shares[0].x).unwrap();
+        let y0 = used
+            .iter()
+            .zip(&predict)
+            .fold(GF256::ZERO, |acc, (s, &w)| acc + GF256(s.y[0]) * w);
+        assert_eq!(y0, GF256(shares[0].y[0]));
+    }
+}
@@ -6,17 +6,57 @@
 // https://gendignoux.com/blog/2021/11/01/horcrux-1-math.html
 //
 // Uses the AES polynomial x^8 + x^4 + x^3 + x + 1 (0x11B) as the field modulus.
+//
+// Builds without `std` (only `alloc`) when the default `std` feature is disabled;
+// `split_into` / `reconstruct_into` additionally avoid allocation entirely.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
 
-use rand::random;
-use std::fmt;
-use zeroize::{Zeroize, ZeroizeOnDrop};
+use alloc::vec::Vec;
+use core::fmt;
+use rand::{CryptoRng, RngCore};
+use thiserror::Error;
+use zeroize::{Zeroize, ZeroizeOnDrop, Zeroizing};
 
+mod armor;
+#[cfg(feature = "cbor")]
+mod cbor;
+pub mod encoding;
+pub mod field;
+#[cfg(feature = "merkle")]
+pub mod merkle;
+pub mod parse;
+#[cfg(feature = "age")]
+pub mod seal;
 pub mod share_codec;
-pub use share_codec::{ShareCodecError, share_from_hex, share_to_hex};
+#[cfg(feature = "sign")]
+pub mod sign;
+#[cfg(feature = "vss")]
+pub mod vss;
+#[cfg(feature = "passphrase")]
+pub mod wrap;
+pub use share_codec::{
+    ArmorHeaders, CorrectionReport, ShareCodecError, share_from_armor, share_from_bytes,
+    share_from_hex, share_from_hex_with_report, share_to_armor, share_to_bytes, share_to_hex,
+};
+
+pub use encoding::{DecodedShare, ShareEncoding, share_decode, share_decode_with, share_encode};
+pub use field::Field;
+pub use parse::{ParseAnyError, ParsedShare, ShareFormat, parse_any_share, parse_share_list};
+#[cfg(feature = "sign")]
+pub use sign::{
+    share_from_hex_verified, share_from_hex_verified_with_report, share_to_hex_signed, sign_share,
+    verify_share,
+};
+
+#[cfg(feature = "serde")]
+mod serde_support;
 
 /// An element of GF(256), represented as an unsigned byte.
 #[derive(Copy, Clone, Eq, PartialEq, Debug)]
-pub struct GF256(u8);
+pub struct GF256(pub u8);
 
 // Provide Zeroize so you can call `g.zeroize()`
 impl Zeroize for GF256 {
@@ -126,39 +166,121 @@ impl fmt::Display for Share {
     }
 }
 
+/// Parameters shared by every share of one `split` call.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct ShareSetMeta {
+    /// Number of shares required to reconstruct.
+    pub threshold: u8,
+    /// Total number of shares that were issued.
+    pub share_count: u8,
+}
+
+impl ShareSetMeta {
+    /// Returns `None` unless `1 <= threshold <= share_count`.
+    pub fn new(threshold: u8, share_count: u8) -> Option<Self> {
+        (threshold >= 1 && threshold <= share_count).then_some(ShareSetMeta {
+            threshold,
+            share_count,
+        })
+    }
+}
+
+/// Borrowed view of a share, used by the allocation‑free API.
+#[derive(Copy, Clone, Debug)]
+pub struct ShareRef<'a> {
+    pub x: GF256,
+    pub y: &'a [u8],
+}
+
+impl Share {
+    /// Borrow this share as a [`ShareRef`].
+    pub fn as_share_ref(&self) -> ShareRef<'_> {
+        ShareRef {
+            x: self.x,
+            y: &self.y,
+        }
+    }
+}
+
+/// Errors reported by the non‑panicking `*_into` functions.
+#[derive(Debug, Error, Clone, Copy, PartialEq, Eq)]
+pub enum SssError {
+    #[error("threshold must be in 1..=share_count and share_count in 1..=255")]
+    InvalidParameters,
+    #[error("output buffer has the wrong length")]
+    BufferLength,
+    #[error("not enough shares to reach the threshold")]
+    NotEnoughShares,
+    #[error("shares have different lengths")]
+    LengthMismatch,
+    #[error("share x-coordinates must be distinct and non-zero")]
+    InvalidX,
+}
+
 /// Produce `share_count` shares of `secret`, requiring `threshold` to reconstruct.
 ///
 /// The secret is split byte‑wise: for every byte we build an independent random
 /// polynomial of degree `threshold‑1` with the secret byte as the free coefficient.
+#[cfg(feature = "std")]
 pub fn split(secret: &[u8], threshold: usize, share_count: usize) -> Vec<Share> {
+    split_with_rng(secret, threshold, share_count, &mut rand::thread_rng())
+}
+
+/// Like [`split`], but draws the polynomial coefficients from `rng`.
+pub fn split_with_rng<R: RngCore + CryptoRng>(
+    secret: &[u8],
+    threshold: usize,
+    share_count: usize,
+    rng: &mut R,
+) -> Vec<Share> {
     assert!((1..=255).contains(&threshold));
     assert!(share_count >= threshold && share_count <= 255);
 
-    let xs: Vec<GF256> = (1..=share_count as u8).map(GF256).collect();
-    let mut shares: Vec<Share> = xs
-        .iter()
-        .map(|&x| Share {
-            x,
-            y: Vec::with_capacity(secret.len()),
+    let len = secret.len();
+    let mut ys = Zeroizing::new(alloc::vec![0u8; share_count * len]);
+    split_into(secret, threshold, share_count, rng, &mut ys).expect("parameters checked above");
+
+    (0..share_count)
+        .map(|i| Share {
+            x: GF256(i as u8 + 1),
+            y: ys[i * len..(i + 1) * len].to_vec(),
         })
-        .collect();
+        .collect()
+}
 
-    for &secret_byte in secret {
-        let mut coeffs: Vec<GF256> = Vec::with_capacity(threshold);
-        coeffs.push(GF256(secret_byte));
-        for _ in 1..threshold {
-            coeffs.push(GF256(random::<u8>()));
-        }
+/// Allocation‑free split: writes the y‑vectors of `share_count` shares into `out`.
+///
+/// `out` must be exactly `share_count * secret.len()` bytes long; the share with
+/// x = i + 1 occupies `out[i * secret.len()..(i + 1) * secret.len()]`.
+pub fn split_into<R: RngCore + CryptoRng>(
+    secret: &[u8],
+    threshold: usize,
+    share_count: usize,
+    rng: &mut R,
+    out: &mut [u8],
+) -> Result<(), SssError> {
+    if !(1..=255).contains(&threshold) || share_count < threshold || share_count > 255 {
+        return Err(SssError::InvalidParameters);
+    }
+    if out.len() != share_count * secret.len() {
+        return Err(SssError::BufferLength);
+    }
+
+    let mut coeffs = Zeroizing::new([0u8; 255]);
+    for (byte_idx, &secret_byte) in secret.iter().enumerate() {
+        coeffs[0] = secret_byte;
+        rng.fill_bytes(&mut coeffs[1..threshold]);
 
-        for share in &mut shares {
+        for share_idx in 0..share_count {
+            let x = GF256(share_idx as u8 + 1);
             let mut y = GF256::ZERO;
-            for &coeff in coeffs.iter().rev() {
-                y = y * share.x + coeff;
+            for &coeff in coeffs[..threshold].iter().rev() {
+                y = y * x + GF256(coeff);
             }
-            share.y.push(y.0);
+            out[share_idx * secret.len() + byte_idx] = y.0;
         }
     }
-    shares
+    Ok(())
 }
 
 /// Reconstruct the secret from at least `threshold` shares using Lagrange interpolation.
@@ -167,25 +289,109 @@ pub fn reconstruct(shares: &[Share], threshold: usize) -> Vec<u8> {
     let secret_len = shares[0].y.len();
     assert!(shares.iter().all(|s| s.y.len() == secret_len));
 
-    let mut secret = Vec::with_capacity(secret_len);
-    for byte_idx in 0..secret_len {
-        let mut s = GF256::ZERO;
-        for (i, share_i) in shares.iter().take(threshold).enumerate() {
-            let mut num = GF256::ONE;
-            let mut den = GF256::ONE;
-            for (j, share_j) in shares.iter().take(threshold).enumerate() {
-                if i == j {
-                    continue;
-                }
-                num = num * share_j.x;
-                den = den * (share_j.x - share_i.x);
+    let refs: Vec<ShareRef<'_>> = shares.iter().map(Share::as_share_ref).collect();
+    let mut secret = alloc::vec![0u8; secret_len];
+    if let Err(e) = reconstruct_into(&refs, threshold, &mut secret) {
+        panic!("reconstruct failed: {e}");
+    }
+    secret
+}
+
+/// Allocation‑free reconstruction: interpolates the first `threshold` shares at
+/// x = 0 and writes the secret into `out`, which must match the shares' y length.
+pub fn reconstruct_into(
+    shares: &[ShareRef<'_>],
+    threshold: usize,
+    out: &mut [u8],
+) -> Result<(), SssError> {
+    if !(1..=255).contains(&threshold) {
+        return Err(SssError::InvalidParameters);
+    }
+    if shares.len() < threshold {
+        return Err(SssError::NotEnoughShares);
+    }
+    // x = 0 is where the secret sits; with threshold 1 such a share would
+    // otherwise be returned as the secret without ever meeting the loop below.
+    if shares.iter().any(|s| s.x == GF256::ZERO) {
+        return Err(SssError::InvalidX);
+    }
+    let used = &shares[..threshold];
+    if used.iter().any(|s| s.y.len() != out.len()) {
+        return Err(SssError::LengthMismatch);
+    }
+
+    // Lagrange basis values at x = 0 only depend on the x-coordinates.
+    let mut weights = [GF256::ZERO; 255];
+    for (i, share_i) in used.iter().enumerate() {
+        let mut num = GF256::ONE;
+        let mut den = GF256::ONE;
+        for (j, share_j) in used.iter().enumerate() {
+            if i == j {
+                continue;
+            }
+            if share_j.x == share_i.x {
+                return Err(SssError::InvalidX);
             }
-            let li = num / den;
-            s = s + (GF256(share_i.y[byte_idx]) * li);
+            num = num * share_j.x;
+            den = den * (share_j.x - share_i.x);
         }
-        secret.push(s.0);
+        weights[i] = num / den;
     }
-    secret
+
+    for (byte_idx, byte) in out.iter_mut().enumerate() {
+        let mut s = GF256::ZERO;
+        for (share, &li) in used.iter().zip(weights.iter()) {
+            s = s + (GF256(share.y[byte_idx]) * li);
+        }
+        *byte = s.0;
+    }
+    Ok(())
+}
+
+/// Check that shares lie on one polynomial of degree `threshold - 1`
+/// without reconstructing the secret.
+///
+/// The polynomial through the first `threshold` shares is evaluated at the
+/// x-coordinate of every later share; the x-coordinates of the shares that
+/// disagree are returned, so an empty result means the set is consistent.
+/// With exactly `threshold` shares there is nothing to compare.
+pub fn inconsistent_shares(
+    shares: &[ShareRef<'_>],
+    threshold: usize,
+) -> Result<Vec<GF256>, SssError> {
+    if !(1..=255).contains(&threshold) {
+        return Err(SssError::InvalidParameters);
+    }
+    if shares.len() < threshold {
+        return Err(SssError::NotEnoughShares);
+    }
+    let y_len = shares[0].y.len();
+    if shares.iter().any(|s| s.y.len() != y_len) {
+        return Err(SssError::LengthMismatch);
+    }
+    for (i, share) in shares.iter().enumerate() {
+        if share.x == GF256::ZERO || shares[..i].iter().any(|s| s.x == share.x) {
+            return Err(SssError::InvalidX);
+        }
+    }
+
+    let (basis, rest) = shares.split_at(threshold);
+    let xs: Vec<GF256> = basis.iter().map(|s| s.x).collect();
+    let mut outliers = Vec::new();
+    for share in rest {
+        let weights = field::lagrange_weights_at(&xs, share.x).ok_or(SssError::InvalidX)?;
+        let on_curve = (0..y_len).all(|b| {
+            let predicted = basis
+                .iter()
+                .zip(&weights)
+                .fold(GF256::ZERO, |acc, (s, &w)| acc + GF256(s.y[b]) * w);
+            predicted.0 == share.y[b]
+        });
+        if !on_curve {
+            outliers.push(share.x);
+        }
+    }
+    Ok(outliers)
 }
 
 #[cfg(test)]
@@ -217,4 +423,77 @@ mod tests {
         println!("Treshhold: {threshold:?}");
         println!("Result: {}", String::from_utf8_lossy(&recovered));
     }
+
+    #[test]
+    fn consistency_check_finds_the_tampered_share() {
+        let mut parts = split(b"consistent", 3, 5);
+        let refs: Vec<ShareRef<'_>> = parts.iter().map(Share::as_share_ref).collect();
+        assert_eq!(inconsistent_shares(&refs, 3), Ok(Vec::new()));
+        assert_eq!(
+            inconsistent_shares(&refs[..2], 3),
+            Err(SssError::NotEnoughShares)
+        );
+
+        parts[4].y[7] ^= 1;
+        let refs: Vec<ShareRef<'_>> = parts.iter().map(Share::as_share_ref).collect();
+        assert_eq!(inconsistent_shares(&refs, 3), Ok(alloc::vec![GF256(5)]));
+    }
+
+    #[test]
+    fn buffer_api_round_trip() {
+        let secret = b"no_std";
+        let mut ys = [0u8; 5 * 6];
+        split_into(secret, 3, 5, &mut rand::thread_rng(), &mut ys).unwrap();
+
+        let picked: Vec<ShareRef<'_>> = [4usize, 1, 2]
+            .iter()
+            .map(|&i| ShareRef {
+                x: GF256(i as u8 + 1),
+                y: &ys[i * 6..(i + 1) * 6],
+            })
+            .collect();
+        let mut out = [0u8; 6];
+        reconstruct_into(&picked, 3, &mut out).unwrap();
+        assert_eq!(&out, secret);
+    }
+
+    #[test]
+    fn buffer_api_rejects_bad_input() {
+        let mut rng = rand::thread_rng();
+        let mut ys = [0u8; 4];
+        assert_eq!(
+            split_into(b"ab", 3, 2, &mut rng, &mut ys),
+            Err(SssError::InvalidParameters)
+        );
+        assert_eq!(
+            split_into(b"ab", 2, 3, &mut rng, &mut ys),
+            Err(SssError::BufferLength)
+        );
+
+        let dup = [
+            ShareRef {
+                x: GF256(1),
+                y: &[1],
+            },
+            ShareRef {
+                x: GF256(1),
+                y: &[2],
+            },
+        ];
+        let mut out = [0u8; 1];
+        assert_eq!(reconstruct_into(&dup, 2, &mut out), Err(SssError::InvalidX));
+        assert_eq!(
+            reconstruct_into(&dup[..1], 2, &mut out),
+            Err(SssError::NotEnoughShares)
+        );
+
+        let origin = [ShareRef {
+            x: GF256::ZERO,
+            y: &[7],
+        }];
+        assert_eq!(
+            reconstruct_into(&origin, 1, &mut out),
+            Err(SssError::InvalidX)
+        );
+    }
 }
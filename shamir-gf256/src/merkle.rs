@@ -0,0 +1,294 @@
+/*
+Merkle commitments to a share set (behind the `merkle` feature).
+
+A lighter alternative to `vss`: the dealer publishes one 32-byte Merkle root
+over all shares of a split and hands every custodian an inclusion proof with
+their share. Anyone holding the root can check that a share belongs to the
+official set; `reconstruct_verified` drops shares that do not.
+
+The tree follows RFC 9162 (Certificate Transparency v2) §2.1, so the leaf
+and node hashes are domain-separated and an odd leaf is never duplicated:
+
+  leaf  = H(0x00 || x || y)
+  node  = H(0x01 || left || right)
+  split = largest power of two smaller than the number of leaves
+
+H is SHA-256 or BLAKE3, chosen per set. Proof encoding:
+
+  hash:       u8         // 1 = SHA-256, 2 = BLAKE3
+  leaf_index: u8
+  leaf_count: u8
+  path:       [[u8; 32]] // audit path, leaf to root
+
+Like the dealer signatures in `sign`, the leaf covers the share itself, not
+its encoding, so RS-repaired shares still verify.
+*/
+
+use crate::{GF256, Share, ShareRef, SssError, reconstruct_into};
+use alloc::vec::Vec;
+use sha2::{Digest, Sha256};
+use zeroize::Zeroizing;
+
+/// Hash function used for one Merkle tree.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MerkleHash {
+    Sha256,
+    Blake3,
+}
+
+impl MerkleHash {
+    fn id(self) -> u8 {
+        match self {
+            MerkleHash::Sha256 => 1,
+            MerkleHash::Blake3 => 2,
+        }
+    }
+
+    fn from_id(id: u8) -> Option<Self> {
+        match id {
+            1 => Some(MerkleHash::Sha256),
+            2 => Some(MerkleHash::Blake3),
+            _ => None,
+        }
+    }
+
+    fn hash(self, prefix: u8, parts: &[&[u8]]) -> [u8; 32] {
+        match self {
+            MerkleHash::Sha256 => {
+                let mut h = Sha256::new();
+                h.update([prefix]);
+                for p in parts {
+                    h.update(p);
+                }
+                h.finalize().into()
+            }
+            MerkleHash::Blake3 => {
+                let mut h = blake3::Hasher::new();
+                h.update(&[prefix]);
+                for p in parts {
+                    h.update(p);
+                }
+                h.finalize().into()
+            }
+        }
+    }
+
+    fn leaf(self, share: &Share) -> [u8; 32] {
+        self.hash(0x00, &[&[share.x.0], &share.y])
+    }
+
+    fn node(self, left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+        self.hash(0x01, &[left, right])
+    }
+}
+
+/// Proof that one share is a leaf of a published Merkle root.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct InclusionProof {
+    pub hash: MerkleHash,
+    pub leaf_index: u8,
+    pub leaf_count: u8,
+    pub path: Vec<[u8; 32]>,
+}
+
+impl InclusionProof {
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut out = Vec::with_capacity(3 + 32 * self.path.len());
+        out.extend_from_slice(&[self.hash.id(), self.leaf_index, self.leaf_count]);
+        for p in &self.path {
+            out.extend_from_slice(p);
+        }
+        out
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        let (&[id, leaf_index, leaf_count], rest) = bytes.split_first_chunk::<3>()?;
+        if rest.len() % 32 != 0 {
+            return None;
+        }
+        Some(InclusionProof {
+            hash: MerkleHash::from_id(id)?,
+            leaf_index,
+            leaf_count,
+            path: rest.chunks(32).map(|c| c.try_into().unwrap()).collect(),
+        })
+    }
+
+    /// Whether `share` is the leaf this proof describes under `root`
+    /// (RFC 9162 §2.1.3.2).
+    pub fn verify(&self, share: &Share, root: &[u8; 32]) -> bool {
+        if self.leaf_index >= self.leaf_count {
+            return false;
+        }
+        let mut f = self.leaf_index as usize;
+        let mut s = self.leaf_count as usize - 1;
+        let mut r = self.hash.leaf(share);
+        for p in &self.path {
+            if s == 0 {
+                return false;
+            }
+            if f & 1 == 1 || f == s {
+                r = self.hash.node(p, &r);
+                while f & 1 == 0 && f != 0 {
+                    f >>= 1;
+                    s >>= 1;
+                }
+            } else {
+                r = self.hash.node(&r, p);
+            }
+            f >>= 1;
+            s >>= 1;
+        }
+        s == 0 && r == *root
+    }
+}
+
+/// A share together with its inclusion proof, as handed to a custodian.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ProvenShare {
+    pub share: Share,
+    pub proof: InclusionProof,
+}
+
+/// Commit to `shares` (in order): returns the root to publish and one
+/// inclusion proof per share.
+pub fn merkle_commit(shares: &[Share], hash: MerkleHash) -> ([u8; 32], Vec<InclusionProof>) {
+    assert!(
+        (1..=255).contains(&shares.len()),
+        "a share set has 1..=255 shares"
+    );
+    let leaves: Vec<[u8; 32]> = shares.iter().map(|s| hash.leaf(s)).collect();
+    let root = tree_hash(hash, &leaves);
+    let proofs = (0..leaves.len())
+        .map(|m| {
+            let mut path = Vec::new();
+            audit_path(hash, m, &leaves, &mut path);
+            InclusionProof {
+                hash,
+                leaf_index: m as u8,
+                leaf_count: leaves.len() as u8,
+                path,
+            }
+        })
+        .collect();
+    (root, proofs)
+}
+
+/// Largest power of two strictly smaller than `n` (n >= 2).
+fn split_point(n: usize) -> usize {
+    1 << (usize::BITS - 1 - (n - 1).leading_zeros())
+}
+
+fn tree_hash(hash: MerkleHash, leaves: &[[u8; 32]]) -> [u8; 32] {
+    match leaves {
+        [leaf] => *leaf,
+        _ => {
+            let k = split_point(leaves.len());
+            hash.node(
+                &tree_hash(hash, &leaves[..k]),
+                &tree_hash(hash, &leaves[k..]),
+            )
+        }
+    }
+}
+
+fn audit_path(hash: MerkleHash, m: usize, leaves: &[[u8; 32]], path: &mut Vec<[u8; 32]>) {
+    if leaves.len() <= 1 {
+        return;
+    }
+    let k = split_point(leaves.len());
+    if m < k {
+        audit_path(hash, m, &leaves[..k], path);
+        path.push(tree_hash(hash, &leaves[k..]));
+    } else {
+        audit_path(hash, m - k, &leaves[k..], path);
+        path.push(tree_hash(hash, &leaves[..k]));
+    }
+}
+
+/// Drop every share whose proof does not verify against `root`, then
+/// reconstruct from the first `threshold` that remain.
+///
+/// Returns the secret and the x-coordinates of the rejected shares.
+pub fn reconstruct_verified(
+    shares: &[ProvenShare],
+    root: &[u8; 32],
+    threshold: usize,
+) -> Result<(Zeroizing<Vec<u8>>, Vec<GF256>), SssError> {
+    let (valid, rejected): (Vec<&ProvenShare>, Vec<&ProvenShare>) =
+        shares.iter().partition(|p| p.proof.verify(&p.share, root));
+    let refs: Vec<ShareRef<'_>> = valid.iter().map(|p| p.share.as_share_ref()).collect();
+    let len = refs.first().map_or(0, |s| s.y.len());
+    let mut secret = Zeroizing::new(alloc::vec![0u8; len]);
+    reconstruct_into(&refs, threshold, &mut secret)?;
+    Ok((secret, rejected.iter().map(|p| p.share.x).collect()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::split;
+
+    #[test]
+    fn every_share_proves_membership_for_any_set_size() {
+        for hash in [MerkleHash::Sha256, MerkleHash::Blake3] {
+            for n in [1usize, 2, 3, 5, 7, 8, 13] {
+                let shares = split(b"merkle", 1, n);
+                let (root, proofs) = merkle_commit(&shares, hash);
+                for (share, proof) in shares.iter().zip(&proofs) {
+                    assert!(proof.verify(share, &root), "n={n}, x={}", share.x.0);
+                    let decoded = InclusionProof::from_bytes(&proof.to_bytes()).unwrap();
+                    assert_eq!(&decoded, proof);
+                }
+                // A proof for one leaf does not vouch for another.
+                if n > 1 {
+                    assert!(!proofs[0].verify(&shares[1], &root));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn rfc9162_two_leaf_root() {
+        let a = Share {
+            x: GF256(1),
+            y: alloc::vec![0xaa],
+        };
+        let b = Share {
+            x: GF256(2),
+            y: alloc::vec![0xbb],
+        };
+        let h = MerkleHash::Sha256;
+        let (root, _) = merkle_commit(&[a.clone(), b.clone()], h);
+        let leaf = |s: &Share| Sha256::digest([&[0u8, s.x.0][..], &s.y].concat());
+        let mut node = Sha256::new();
+        node.update([1u8]);
+        node.update(leaf(&a));
+        node.update(leaf(&b));
+        assert_eq!(root, <[u8; 32]>::from(node.finalize()));
+    }
+
+    #[test]
+    fn reconstruct_drops_shares_outside_the_set() {
+        let shares = split(b"official", 3, 5);
+        let (root, proofs) = merkle_commit(&shares, MerkleHash::Blake3);
+        let mut proven: Vec<ProvenShare> = shares
+            .iter()
+            .zip(proofs)
+            .map(|(share, proof)| ProvenShare {
+                share: share.clone(),
+                proof,
+            })
+            .collect();
+        // A forged share placed first would otherwise poison interpolation.
+        proven[0].share.y[0] ^= 1;
+        let (secret, rejected) = reconstruct_verified(&proven, &root, 3).unwrap();
+        assert_eq!(&secret[..], b"official");
+        assert_eq!(rejected, [GF256(1)]);
+
+        assert_eq!(
+            reconstruct_verified(&proven[..3], &root, 3).unwrap_err(),
+            SssError::NotEnoughShares
+        );
+    }
+}
@@ -0,0 +1,440 @@
+/*
+Universal share parser: accepts every share format this crate can read and
+reports which one it found.
+
+Detection order:
+  1. armor      `-----BEGIN SSS SHARE-----` anywhere in the text
+  2. json       `{"x": 3, "y": "<hex>"}`                  (feature `json`)
+  3. encodings  every `ShareEncoding` (SHR1/SHR2 hex, base32, base58check,
+                base64url, bech32m, words), first on the text with all
+                whitespace removed, then also without dashes and lowercased
+  4. ssss       `N-hex` or `token-N-hex` as printed by ssss-split
+  5. vault      standard base64 of a 33-byte `y || x`, as printed by HashiCorp Vault
+
+Vault's `shamir` package uses the same AES field, so its parts reconstruct
+with this crate. ssss computes in GF(2^(8·len)) instead: its shares parse (as
+x = N, y = hex) but only shares produced in that layout by a GF(256) tool will
+reconstruct correctly.
+*/
+
+use crate::encoding::ENCODINGS;
+use crate::share_codec::{
+    ArmorHeaders, CorrectionReport, ShareCodecError, decode_frame, is_frame, is_wrapped,
+    share_from_armor_with_report,
+};
+use crate::{GF256, Share};
+use alloc::string::String;
+use alloc::vec::Vec;
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use core::fmt;
+use zeroize::Zeroizing;
+
+const ARMOR_MARKER: &str = "-----BEGIN SSS SHARE-----";
+const ARMOR_END: &str = "-----END SSS SHARE-----";
+
+/// Which representation a share was parsed from.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ShareFormat {
+    /// A `ShareEncoding` of an `SHR1`/`SHR2` frame, by encoding name.
+    Encoded(&'static str),
+    Armor,
+    Json,
+    Ssss,
+    Vault,
+}
+
+impl fmt::Display for ShareFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ShareFormat::Encoded(name) => f.write_str(name),
+            ShareFormat::Armor => f.write_str("armor"),
+            ShareFormat::Json => f.write_str("json"),
+            ShareFormat::Ssss => f.write_str("ssss"),
+            ShareFormat::Vault => f.write_str("vault"),
+        }
+    }
+}
+
+/// A share plus what [`parse_any_share`] learned while parsing it.
+#[derive(Clone, Debug)]
+pub struct ParsedShare {
+    pub share: Share,
+    pub format: ShareFormat,
+    /// RS repairs, for formats that carry parity (frames and armor).
+    pub report: Option<CorrectionReport>,
+    /// Armor headers, when the share came in an armored block.
+    pub headers: Option<ArmorHeaders>,
+}
+
+/// No format accepted the input; lists every format tried and why it failed.
+#[derive(Debug)]
+pub struct ParseAnyError {
+    pub attempts: Vec<(ShareFormat, ShareCodecError)>,
+}
+
+impl fmt::Display for ParseAnyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let [(format, e)] = &self.attempts[..] {
+            return write!(f, "{format}: {e}");
+        }
+        f.write_str("no share format matched (tried ")?;
+        for (i, (format, e)) in self.attempts.iter().enumerate() {
+            if i > 0 {
+                f.write_str("; ")?;
+            }
+            write!(f, "{format}: {e}")?;
+        }
+        f.write_str(")")
+    }
+}
+
+impl core::error::Error for ParseAnyError {}
+
+impl ParseAnyError {
+    /// The input is a passphrase-wrapped share; ask for the passphrase and
+    /// unwrap it instead.
+    pub fn needs_passphrase(&self) -> bool {
+        matches!(self.attempts[..], [(_, ShareCodecError::Wrapped)])
+    }
+
+    /// The input is a dealer-signed share; it can only be read together with
+    /// the set's verification key.
+    pub fn needs_verification_key(&self) -> bool {
+        matches!(self.attempts[..], [(_, ShareCodecError::Signed)])
+    }
+}
+
+/// Parse one share in any supported format.
+pub fn parse_any_share(input: &str) -> Result<ParsedShare, ParseAnyError> {
+    let trimmed = input.trim();
+
+    // Formats with unmistakable markers fail with their own error only.
+    if trimmed.contains(ARMOR_MARKER) {
+        return share_from_armor_with_report(trimmed)
+            .map(|(share, headers, report)| ParsedShare {
+                share,
+                format: ShareFormat::Armor,
+                report: Some(report),
+                headers: Some(headers),
+            })
+            .map_err(|e| ParseAnyError {
+                attempts: alloc::vec![(ShareFormat::Armor, e)],
+            });
+    }
+    #[cfg(feature = "json")]
+    if trimmed.starts_with('{') {
+        return serde_json::from_str::<Share>(trimmed)
+            .map(|share| ParsedShare {
+                share,
+                format: ShareFormat::Json,
+                report: None,
+                headers: None,
+            })
+            .map_err(|_| ParseAnyError {
+                attempts: alloc::vec![(ShareFormat::Json, ShareCodecError::Encoding("json"))],
+            });
+    }
+
+    let mut attempts: Vec<(ShareFormat, ShareCodecError)> = Vec::new();
+    let mut record = |format: ShareFormat, e: ShareCodecError| {
+        if !attempts.iter().any(|(f, _)| *f == format) {
+            attempts.push((format, e));
+        }
+    };
+
+    // Normalized candidates, most literal first.
+    let compact = Zeroizing::new(trimmed.split_whitespace().collect::<String>());
+    let dashless = Zeroizing::new(compact.replace('-', ""));
+    let lower = Zeroizing::new(dashless.to_ascii_lowercase());
+    let spaced = Zeroizing::new(trimmed.replace('-', " "));
+
+    for &encoding in ENCODINGS {
+        let format = ShareFormat::Encoded(encoding.name());
+        let candidates: &[&str] = if encoding.name() == "words" {
+            &[trimmed, &spaced]
+        } else {
+            &[&compact, &dashless, &lower]
+        };
+        for &candidate in candidates {
+            match encoding.decode_with_erasures(candidate) {
+                Ok((bytes, _)) if is_wrapped(&bytes) => {
+                    return Err(ParseAnyError {
+                        attempts: alloc::vec![(format, ShareCodecError::Wrapped)],
+                    });
+                }
+                Ok((bytes, erasures)) if is_frame(&bytes) => {
+                    match decode_frame(&bytes, &erasures) {
+                        Ok((share, report)) => {
+                            return Ok(ParsedShare {
+                                share,
+                                format,
+                                report: Some(report),
+                                headers: None,
+                            });
+                        }
+                        // A frame that will not decode is that frame's
+                        // error, never a cue to try looser formats.
+                        Err(e) => {
+                            return Err(ParseAnyError {
+                                attempts: alloc::vec![(format, e)],
+                            });
+                        }
+                    }
+                }
+                Ok(_) => record(format, ShareCodecError::BadMagic),
+                Err(e) => record(format, e),
+            }
+        }
+    }
+
+    match parse_ssss(&compact) {
+        Ok(share) => {
+            return Ok(ParsedShare {
+                share,
+                format: ShareFormat::Ssss,
+                report: None,
+                headers: None,
+            });
+        }
+        Err(e) => record(ShareFormat::Ssss, e),
+    }
+
+    match parse_vault(&compact) {
+        Ok(share) => {
+            return Ok(ParsedShare {
+                share,
+                format: ShareFormat::Vault,
+                report: None,
+                headers: None,
+            });
+        }
+        Err(e) => record(ShareFormat::Vault, e),
+    }
+
+    Err(ParseAnyError { attempts })
+}
+
+/// Parse every share in `text`: armored blocks may span lines, every other
+/// non-blank line is one share. Returns the 1-based starting line of each.
+pub fn parse_share_list(text: &str) -> Vec<(usize, Result<ParsedShare, ParseAnyError>)> {
+    let mut out = Vec::new();
+    let mut lines = text.lines().enumerate();
+    while let Some((idx, line)) = lines.next() {
+        if line.contains(ARMOR_MARKER) {
+            let mut block = String::from(line);
+            for (_, next) in lines.by_ref() {
+                block.push('\n');
+                block.push_str(next);
+                if next.contains(ARMOR_END) {
+                    break;
+                }
+            }
+            let block = Zeroizing::new(block);
+            out.push((idx + 1, parse_any_share(&block)));
+        } else if !line.trim().is_empty() {
+            out.push((idx + 1, parse_any_share(line)));
+        }
+    }
+    out
+}
+
+/// `N-hex` or `token-N-hex` (ssss-split output).
+fn parse_ssss(s: &str) -> Result<Share, ShareCodecError> {
+    let mut parts = s.rsplitn(3, '-');
+    let (Some(y_hex), Some(index)) = (parts.next(), parts.next()) else {
+        return Err(ShareCodecError::Encoding("ssss"));
+    };
+    let x: u8 = index
+        .parse()
+        .ok()
+        .filter(|&x| x != 0)
+        .ok_or(ShareCodecError::Encoding("ssss"))?;
+    let mut y = Zeroizing::new(alloc::vec![0u8; y_hex.len() / 2]);
+    hex::decode_to_slice(y_hex, &mut y[..])?;
+    if y.is_empty() {
+        return Err(ShareCodecError::Truncated);
+    }
+    Ok(Share {
+        x: GF256(x),
+        y: core::mem::take(&mut *y),
+    })
+}
+
+/// Length of `y` in a Vault part. Vault splits 32-byte keys, so every part
+/// is 33 bytes: exactly 44 characters of standard (padded) base64.
+const VAULT_Y_LEN: usize = 32;
+const VAULT_TEXT_LEN: usize = (VAULT_Y_LEN + 1).div_ceil(3) * 4;
+
+/// Standard base64 of `y || x` (HashiCorp Vault unseal/recovery key shares).
+fn parse_vault(s: &str) -> Result<Share, ShareCodecError> {
+    if s.len() != VAULT_TEXT_LEN {
+        return Err(ShareCodecError::Encoding("vault base64"));
+    }
+    let mut bytes = Zeroizing::new(
+        STANDARD
+            .decode(s)
+            .map_err(|_| ShareCodecError::Encoding("vault base64"))?,
+    );
+    let x = match bytes.pop() {
+        Some(x) if x != 0 && bytes.len() == VAULT_Y_LEN => x,
+        _ => return Err(ShareCodecError::Truncated),
+    };
+    Ok(Share {
+        x: GF256(x),
+        y: core::mem::take(&mut *bytes),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::encoding::{CrockfordBase32, Words};
+    use crate::{reconstruct, share_encode, share_to_armor, share_to_hex, split};
+    use alloc::format;
+    use alloc::string::ToString;
+
+    fn sample() -> Share {
+        Share {
+            x: GF256(3),
+            y: b"universal".to_vec(),
+        }
+    }
+
+    #[test]
+    fn detects_frame_encodings_after_normalization() {
+        let sh = sample();
+        let hex = share_to_hex(&sh, 8).to_ascii_uppercase();
+        let grouped: String = hex
+            .as_bytes()
+            .chunks(4)
+            .map(|c| core::str::from_utf8(c).unwrap())
+            .collect::<Vec<_>>()
+            .join("-");
+        let parsed = parse_any_share(&format!("  {grouped}\n")).unwrap();
+        assert_eq!(parsed.format, ShareFormat::Encoded("hex"));
+        assert_eq!(parsed.share, sh);
+
+        let b32 = share_encode(&sh, 8, &CrockfordBase32).unwrap();
+        let wrapped = format!("{}\n{}", &b32[..10], &b32[10..]);
+        assert_eq!(
+            parse_any_share(&wrapped).unwrap().format,
+            ShareFormat::Encoded("base32")
+        );
+
+        let words = share_encode(&sh, 8, &Words).unwrap().replace(' ', "-");
+        let parsed = parse_any_share(&words).unwrap();
+        assert_eq!(parsed.format, ShareFormat::Encoded("words"));
+        assert_eq!(parsed.share, sh);
+    }
+
+    #[test]
+    fn detects_armor_with_headers() {
+        let headers = ArmorHeaders {
+            custodian: Some("Dana".into()),
+            ..ArmorHeaders::default()
+        };
+        let text = share_to_armor(&sample(), 8, &headers).unwrap();
+        let parsed = parse_any_share(&format!("see below\n\n{text}")).unwrap();
+        assert_eq!(parsed.format, ShareFormat::Armor);
+        assert_eq!(parsed.headers.unwrap().custodian.as_deref(), Some("Dana"));
+    }
+
+    #[test]
+    fn detects_vault_and_ssss() {
+        let shares = split(b"vault unseal key, 32 bytes long!", 2, 3);
+        let vault: Vec<String> = shares
+            .iter()
+            .map(|s| {
+                let mut raw = s.y.clone();
+                raw.push(s.x.0);
+                STANDARD.encode(raw)
+            })
+            .collect();
+        let parsed: Vec<ParsedShare> = vault.iter().map(|v| parse_any_share(v).unwrap()).collect();
+        assert!(parsed.iter().all(|p| p.format == ShareFormat::Vault));
+        let back: Vec<Share> = parsed.into_iter().map(|p| p.share).collect();
+        assert_eq!(
+            reconstruct(&back[1..], 2),
+            b"vault unseal key, 32 bytes long!"
+        );
+
+        let ssss = parse_any_share("2-c0ffee").unwrap();
+        assert_eq!(ssss.format, ShareFormat::Ssss);
+        assert_eq!(ssss.share.x, GF256(2));
+        assert_eq!(ssss.share.y, [0xc0, 0xff, 0xee]);
+        assert_eq!(parse_any_share("token-7-abcd").unwrap().share.x, GF256(7));
+    }
+
+    #[test]
+    fn unrepairable_frames_fail_instead_of_matching_vault() {
+        let sh = Share {
+            x: GF256(3),
+            y: b"sixteen byte key".to_vec(),
+        };
+        let mut hex = share_to_hex(&sh, 16).into_bytes();
+        for b in &mut hex[20..60] {
+            // Shift every hex digit: 20 byte errors, far from any codeword.
+            *b = b"123456789abcdef0"[(*b as char).to_digit(16).unwrap() as usize];
+        }
+        let err = parse_any_share(core::str::from_utf8(&hex).unwrap()).unwrap_err();
+        assert!(
+            matches!(
+                err.attempts[..],
+                [(ShareFormat::Encoded("hex"), ShareCodecError::EccDecode)]
+            ),
+            "{err}"
+        );
+
+        // Only 33-byte parts in padded base64 are taken for Vault.
+        let mut short = b"sixteen byte key".to_vec();
+        short.push(1);
+        assert!(parse_any_share(&STANDARD.encode(short)).is_err());
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn detects_json() {
+        let parsed = parse_any_share(r#" {"x": 3, "y": "756e6976657273616c"} "#).unwrap();
+        assert_eq!(parsed.format, ShareFormat::Json);
+        assert_eq!(parsed.share, sample());
+    }
+
+    #[test]
+    fn error_lists_every_format_tried() {
+        let err = parse_any_share("not a share at all!").unwrap_err();
+        let tried: Vec<String> = err.attempts.iter().map(|(f, _)| f.to_string()).collect();
+        for name in [
+            "hex",
+            "base32",
+            "base58check",
+            "base64url",
+            "bech32m",
+            "words",
+            "ssss",
+            "vault",
+        ] {
+            assert!(
+                tried.iter().any(|t| t == name),
+                "{name} missing from {tried:?}"
+            );
+        }
+        assert!(err.to_string().starts_with("no share format matched"));
+    }
+
+    #[test]
+    fn share_list_handles_lines_and_armor_blocks() {
+        let sh = sample();
+        let text = format!(
+            "{}\n\n{}\nnonsense\n",
+            share_to_hex(&sh, 4),
+            share_to_armor(&sh, 4, &ArmorHeaders::default()).unwrap()
+        );
+        let parsed = parse_share_list(&text);
+        assert_eq!(parsed.len(), 3);
+        assert_eq!(parsed[0].0, 1);
+        assert_eq!(parsed[1].0, 3);
+        assert_eq!(parsed[1].1.as_ref().unwrap().format, ShareFormat::Armor);
+        assert!(parsed[2].1.is_err());
+    }
+}
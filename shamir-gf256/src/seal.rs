@@ -0,0 +1,126 @@
+/*
+Shares sealed to custodians' age X25519 keys (behind the `age` feature).
+
+`split_sealed` encrypts share i to recipient i as soon as it is created, so
+plaintext shares never leave the dealer's process. The sealed form is an
+ASCII-armored age file:
+
+  -----BEGIN AGE ENCRYPTED FILE-----
+  YWdlLWVuY3J5cHRpb24ub3JnL3YxCi0+IFgyNTUxOSBt...
+  -----END AGE ENCRYPTED FILE-----
+
+whose plaintext is the share's hex text (`share_to_hex`), so it can travel
+over chat or email and the custodian can open it with the `age` CLI as well
+as with `open_share`. `seal_text` / `open_text` seal any share text, e.g. a
+dealer-signed hex line.
+*/
+
+use crate::share_codec::{ShareCodecError, share_from_hex, share_to_hex};
+use crate::{Share, split};
+use std::str::FromStr;
+use zeroize::Zeroizing;
+
+pub use age::x25519::{Identity, Recipient};
+
+/// Seal `share` (as hex with `ecc_len` parity bytes) to `recipient`.
+pub fn seal_share(
+    share: &Share,
+    ecc_len: usize,
+    recipient: &Recipient,
+) -> Result<String, ShareCodecError> {
+    seal_text(&Zeroizing::new(share_to_hex(share, ecc_len)), recipient)
+}
+
+/// Open a share sealed by [`seal_share`].
+pub fn open_share(sealed: &str, identity: &Identity) -> Result<Share, ShareCodecError> {
+    share_from_hex(open_text(sealed, identity)?.trim())
+}
+
+/// Seal arbitrary share text to `recipient` as armored age.
+pub fn seal_text(text: &str, recipient: &Recipient) -> Result<String, ShareCodecError> {
+    age::encrypt_and_armor(recipient, text.as_bytes())
+        .map_err(|_| ShareCodecError::Sealed("encryption failed"))
+}
+
+/// Decrypt armored (or binary) age with `identity` and return the text inside.
+pub fn open_text(sealed: &str, identity: &Identity) -> Result<Zeroizing<String>, ShareCodecError> {
+    let plain = Zeroizing::new(age::decrypt(identity, sealed.trim().as_bytes()).map_err(
+        |e| match e {
+            age::DecryptError::NoMatchingKeys => {
+                ShareCodecError::Sealed("not sealed to this identity")
+            }
+            _ => ShareCodecError::Sealed("malformed or tampered age file"),
+        },
+    )?);
+    let text = core::str::from_utf8(&plain)
+        .map_err(|_| ShareCodecError::Sealed("plaintext is not share text"))?;
+    Ok(Zeroizing::new(text.to_owned()))
+}
+
+/// Split `secret` into one share per recipient and seal share i to
+/// `recipients[i]`. Panics on invalid parameters, like [`split`].
+pub fn split_sealed(
+    secret: &[u8],
+    threshold: usize,
+    recipients: &[Recipient],
+    ecc_len: usize,
+) -> Result<Vec<String>, ShareCodecError> {
+    split(secret, threshold, recipients.len())
+        .iter()
+        .zip(recipients)
+        .map(|(share, recipient)| seal_share(share, ecc_len, recipient))
+        .collect()
+}
+
+/// Parse recipient keys (`age1…`), one per line. Blank lines and `#`
+/// comments are skipped, as in an age recipients file.
+pub fn parse_recipients(text: &str) -> Result<Vec<Recipient>, ShareCodecError> {
+    text.lines()
+        .map(str::trim)
+        .filter(|l| !l.is_empty() && !l.starts_with('#'))
+        .map(|l| {
+            Recipient::from_str(l).map_err(|_| ShareCodecError::Sealed("invalid age recipient"))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::reconstruct;
+
+    #[test]
+    fn each_custodian_opens_only_their_share() {
+        let identities: Vec<Identity> = (0..3).map(|_| Identity::generate()).collect();
+        let list: String = identities
+            .iter()
+            .map(|id| format!("{}\n", id.to_public()))
+            .collect();
+        let recipients = parse_recipients(&format!("# custodians\n\n{list}")).unwrap();
+        assert_eq!(recipients.len(), 3);
+
+        let sealed = split_sealed(b"for your eyes only", 2, &recipients, 8).unwrap();
+        assert!(sealed[0].starts_with("-----BEGIN AGE ENCRYPTED FILE-----"));
+        assert!(!sealed[0].contains("5348523"), "hex frame must not leak");
+
+        let opened: Vec<Share> = sealed
+            .iter()
+            .zip(&identities)
+            .map(|(s, id)| open_share(s, id).unwrap())
+            .collect();
+        assert_eq!(reconstruct(&opened[1..], 2), b"for your eyes only");
+
+        assert!(matches!(
+            open_share(&sealed[0], &identities[1]),
+            Err(ShareCodecError::Sealed("not sealed to this identity"))
+        ));
+        assert!(parse_recipients("age1notakey").is_err());
+    }
+
+    #[test]
+    fn sealed_text_round_trips() {
+        let id = Identity::generate();
+        let sealed = seal_text("53485231...", &id.to_public()).unwrap();
+        assert_eq!(open_text(&sealed, &id).unwrap().as_str(), "53485231...");
+    }
+}
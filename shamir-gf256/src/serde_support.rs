@@ -0,0 +1,265 @@
+/*
+Serde representations for the public types (behind the `serde` feature).
+
+  GF256         -> u8
+  Share         -> { "x": u8, "y": "<lowercase hex>" }   (human-readable formats)
+                   { "x": u8, "y": <bytes> }             (binary formats)
+  ShareSetMeta  -> { "threshold": u8, "share_count": u8 }
+
+Deserializers reject x = 0 (that point *is* the secret), empty y, and
+inconsistent set metadata. Decoded y bytes only ever live in `Zeroizing`
+buffers before being moved into the `Share`.
+*/
+
+use crate::{GF256, Share, ShareSetMeta};
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt;
+use serde::de::{self, Deserializer, MapAccess, SeqAccess, Visitor};
+use serde::ser::{SerializeStruct, Serializer};
+use serde::{Deserialize, Serialize};
+use zeroize::Zeroizing;
+
+impl Serialize for GF256 {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u8(self.0)
+    }
+}
+
+impl<'de> Deserialize<'de> for GF256 {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        u8::deserialize(deserializer).map(GF256)
+    }
+}
+
+impl Serialize for Share {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let human = serializer.is_human_readable();
+        let mut st = serializer.serialize_struct("Share", 2)?;
+        st.serialize_field("x", &self.x)?;
+        if human {
+            let y = Zeroizing::new(hex::encode(&self.y));
+            st.serialize_field("y", y.as_str())?;
+        } else {
+            st.serialize_field("y", &Bytes(&self.y))?;
+        }
+        st.end()
+    }
+}
+
+struct Bytes<'a>(&'a [u8]);
+
+impl Serialize for Bytes<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_bytes(self.0)
+    }
+}
+
+/// Largest `size_hint` trusted up front when `y` arrives as a sequence.
+const MAX_SEQ_HINT: usize = 4096;
+
+/// Deserializes the `y` field from either a hex string or raw bytes.
+struct YBytes(Zeroizing<Vec<u8>>);
+
+impl<'de> Deserialize<'de> for YBytes {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct YVisitor;
+
+        impl<'de> Visitor<'de> for YVisitor {
+            type Value = YBytes;
+
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str("a hex string or a byte array")
+            }
+
+            fn visit_str<E: de::Error>(self, v: &str) -> Result<YBytes, E> {
+                let mut buf = Zeroizing::new(vec![0u8; v.len() / 2]);
+                hex::decode_to_slice(v, &mut buf[..]).map_err(E::custom)?;
+                Ok(YBytes(buf))
+            }
+
+            fn visit_bytes<E: de::Error>(self, v: &[u8]) -> Result<YBytes, E> {
+                Ok(YBytes(Zeroizing::new(v.to_vec())))
+            }
+
+            fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<YBytes, A::Error> {
+                // `Vec::push` would free outgrown buffers without wiping them,
+                // so grow by hand: copy into a larger `Zeroizing` buffer and
+                // let the old one zeroize itself on drop. The hint is capped
+                // so a hostile length cannot force a huge allocation.
+                let hint = seq.size_hint().unwrap_or(0).min(MAX_SEQ_HINT);
+                let mut buf = Zeroizing::new(Vec::with_capacity(hint.max(16)));
+                while let Some(b) = seq.next_element::<u8>()? {
+                    if buf.len() == buf.capacity() {
+                        let mut grown = Zeroizing::new(Vec::with_capacity(2 * buf.capacity()));
+                        grown.extend_from_slice(&buf);
+                        buf = grown;
+                    }
+                    buf.push(b);
+                }
+                Ok(YBytes(buf))
+            }
+        }
+
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_str(YVisitor)
+        } else {
+            deserializer.deserialize_bytes(YVisitor)
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Share {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct ShareVisitor;
+
+        impl<'de> Visitor<'de> for ShareVisitor {
+            type Value = Share;
+
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str("a share with fields `x` and `y`")
+            }
+
+            fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Share, A::Error> {
+                let mut x: Option<GF256> = None;
+                let mut y: Option<YBytes> = None;
+                while let Some(key) = map.next_key::<ShareField>()? {
+                    match key {
+                        ShareField::X => {
+                            if x.is_some() {
+                                return Err(de::Error::duplicate_field("x"));
+                            }
+                            x = Some(map.next_value()?);
+                        }
+                        ShareField::Y => {
+                            if y.is_some() {
+                                return Err(de::Error::duplicate_field("y"));
+                            }
+                            y = Some(map.next_value()?);
+                        }
+                    }
+                }
+                let x = x.ok_or_else(|| de::Error::missing_field("x"))?;
+                let y = y.ok_or_else(|| de::Error::missing_field("y"))?;
+                build_share(x, y)
+            }
+
+            fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Share, A::Error> {
+                let x: GF256 = seq
+                    .next_element()?
+                    .ok_or_else(|| de::Error::invalid_length(0, &self))?;
+                let y: YBytes = seq
+                    .next_element()?
+                    .ok_or_else(|| de::Error::invalid_length(1, &self))?;
+                build_share(x, y)
+            }
+        }
+
+        deserializer.deserialize_struct("Share", &["x", "y"], ShareVisitor)
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(field_identifier, rename_all = "lowercase")]
+enum ShareField {
+    X,
+    Y,
+}
+
+fn build_share<E: de::Error>(x: GF256, y: YBytes) -> Result<Share, E> {
+    if x == GF256::ZERO {
+        return Err(E::custom("share x must be non-zero"));
+    }
+    if y.0.is_empty() {
+        return Err(E::custom("share y must not be empty"));
+    }
+    let mut buf = y.0;
+    Ok(Share {
+        x,
+        y: core::mem::take(&mut *buf),
+    })
+}
+
+impl Serialize for ShareSetMeta {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut st = serializer.serialize_struct("ShareSetMeta", 2)?;
+        st.serialize_field("threshold", &self.threshold)?;
+        st.serialize_field("share_count", &self.share_count)?;
+        st.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for ShareSetMeta {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(rename = "ShareSetMeta", deny_unknown_fields)]
+        struct Raw {
+            threshold: u8,
+            share_count: u8,
+        }
+
+        let raw = Raw::deserialize(deserializer)?;
+        ShareSetMeta::new(raw.threshold, raw.share_count)
+            .ok_or_else(|| de::Error::custom("threshold must be between 1 and share_count"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::split;
+
+    #[test]
+    fn share_json_shape() {
+        let sh = Share {
+            x: GF256(3),
+            y: vec![0xde, 0xad, 0xbe, 0xef],
+        };
+        let json = serde_json::to_string(&sh).unwrap();
+        assert_eq!(json, r#"{"x":3,"y":"deadbeef"}"#);
+        let back: Share = serde_json::from_str(&json).unwrap();
+        assert_eq!(back, sh);
+    }
+
+    #[test]
+    fn split_shares_roundtrip_through_json() {
+        let shares = split(b"json", 2, 3);
+        let json = serde_json::to_string(&shares).unwrap();
+        let back: Vec<Share> = serde_json::from_str(&json).unwrap();
+        assert_eq!(back, shares);
+    }
+
+    #[test]
+    fn rejects_invalid_shares() {
+        assert!(serde_json::from_str::<Share>(r#"{"x":0,"y":"00"}"#).is_err());
+        assert!(serde_json::from_str::<Share>(r#"{"x":1,"y":""}"#).is_err());
+        assert!(serde_json::from_str::<Share>(r#"{"x":1,"y":"0g"}"#).is_err());
+        assert!(serde_json::from_str::<Share>(r#"{"x":1,"y":"abc"}"#).is_err());
+        assert!(serde_json::from_str::<Share>(r#"{"x":256,"y":"00"}"#).is_err());
+        assert!(serde_json::from_str::<Share>(r#"{"y":"00"}"#).is_err());
+    }
+
+    #[test]
+    fn meta_validation() {
+        let meta = ShareSetMeta::new(3, 5).unwrap();
+        let json = serde_json::to_string(&meta).unwrap();
+        assert_eq!(json, r#"{"threshold":3,"share_count":5}"#);
+        assert_eq!(serde_json::from_str::<ShareSetMeta>(&json).unwrap(), meta);
+        assert!(
+            serde_json::from_str::<ShareSetMeta>(r#"{"threshold":6,"share_count":5}"#).is_err()
+        );
+        assert!(
+            serde_json::from_str::<ShareSetMeta>(r#"{"threshold":0,"share_count":5}"#).is_err()
+        );
+    }
+
+    #[test]
+    fn y_sequences_longer_than_the_hint_cap_decode() {
+        use serde::de::value::{Error, SeqDeserializer};
+        let len = 3 * MAX_SEQ_HINT + 5;
+        let seq = SeqDeserializer::<_, Error>::new((0..len).map(|i| i as u8));
+        let y = YBytes::deserialize(seq).unwrap();
+        assert_eq!(y.0.len(), len);
+        assert!(y.0.iter().enumerate().all(|(i, &b)| b == i as u8));
+    }
+}
@@ -1,66 +1,336 @@
 /*
 Hex codec for a single Shamir `Share` with Reed–Solomon ECC.
-Serializes as `SHR1`: stores x, y_len, and RS codeword (y || ecc_len parity).
-`ecc_len` >= 2; corrects up to floor(ecc_len/2) random byte errors.
+`ecc_len` >= 2; each RS codeword corrects up to floor(ecc_len/2) random byte errors.
 
-Layout:
+A GF(256) RS codeword holds at most 255 bytes, so shares whose `y_len + ecc`
+fits in one codeword are written as `SHR1`, longer ones as `SHR2`.
+
+SHR1 layout (single codeword):
   magic:  b"SHR1"     // 4 bytes
   x:      u8          // 1 byte
   ecc:    u8          // number of RS parity bytes
   y_len:  u32 LE      // original y length (without parity)
   code:   [u8; y_len + ecc]
+
+SHR2 layout (interleaved codewords):
+  magic:  b"SHR2"     // 4 bytes
+  x:      u8          // 1 byte
+  ecc:    u8          // RS parity bytes per block
+  blocks: u32 LE      // number of RS blocks B
+  y_len:  u32 LE      // original y length (without parity)
+  code:   [u8; y_len + B * ecc]
+
+In SHR2, y[i] goes to block i % B, and every block is an independent RS
+codeword (its data || ecc parity). The code region is written column by column
+(byte 0 of every block, then byte 1 of every block, ...), so a burst of L
+corrupted bytes hits each block at most ceil(L / B) times.
 */
 
 use crate::{GF256, Share};
+use alloc::string::String;
+use alloc::vec::Vec;
 use reed_solomon::{Decoder, Encoder};
 use thiserror::Error;
 use zeroize::Zeroizing;
 
+pub use crate::armor::{
+    ArmorHeaders, share_from_armor, share_from_armor_with_report, share_to_armor,
+};
+#[cfg(feature = "cbor")]
+pub use crate::cbor::{cbor_to_frame, frame_to_cbor, share_from_cbor, share_to_cbor};
+
 const MAGIC: &[u8; 4] = b"SHR1";
+const MAGIC_INTERLEAVED: &[u8; 4] = b"SHR2";
+/// Passphrase-wrapped share; see `wrap.rs`. Recognized even without the
+/// `passphrase` feature so decoders can say why they cannot read it.
+pub(crate) const MAGIC_WRAPPED: &[u8; 4] = b"SHW1";
+
+/// Largest RS codeword over GF(256), data and parity together.
+const MAX_CODEWORD: usize = 255;
+/// Ed25519 signature that follows a dealer-signed frame; see `sign.rs`.
+/// Known without the `sign` feature so plain decoders can name the problem.
+const SIGNATURE_TRAILER: usize = 64;
 
 #[derive(Debug, Error)]
 pub enum ShareCodecError {
+    // Not `#[from]`: `FromHexError` only implements `Error` with `hex/std`.
     #[error("invalid hex: {0}")]
-    Hex(#[from] hex::FromHexError),
+    Hex(hex::FromHexError),
     #[error("invalid header (bad magic)")]
     BadMagic,
     #[error("truncated or malformed input")]
     Truncated,
     #[error("too many items or lengths overflow")]
     Overflow,
+    #[error("invalid reed-solomon block layout")]
+    BadLayout,
+    #[error("unreadable characters (`?`) in the share header")]
+    ErasedHeader,
+    #[error("invalid {0} text")]
+    Encoding(&'static str),
+    #[error("{0} checksum mismatch")]
+    Checksum(&'static str),
+    #[error("unrecognized share encoding")]
+    UnknownEncoding,
+    #[error("invalid armor: {0}")]
+    Armor(&'static str),
+    #[error("invalid CBOR share: {0}")]
+    Cbor(&'static str),
     #[error("reed-solomon decode failed")]
     EccDecode,
+    #[error("dealer signature does not verify")]
+    Signature,
+    #[error("share carries no dealer signature")]
+    Unsigned,
+    #[error("sealed share: {0}")]
+    Sealed(&'static str),
+    #[error("share is passphrase-protected")]
+    Wrapped,
+    #[error("signed share — enter the dealer verification key")]
+    Signed,
+    #[error("wrong passphrase or damaged share")]
+    Passphrase,
+}
+
+impl From<hex::FromHexError> for ShareCodecError {
+    fn from(e: hex::FromHexError) -> Self {
+        ShareCodecError::Hex(e)
+    }
 }
 
 pub fn share_to_hex(share: &Share, ecc_len: usize) -> String {
-    assert!((2..=255).contains(&ecc_len), "ecc_len must be in 2..=255");
+    hex::encode(encode_frame(share, ecc_len))
+}
+
+/// Decode a share written by [`share_to_hex`].
+///
+/// Unreadable hex digits may be written as `?`. Every byte containing a `?`
+/// is handed to the RS decoder as an erasure (a known-bad position), which
+/// costs half as much correction capacity as an unknown error: per block,
+/// `2 * errors + erasures <= ecc_len`. Header bytes cannot be erased.
+pub fn share_from_hex(s: &str) -> Result<Share, ShareCodecError> {
+    share_from_hex_with_report(s).map(|(share, _)| share)
+}
+
+/// Like [`share_from_hex`], but also reports what the RS decoder had to repair.
+pub fn share_from_hex_with_report(s: &str) -> Result<(Share, CorrectionReport), ShareCodecError> {
+    let (bytes, erasures) = decode_hex_with_erasures(s)?;
+    decode_frame(&bytes, &erasures)
+}
+
+/// What Reed–Solomon decoding repaired in one share.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct CorrectionReport {
+    /// Byte offsets into the decoded frame (hex digit `2 * pos`) that were
+    /// repaired, including `?` erasures. Sorted.
+    pub positions: Vec<usize>,
+    /// How many of `positions` were marked unreadable with `?`.
+    pub erasures: usize,
+    /// Further unknown byte errors the most damaged RS block could still have
+    /// absorbed: `(ecc_len - 2 * errors - erasures) / 2`.
+    pub remaining: usize,
+    /// Parity bytes per RS block.
+    pub ecc_len: usize,
+    /// Number of interleaved RS blocks (1 for an `SHR1` frame).
+    pub blocks: usize,
+}
+
+impl CorrectionReport {
+    /// Number of repaired bytes.
+    pub fn corrected(&self) -> usize {
+        self.positions.len()
+    }
+}
+
+/// Hex-decode `s`, treating `?` as an unknown nibble. Returns the bytes (unknown
+/// nibbles set to 0) and the sorted indices of bytes that contained a `?`.
+pub(crate) fn decode_hex_with_erasures(
+    s: &str,
+) -> Result<(Zeroizing<Vec<u8>>, Vec<usize>), ShareCodecError> {
+    if !s.contains('?') {
+        return Ok((Zeroizing::new(hex::decode(s)?), Vec::new()));
+    }
+    let digits = s.as_bytes();
+    if !digits.len().is_multiple_of(2) {
+        return Err(hex::FromHexError::OddLength.into());
+    }
+
+    let mut bytes = Zeroizing::new(Vec::with_capacity(digits.len() / 2));
+    let mut erasures = Vec::new();
+    for (idx, pair) in digits.chunks(2).enumerate() {
+        let mut byte = 0u8;
+        let mut erased = false;
+        for (k, &c) in pair.iter().enumerate() {
+            let nibble = match c {
+                b'?' => {
+                    erased = true;
+                    0
+                }
+                b'0'..=b'9' => c - b'0',
+                b'a'..=b'f' => c - b'a' + 10,
+                b'A'..=b'F' => c - b'A' + 10,
+                _ => {
+                    return Err(hex::FromHexError::InvalidHexCharacter {
+                        c: c as char,
+                        index: idx * 2 + k,
+                    }
+                    .into());
+                }
+            };
+            byte = (byte << 4) | nibble;
+        }
+        if erased {
+            erasures.push(idx);
+        }
+        bytes.push(byte);
+    }
+    Ok((bytes, erasures))
+}
+
+/// The raw `SHR1`/`SHR2` frame: the compact binary form of a share, and
+/// exactly the bytes that [`share_to_hex`] hex-encodes.
+pub fn share_to_bytes(share: &Share, ecc_len: usize) -> Vec<u8> {
+    encode_frame(share, ecc_len).to_vec()
+}
+
+/// Decode a binary frame written by [`share_to_bytes`].
+pub fn share_from_bytes(bytes: &[u8]) -> Result<Share, ShareCodecError> {
+    decode_frame(bytes, &[]).map(|(share, _)| share)
+}
+
+/// Whether `bytes` starts with a known frame magic.
+pub(crate) fn is_frame(bytes: &[u8]) -> bool {
+    bytes.starts_with(MAGIC) || bytes.starts_with(MAGIC_INTERLEAVED)
+}
 
+/// Whether `bytes` is a passphrase-wrapped share.
+pub(crate) fn is_wrapped(bytes: &[u8]) -> bool {
+    bytes.starts_with(MAGIC_WRAPPED)
+}
+
+/// Total frame length announced by the header at the start of `bytes`,
+/// if the header is complete. Does not validate the layout.
+pub(crate) fn frame_len(bytes: &[u8]) -> Option<usize> {
+    let interleaved = bytes.starts_with(MAGIC_INTERLEAVED);
+    if !interleaved && !bytes.starts_with(MAGIC) {
+        return None;
+    }
+    let ecc_len = *bytes.get(5)? as usize;
+    let (blocks, len_at) = if interleaved {
+        (
+            u32::from_le_bytes(bytes.get(6..10)?.try_into().ok()?) as usize,
+            10,
+        )
+    } else {
+        (1, 6)
+    };
+    let y_len = u32::from_le_bytes(bytes.get(len_at..len_at + 4)?.try_into().ok()?) as usize;
+    (len_at + 4)
+        .checked_add(y_len)?
+        .checked_add(blocks.checked_mul(ecc_len)?)
+}
+
+/// Serialize `share` as an `SHR1` or `SHR2` frame.
+pub(crate) fn encode_frame(share: &Share, ecc_len: usize) -> Zeroizing<Vec<u8>> {
+    assert!(
+        (2..MAX_CODEWORD).contains(&ecc_len),
+        "ecc_len must be in 2..=254"
+    );
+    let y_len: u32 = share.y.len().try_into().expect("y too long");
+
+    if share.y.len() + ecc_len <= MAX_CODEWORD {
+        let enc = Encoder::new(ecc_len);
+        let code = enc.encode(&share.y);
+
+        let mut out = Zeroizing::new(Vec::with_capacity(4 + 1 + 1 + 4 + code.len()));
+        out.extend_from_slice(MAGIC);
+        out.push(share.x.0);
+        out.push(ecc_len as u8);
+        out.extend_from_slice(&y_len.to_le_bytes());
+        out.extend_from_slice(&code[..]); // data + parity
+        return out;
+    }
+
+    let layout = Layout::for_len(share.y.len(), ecc_len);
     let enc = Encoder::new(ecc_len);
-    let code = enc.encode(&share.y);
+    let codewords: Vec<Zeroizing<Vec<u8>>> = (0..layout.blocks)
+        .map(|b| {
+            let data: Zeroizing<Vec<u8>> = Zeroizing::new(
+                share
+                    .y
+                    .iter()
+                    .skip(b)
+                    .step_by(layout.blocks)
+                    .copied()
+                    .collect(),
+            );
+            Zeroizing::new(enc.encode(&data)[..].to_vec())
+        })
+        .collect();
 
-    let mut out = Zeroizing::new(Vec::with_capacity(4 + 1 + 1 + 4 + code.len()));
-    out.extend_from_slice(MAGIC);
+    let mut out = Zeroizing::new(Vec::with_capacity(4 + 1 + 1 + 4 + 4 + layout.code_len()));
+    out.extend_from_slice(MAGIC_INTERLEAVED);
     out.push(share.x.0);
     out.push(ecc_len as u8);
-
-    let y_len: u32 = share.y.len().try_into().expect("y too long");
+    out.extend_from_slice(&(layout.blocks as u32).to_le_bytes());
     out.extend_from_slice(&y_len.to_le_bytes());
-    out.extend_from_slice(&code[..]); // data + parity
+    for (b, j) in layout.order() {
+        out.push(codewords[b][j]);
+    }
+    out
+}
 
-    hex::encode(out)
+/// Header fields and code region of an `SHR1`/`SHR2` frame, before RS decoding.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct Frame<'a> {
+    /// `SHR2` rather than `SHR1`.
+    #[cfg_attr(not(feature = "cbor"), allow(dead_code))]
+    pub interleaved: bool,
+    pub x: GF256,
+    pub ecc_len: usize,
+    pub blocks: usize,
+    pub y_len: usize,
+    /// Offset of `code` within the frame bytes.
+    pub code_start: usize,
+    pub code: &'a [u8],
 }
 
-pub fn share_from_hex(s: &str) -> Result<Share, ShareCodecError> {
-    let bytes = Zeroizing::new(hex::decode(s)?);
+impl Frame<'_> {
+    /// Re-serialize the header and code region.
+    #[cfg_attr(not(feature = "cbor"), allow(dead_code))]
+    pub(crate) fn to_bytes(self) -> Zeroizing<Vec<u8>> {
+        let mut out = Zeroizing::new(Vec::with_capacity(self.code_start + self.code.len()));
+        if self.interleaved {
+            out.extend_from_slice(MAGIC_INTERLEAVED);
+        } else {
+            out.extend_from_slice(MAGIC);
+        }
+        out.push(self.x.0);
+        out.push(self.ecc_len as u8);
+        if self.interleaved {
+            out.extend_from_slice(&(self.blocks as u32).to_le_bytes());
+        }
+        out.extend_from_slice(&(self.y_len as u32).to_le_bytes());
+        out.extend_from_slice(self.code);
+        out
+    }
+}
+
+/// Split an `SHR1` or `SHR2` frame into header fields and code region,
+/// validating the lengths and block layout.
+pub(crate) fn parse_frame(bytes: &[u8]) -> Result<Frame<'_>, ShareCodecError> {
     let mut i = 0usize;
 
     // magic
     if bytes.len() < i + 4 {
         return Err(ShareCodecError::Truncated);
     }
-    if &bytes[i..i + 4] != MAGIC {
-        return Err(ShareCodecError::BadMagic);
-    }
+    let interleaved = match &bytes[i..i + 4] {
+        m if m == MAGIC => false,
+        m if m == MAGIC_INTERLEAVED => true,
+        m if m == MAGIC_WRAPPED => return Err(ShareCodecError::Wrapped),
+        _ => return Err(ShareCodecError::BadMagic),
+    };
     i += 4;
 
     // x
@@ -80,6 +350,18 @@ pub fn share_from_hex(s: &str) -> Result<Share, ShareCodecError> {
         return Err(ShareCodecError::Truncated);
     }
 
+    // blocks (SHR2 only)
+    let blocks = if interleaved {
+        if bytes.len() < i + 4 {
+            return Err(ShareCodecError::Truncated);
+        }
+        let blocks = u32::from_le_bytes(bytes[i..i + 4].try_into().unwrap()) as usize;
+        i += 4;
+        blocks
+    } else {
+        1
+    };
+
     // y_len (original, without parity)
     if bytes.len() < i + 4 {
         return Err(ShareCodecError::Truncated);
@@ -87,29 +369,184 @@ pub fn share_from_hex(s: &str) -> Result<Share, ShareCodecError> {
     let y_len = u32::from_le_bytes(bytes[i..i + 4].try_into().unwrap()) as usize;
     i += 4;
 
-    let code_len = y_len
-        .checked_add(ecc_len)
-        .ok_or(ShareCodecError::Overflow)?;
+    let code_len = Layout::new(y_len, ecc_len, blocks)?.code_len();
     if bytes.len() < i + code_len {
         return Err(ShareCodecError::Truncated);
     }
+    let code_start = i;
+    let code = &bytes[i..i + code_len];
+    i += code_len;
+    if bytes.len() - i == SIGNATURE_TRAILER {
+        return Err(ShareCodecError::Signed);
+    }
+    if i != bytes.len() {
+        return Err(ShareCodecError::Truncated);
+    }
 
-    // Correct using RS
-    let code = Zeroizing::new(bytes[i..i + code_len].to_vec());
+    Ok(Frame {
+        interleaved,
+        x,
+        ecc_len,
+        blocks,
+        y_len,
+        code_start,
+        code,
+    })
+}
+
+/// Parse and RS-correct an `SHR1` or `SHR2` frame.
+///
+/// `erasures` are sorted indices into `bytes` of positions known to be bad.
+pub(crate) fn decode_frame(
+    bytes: &[u8],
+    erasures: &[usize],
+) -> Result<(Share, CorrectionReport), ShareCodecError> {
+    let Frame {
+        x,
+        ecc_len,
+        blocks,
+        y_len,
+        code_start,
+        code,
+        ..
+    } = parse_frame(bytes)?;
+    let layout = Layout::new(y_len, ecc_len, blocks)?;
+    if erasures.first().is_some_and(|&e| e < code_start) {
+        return Err(ShareCodecError::ErasedHeader);
+    }
+
+    // De-interleave into per-block codewords (identity for a single block),
+    // translating erased frame positions into per-block codeword positions.
+    let mut codewords: Vec<Zeroizing<Vec<u8>>> = (0..layout.blocks)
+        .map(|b| Zeroizing::new(Vec::with_capacity(layout.codeword_len(b))))
+        .collect();
+    let mut frame_pos: Vec<Vec<usize>> = (0..layout.blocks)
+        .map(|b| Vec::with_capacity(layout.codeword_len(b)))
+        .collect();
+    let mut block_erasures: Vec<Vec<u8>> = alloc::vec![Vec::new(); layout.blocks];
+    let mut pending = erasures.iter().map(|&e| e - code_start).peekable();
+    for (k, ((b, j), &byte)) in layout.order().zip(code).enumerate() {
+        codewords[b].push(byte);
+        frame_pos[b].push(code_start + k);
+        if pending.next_if_eq(&k).is_some() {
+            block_erasures[b].push(j as u8);
+        }
+    }
+
+    // Correct each block using RS and re-assemble y
     let dec = Decoder::new(ecc_len);
-    let recovered = dec
-        .correct(&code, None)
-        .map_err(|_| ShareCodecError::EccDecode)?;
-    let y = recovered.data().to_vec();
-    if y.len() != y_len {
-        return Err(ShareCodecError::EccDecode);
+    let mut y = Zeroizing::new(alloc::vec![0u8; y_len]);
+    let mut report = CorrectionReport {
+        positions: Vec::new(),
+        erasures: erasures.len(),
+        remaining: ecc_len / 2,
+        ecc_len,
+        blocks: layout.blocks,
+    };
+    for (b, codeword) in codewords.iter().enumerate() {
+        let erased = &block_erasures[b];
+        let recovered = dec
+            .correct(codeword, (!erased.is_empty()).then_some(&erased[..]))
+            .map_err(|_| ShareCodecError::EccDecode)?;
+        let data = recovered.data();
+        if data.len() != layout.data_len(b) {
+            return Err(ShareCodecError::EccDecode);
+        }
+        for (j, &byte) in data.iter().enumerate() {
+            y[b + j * layout.blocks] = byte;
+        }
+
+        let mut errors = 0;
+        for (j, (&got, &fixed)) in codeword.iter().zip(recovered.iter()).enumerate() {
+            let was_erased = erased.contains(&(j as u8));
+            if got != fixed && !was_erased {
+                errors += 1;
+            }
+            if got != fixed || was_erased {
+                report.positions.push(frame_pos[b][j]);
+            }
+        }
+        let used = 2 * errors + erased.len();
+        report.remaining = report.remaining.min(ecc_len.saturating_sub(used) / 2);
     }
-    i += code_len;
+    report.positions.sort_unstable();
 
-    if i != bytes.len() {
-        return Err(ShareCodecError::Truncated);
+    Ok((
+        Share {
+            x,
+            y: core::mem::take(&mut *y),
+        },
+        report,
+    ))
+}
+
+/// How `y_len` data bytes are spread over RS blocks of `ecc` parity bytes each.
+#[derive(Clone, Copy, Debug)]
+struct Layout {
+    y_len: usize,
+    ecc: usize,
+    blocks: usize,
+}
+
+impl Layout {
+    /// Smallest layout whose codewords all fit in 255 bytes.
+    fn for_len(y_len: usize, ecc: usize) -> Self {
+        let per_block = MAX_CODEWORD - ecc;
+        Layout {
+            y_len,
+            ecc,
+            blocks: y_len.div_ceil(per_block).max(1),
+        }
+    }
+
+    /// Validate a layout read from a header.
+    fn new(y_len: usize, ecc: usize, blocks: usize) -> Result<Self, ShareCodecError> {
+        if blocks == 0 || (blocks > 1 && blocks > y_len) {
+            return Err(ShareCodecError::BadLayout);
+        }
+        // Header fields are u32s from untrusted input; on 32-bit targets the
+        // lengths derived from them can overflow `usize`. Once these two sums
+        // fit, `data_len` and `code_len` cannot overflow.
+        y_len
+            .checked_add(blocks - 1)
+            .ok_or(ShareCodecError::Overflow)?;
+        blocks
+            .checked_mul(ecc)
+            .and_then(|parity| y_len.checked_add(parity))
+            .ok_or(ShareCodecError::Overflow)?;
+        let layout = Layout { y_len, ecc, blocks };
+        let longest = layout
+            .data_len(0)
+            .checked_add(ecc)
+            .ok_or(ShareCodecError::Overflow)?;
+        if longest > MAX_CODEWORD {
+            return Err(ShareCodecError::BadLayout);
+        }
+        Ok(layout)
+    }
+
+    /// Number of y bytes stored in block `b` (those with index ≡ b mod blocks).
+    fn data_len(&self, b: usize) -> usize {
+        (self.y_len + self.blocks - 1 - b) / self.blocks
+    }
+
+    fn codeword_len(&self, b: usize) -> usize {
+        self.data_len(b) + self.ecc
+    }
+
+    fn code_len(&self) -> usize {
+        self.y_len + self.blocks * self.ecc
+    }
+
+    /// `(block, index in block)` for every byte of the code region, in stored order.
+    fn order(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        let rows = self.codeword_len(0);
+        (0..rows).flat_map(move |j| {
+            (0..self.blocks)
+                .filter(move |&b| j < self.codeword_len(b))
+                .map(move |b| (b, j))
+        })
     }
-    Ok(Share { x, y })
 }
 
 #[cfg(test)]
@@ -117,6 +554,31 @@ mod ecc_tests {
     use super::*;
     use crate::{GF256, Share, split};
 
+    #[test]
+    fn oversized_header_lengths_are_rejected_without_panicking() {
+        // SHR2, x = 1, ecc = 16, blocks = y_len = u32::MAX, and no code bytes.
+        let mut frame = b"SHR2".to_vec();
+        frame.extend_from_slice(&[1, 16]);
+        frame.extend_from_slice(&u32::MAX.to_le_bytes());
+        frame.extend_from_slice(&u32::MAX.to_le_bytes());
+        let expected_overflow = usize::BITS == 32;
+        match share_from_hex(&hex::encode(&frame)) {
+            Err(ShareCodecError::Overflow) => assert!(expected_overflow),
+            Err(ShareCodecError::Truncated) => assert!(!expected_overflow),
+            other => panic!("unexpected {other:?}"),
+        }
+
+        // The same sums at `usize` scale, which every target must refuse.
+        assert!(matches!(
+            Layout::new(usize::MAX, 16, usize::MAX),
+            Err(ShareCodecError::Overflow)
+        ));
+        assert!(matches!(
+            Layout::new(usize::MAX - 1, 16, 2),
+            Err(ShareCodecError::Overflow)
+        ));
+    }
+
     #[test]
     fn shr2_roundtrip_and_correction() {
         let sh = Share {
@@ -151,4 +613,152 @@ mod ecc_tests {
         let decoded: Vec<Share> = blobs.iter().map(|h| share_from_hex(h).unwrap()).collect();
         assert_eq!(shares, decoded);
     }
+
+    #[test]
+    fn short_shares_keep_shr1_layout() {
+        let sh = Share {
+            x: GF256(7),
+            y: alloc::vec![0xAB; 255 - 16],
+        };
+        let bytes = hex::decode(share_to_hex(&sh, 16)).unwrap();
+        assert_eq!(&bytes[..4], MAGIC);
+        assert_eq!(share_from_hex(&hex::encode(bytes)).unwrap(), sh);
+    }
+
+    #[test]
+    fn long_shares_use_interleaved_blocks() {
+        let secret: Vec<u8> = (0..1000u32).map(|i| (i * 7 + 3) as u8).collect();
+        let shares = split(&secret, 2, 3);
+        let ecc = 16;
+
+        let mut bytes = hex::decode(share_to_hex(&shares[1], ecc)).unwrap();
+        assert_eq!(&bytes[..4], MAGIC_INTERLEAVED);
+        let blocks = u32::from_le_bytes(bytes[6..10].try_into().unwrap()) as usize;
+        assert_eq!(blocks, 1000usize.div_ceil(255 - ecc));
+        assert_eq!(bytes.len(), 14 + 1000 + blocks * ecc);
+
+        // A burst of 8 * blocks bytes touches every block only 8 = ecc / 2 times.
+        let base = 14 + 300;
+        for b in &mut bytes[base..base + 8 * blocks] {
+            *b ^= 0xFF;
+        }
+        let decoded = share_from_hex(&hex::encode(&bytes)).unwrap();
+        assert_eq!(decoded, shares[1]);
+
+        let decoded: Vec<Share> = shares
+            .iter()
+            .map(|s| share_from_hex(&share_to_hex(s, ecc)).unwrap())
+            .collect();
+        assert_eq!(crate::reconstruct(&decoded, 2), secret);
+    }
+
+    #[test]
+    fn rejects_bad_block_layout() {
+        let sh = Share {
+            x: GF256(1),
+            y: alloc::vec![1; 600],
+        };
+        let mut bytes = hex::decode(share_to_hex(&sh, 10)).unwrap();
+        // claim a single block: 600 + 10 bytes cannot fit one codeword
+        bytes[6..10].copy_from_slice(&1u32.to_le_bytes());
+        assert!(matches!(
+            share_from_hex(&hex::encode(&bytes)),
+            Err(ShareCodecError::BadLayout)
+        ));
+        bytes[6..10].copy_from_slice(&0u32.to_le_bytes());
+        assert!(matches!(
+            share_from_hex(&hex::encode(&bytes)),
+            Err(ShareCodecError::BadLayout)
+        ));
+    }
+
+    #[test]
+    fn question_marks_are_erasures() {
+        let sh = Share {
+            x: GF256(3),
+            y: b"smudged paper backup".to_vec(),
+        };
+        let ecc = 8;
+        let hex_str = share_to_hex(&sh, ecc);
+        let base = 2 * (4 + 1 + 1 + 4);
+
+        // 8 unreadable bytes: beyond floor(8/2) = 4 errors, but exactly 8 erasures.
+        let mut smudged: Vec<u8> = hex_str.clone().into_bytes();
+        for k in 0..8 {
+            smudged[base + 4 * k] = b'?';
+        }
+        let smudged = String::from_utf8(smudged).unwrap();
+        assert_eq!(share_from_hex(&smudged).unwrap(), sh);
+
+        // The same damage without markers is not recoverable.
+        let guessed = smudged.replace('?', "f");
+        assert!(share_from_hex(&guessed).is_err());
+    }
+
+    #[test]
+    fn erasures_map_through_interleaving() {
+        let sh = Share {
+            x: GF256(9),
+            y: (0..700u32).map(|i| i as u8).collect(),
+        };
+        let ecc = 6;
+        let hex_str = share_to_hex(&sh, ecc);
+        let base = 2 * 14;
+        let mut smudged = hex_str.into_bytes();
+        // 18 consecutive unreadable bytes over 3 blocks = 6 erasures per block.
+        for d in &mut smudged[base + 2 * 100..base + 2 * 118] {
+            *d = b'?';
+        }
+        let smudged = String::from_utf8(smudged).unwrap();
+        assert_eq!(share_from_hex(&smudged).unwrap(), sh);
+    }
+
+    #[test]
+    fn erased_header_is_rejected() {
+        let sh = Share {
+            x: GF256(1),
+            y: b"abc".to_vec(),
+        };
+        let mut s = share_to_hex(&sh, 4);
+        s.replace_range(8..9, "?");
+        assert!(matches!(
+            share_from_hex(&s),
+            Err(ShareCodecError::ErasedHeader)
+        ));
+    }
+
+    #[test]
+    fn report_counts_repairs_and_remaining_capacity() {
+        let sh = Share {
+            x: GF256(3),
+            y: b"custodian backup".to_vec(),
+        };
+        let hex_str = share_to_hex(&sh, 16);
+        let (decoded, report) = share_from_hex_with_report(&hex_str).unwrap();
+        assert_eq!(decoded, sh);
+        assert_eq!(
+            report,
+            CorrectionReport {
+                positions: Vec::new(),
+                erasures: 0,
+                remaining: 8,
+                ecc_len: 16,
+                blocks: 1,
+            }
+        );
+
+        let mut bytes = hex::decode(&hex_str).unwrap();
+        bytes[12] ^= 0x01;
+        bytes[20] ^= 0x80;
+        let mut damaged = hex::encode(&bytes);
+        damaged.replace_range(2 * 15..2 * 15 + 1, "?");
+
+        let (decoded, report) = share_from_hex_with_report(&damaged).unwrap();
+        assert_eq!(decoded, sh);
+        assert_eq!(report.positions, vec![12, 15, 20]);
+        assert_eq!(report.corrected(), 3);
+        assert_eq!(report.erasures, 1);
+        // 2 errors + 1 erasure use 5 of 16 parity bytes.
+        assert_eq!(report.remaining, 5);
+    }
 }
@@ -0,0 +1,198 @@
+/*
+Dealer-signed shares (behind the `sign` feature).
+
+The dealer signs every share with one Ed25519 key per share set and publishes
+the verification key (32 bytes, usually as 64 hex digits) next to the set.
+The signature covers the share itself, not its encoding:
+
+  msg = b"SSS-SIG1" || x || y_len (u32 LE) || y
+
+so RS repairs happen first and a repaired share still verifies. Signed hex is
+the usual `SHR1`/`SHR2` frame followed by the 64-byte signature:
+
+  frame:     [u8; frame_len]   // exactly what `share_to_hex` writes
+  signature: [u8; 64]          // Ed25519 over `msg`
+
+The signature is not covered by the frame's parity: a damaged signature
+fails verification and the share must be re-issued, never silently accepted.
+*/
+
+use crate::Share;
+use crate::share_codec::{
+    CorrectionReport, ShareCodecError, decode_frame, decode_hex_with_erasures, encode_frame,
+    frame_len,
+};
+use alloc::string::String;
+use alloc::vec::Vec;
+use ed25519_dalek::Signer;
+use zeroize::Zeroizing;
+
+pub use ed25519_dalek::{SIGNATURE_LENGTH, Signature, SigningKey, VerifyingKey};
+
+const DOMAIN: &[u8; 8] = b"SSS-SIG1";
+
+/// The exact bytes a dealer signature covers.
+fn message(share: &Share) -> Zeroizing<Vec<u8>> {
+    let mut msg = Zeroizing::new(Vec::with_capacity(DOMAIN.len() + 5 + share.y.len()));
+    msg.extend_from_slice(DOMAIN);
+    msg.push(share.x.0);
+    msg.extend_from_slice(&(share.y.len() as u32).to_le_bytes());
+    msg.extend_from_slice(&share.y);
+    msg
+}
+
+/// Sign `share` with the dealer's key for its set.
+pub fn sign_share(share: &Share, key: &SigningKey) -> Signature {
+    key.sign(&message(share))
+}
+
+/// Check a dealer signature on `share` against the set's verification key.
+pub fn verify_share(
+    share: &Share,
+    signature: &Signature,
+    key: &VerifyingKey,
+) -> Result<(), ShareCodecError> {
+    key.verify_strict(&message(share), signature)
+        .map_err(|_| ShareCodecError::Signature)
+}
+
+/// Hex frame of `share` followed by the dealer's signature.
+pub fn share_to_hex_signed(share: &Share, ecc_len: usize, key: &SigningKey) -> String {
+    let mut bytes = encode_frame(share, ecc_len);
+    bytes.extend_from_slice(&sign_share(share, key).to_bytes());
+    hex::encode(&bytes[..])
+}
+
+/// Decode a share written by [`share_to_hex_signed`] and check its signature.
+///
+/// The share is only returned if the signature verifies, so callers can pass
+/// the result straight to `reconstruct`. `?` digits are allowed in the frame
+/// (as in [`share_from_hex`](crate::share_from_hex)) but not in the signature.
+pub fn share_from_hex_verified(s: &str, key: &VerifyingKey) -> Result<Share, ShareCodecError> {
+    share_from_hex_verified_with_report(s, key).map(|(share, _)| share)
+}
+
+/// Like [`share_from_hex_verified`], but also reports RS repairs.
+pub fn share_from_hex_verified_with_report(
+    s: &str,
+    key: &VerifyingKey,
+) -> Result<(Share, CorrectionReport), ShareCodecError> {
+    let (bytes, erasures) = decode_hex_with_erasures(s)?;
+    let len = frame_len(&bytes).ok_or(ShareCodecError::BadMagic)?;
+    if bytes.len() == len {
+        return Err(ShareCodecError::Unsigned);
+    }
+    if bytes.len() != len + SIGNATURE_LENGTH {
+        return Err(ShareCodecError::Truncated);
+    }
+    if erasures.last().is_some_and(|&e| e >= len) {
+        return Err(ShareCodecError::Signature);
+    }
+    let (frame, sig) = bytes.split_at(len);
+    let signature = Signature::from_slice(sig).map_err(|_| ShareCodecError::Signature)?;
+    let (share, report) = decode_frame(frame, &erasures)?;
+    verify_share(&share, &signature, key)?;
+    Ok((share, report))
+}
+
+/// Parse a published verification key (64 hex digits).
+pub fn verifying_key_from_hex(s: &str) -> Result<VerifyingKey, ShareCodecError> {
+    let mut raw = [0u8; 32];
+    hex::decode_to_slice(s.trim(), &mut raw)?;
+    VerifyingKey::from_bytes(&raw).map_err(|_| ShareCodecError::Encoding("verification key"))
+}
+
+/// Hex form of a verification key, for publishing alongside a share set.
+pub fn verifying_key_to_hex(key: &VerifyingKey) -> String {
+    hex::encode(key.as_bytes())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{reconstruct, share_from_hex, share_to_hex, split};
+
+    fn dealer() -> SigningKey {
+        SigningKey::from_bytes(&[7u8; 32])
+    }
+
+    #[test]
+    fn signed_shares_verify_and_reconstruct() {
+        let key = dealer();
+        let vk = key.verifying_key();
+        let shares = split(b"signed secret", 2, 3);
+        let texts: Vec<String> = shares
+            .iter()
+            .map(|s| share_to_hex_signed(s, 8, &key))
+            .collect();
+        let back: Vec<Share> = texts
+            .iter()
+            .map(|t| share_from_hex_verified(t, &vk).unwrap())
+            .collect();
+        assert_eq!(back, shares);
+        assert_eq!(reconstruct(&back[..2], 2), b"signed secret");
+
+        let published = verifying_key_to_hex(&vk);
+        assert_eq!(verifying_key_from_hex(&published).unwrap(), vk);
+    }
+
+    #[test]
+    fn rejects_forged_and_foreign_shares() {
+        let key = dealer();
+        let vk = key.verifying_key();
+        let share = Share {
+            x: crate::GF256(2),
+            y: b"genuine".to_vec(),
+        };
+
+        // Forger re-frames a different y under the genuine signature.
+        let genuine = share_to_hex_signed(&share, 8, &key);
+        let forged = Share {
+            x: share.x,
+            y: b"forged!".to_vec(),
+        };
+        let mut text = share_to_hex(&forged, 8);
+        text.push_str(&genuine[genuine.len() - 2 * SIGNATURE_LENGTH..]);
+        assert!(matches!(
+            share_from_hex_verified(&text, &vk),
+            Err(ShareCodecError::Signature)
+        ));
+
+        // Signed by some other key.
+        let other = share_to_hex_signed(&share, 8, &SigningKey::from_bytes(&[9u8; 32]));
+        assert!(matches!(
+            share_from_hex_verified(&other, &vk),
+            Err(ShareCodecError::Signature)
+        ));
+
+        // Unsigned share, and a signed share fed to the plain decoder.
+        assert!(matches!(
+            share_from_hex_verified(&share_to_hex(&share, 8), &vk),
+            Err(ShareCodecError::Unsigned)
+        ));
+        assert!(matches!(
+            share_from_hex(&genuine),
+            Err(ShareCodecError::Signed)
+        ));
+        let err = crate::parse_any_share(&genuine).unwrap_err();
+        assert!(err.needs_verification_key(), "{err}");
+    }
+
+    #[test]
+    fn rs_repairs_happen_before_verification() {
+        let key = dealer();
+        let share = Share {
+            x: crate::GF256(5),
+            y: b"noisy but authentic".to_vec(),
+        };
+        let mut text = share_to_hex_signed(&share, 8, &key).into_bytes();
+        // Corrupt one code byte and erase another; the frame repairs both.
+        text[22] = if text[22] == b'0' { b'1' } else { b'0' };
+        text[30] = b'?';
+        let text = String::from_utf8(text).unwrap();
+        let (back, report) =
+            share_from_hex_verified_with_report(&text, &key.verifying_key()).unwrap();
+        assert_eq!(back, share);
+        assert_eq!(report.corrected(), 2);
+    }
+}
@@ -0,0 +1,306 @@
+/*
+Feldman verifiable secret sharing over Ristretto255 (behind the `vss` feature).
+
+Plain Shamir trusts the dealer: a malicious dealer can hand out shares that
+do not lie on one polynomial, and nobody notices until reconstruction gives
+different answers for different subsets. Here the dealer also publishes
+commitments to every polynomial coefficient, and each custodian can check
+their own share against them without talking to anyone else.
+
+The secret is cut into 31-byte chunks; each chunk is a scalar mod ℓ (the
+Ristretto255 group order) and gets its own degree t-1 polynomial
+
+  f_c(z) = a_c0 + a_c1·z + … + a_c(t-1)·z^(t-1),   a_c0 = chunk c
+
+The dealer publishes C_ck = a_ck·G, and share i holds y_c = f_c(i) for every
+chunk. A share verifies iff, for every chunk,
+
+  y_c·G == Σ_k C_ck · i^k
+
+Encodings (all integers little-endian):
+
+  Commitments: secret_len u32 | threshold u8 | chunks × threshold × 32-byte points
+  VssShare:    index u8 | chunks × 32-byte scalars
+
+C_c0 = chunk·G is public, so Feldman commitments only hide secrets that
+cannot be guessed: share keys or other high-entropy data, not passwords.
+*/
+
+use crate::field::{Field, eval_poly, lagrange_weights_at_zero};
+use alloc::vec::Vec;
+use curve25519_dalek::ristretto::{CompressedRistretto, RistrettoPoint};
+use curve25519_dalek::scalar::Scalar;
+use rand::{CryptoRng, RngCore};
+use thiserror::Error;
+use zeroize::{Zeroize, ZeroizeOnDrop, Zeroizing};
+
+/// Secret bytes per scalar; 31 bytes always fit below ℓ ≈ 2^252.
+const CHUNK: usize = 31;
+
+#[derive(Debug, Error, Clone, Copy, PartialEq, Eq)]
+pub enum VssError {
+    #[error("threshold must be in 1..=share_count and share_count in 1..=255")]
+    InvalidParameters,
+    #[error("share {0} does not match the published commitments")]
+    InvalidShare(u8),
+    #[error("not enough shares to reach the threshold")]
+    NotEnoughShares,
+    #[error("share indices must be distinct and non-zero")]
+    InvalidIndex,
+    #[error("malformed {0}")]
+    Malformed(&'static str),
+}
+
+/// A custodian's share: the value of every chunk polynomial at `index`.
+#[derive(Clone, Debug, PartialEq, Eq, Zeroize, ZeroizeOnDrop)]
+pub struct VssShare {
+    pub index: u8,
+    pub values: Vec<Scalar>,
+}
+
+/// The dealer's public commitments to all polynomial coefficients.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Commitments {
+    pub secret_len: usize,
+    /// `points[c][k]` commits to coefficient `k` of chunk `c`'s polynomial.
+    pub points: Vec<Vec<CompressedRistretto>>,
+}
+
+impl Commitments {
+    /// Number of shares needed to reconstruct (polynomial degree + 1).
+    pub fn threshold(&self) -> usize {
+        self.points.first().map_or(0, Vec::len)
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut out = Vec::with_capacity(5 + self.points.len() * self.threshold() * 32);
+        out.extend_from_slice(&(self.secret_len as u32).to_le_bytes());
+        out.push(self.threshold() as u8);
+        for p in self.points.iter().flatten() {
+            out.extend_from_slice(p.as_bytes());
+        }
+        out
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, VssError> {
+        let bad = VssError::Malformed("commitments");
+        let header: [u8; 4] = bytes.get(..4).ok_or(bad)?.try_into().unwrap();
+        let secret_len = u32::from_le_bytes(header) as usize;
+        let threshold = *bytes.get(4).ok_or(bad)? as usize;
+        let body = &bytes[5..];
+        if threshold == 0 || body.len() != chunk_count(secret_len) * threshold * 32 {
+            return Err(bad);
+        }
+        let points = body
+            .chunks(threshold * 32)
+            .map(|row| {
+                row.chunks(32)
+                    .map(|p| CompressedRistretto(p.try_into().unwrap()))
+                    .collect()
+            })
+            .collect();
+        Ok(Commitments { secret_len, points })
+    }
+}
+
+impl VssShare {
+    /// Check this share against the dealer's commitments.
+    pub fn verify(&self, commitments: &Commitments) -> Result<(), VssError> {
+        let invalid = VssError::InvalidShare(self.index);
+        if self.index == 0 || self.values.len() != commitments.points.len() {
+            return Err(invalid);
+        }
+        let x = Scalar::from_index(self.index);
+        for (y, row) in self.values.iter().zip(&commitments.points) {
+            // Horner over the group: Σ C_k · x^k
+            let mut expected = RistrettoPoint::default();
+            for c in row.iter().rev() {
+                expected = expected * x + c.decompress().ok_or(invalid)?;
+            }
+            if RistrettoPoint::mul_base(y) != expected {
+                return Err(invalid);
+            }
+        }
+        Ok(())
+    }
+
+    pub fn to_bytes(&self) -> Zeroizing<Vec<u8>> {
+        let mut out = Zeroizing::new(Vec::with_capacity(1 + self.values.len() * 32));
+        out.push(self.index);
+        for v in &self.values {
+            out.extend_from_slice(v.as_bytes());
+        }
+        out
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, VssError> {
+        let bad = VssError::Malformed("share");
+        let (&index, body) = bytes.split_first().ok_or(bad)?;
+        if index == 0 || body.is_empty() || body.len() % 32 != 0 {
+            return Err(bad);
+        }
+        let values = body
+            .chunks(32)
+            .map(|c| Option::from(Scalar::from_canonical_bytes(c.try_into().unwrap())).ok_or(bad))
+            .collect::<Result<_, _>>()?;
+        Ok(VssShare { index, values })
+    }
+}
+
+fn chunk_count(secret_len: usize) -> usize {
+    secret_len.div_ceil(CHUNK).max(1)
+}
+
+/// Split `secret` into `share_count` verifiable shares, `threshold` of which
+/// reconstruct it, and return them with the commitments to publish.
+#[cfg(feature = "std")]
+pub fn vss_split(
+    secret: &[u8],
+    threshold: usize,
+    share_count: usize,
+) -> Result<(Vec<VssShare>, Commitments), VssError> {
+    vss_split_with_rng(secret, threshold, share_count, &mut rand::thread_rng())
+}
+
+/// Like [`vss_split`], but draws the polynomial coefficients from `rng`.
+pub fn vss_split_with_rng<R: RngCore + CryptoRng>(
+    secret: &[u8],
+    threshold: usize,
+    share_count: usize,
+    rng: &mut R,
+) -> Result<(Vec<VssShare>, Commitments), VssError> {
+    if !(1..=255).contains(&threshold) || share_count < threshold || share_count > 255 {
+        return Err(VssError::InvalidParameters);
+    }
+
+    let chunks = chunk_count(secret.len());
+    let mut shares: Vec<VssShare> = (1..=share_count as u8)
+        .map(|index| VssShare {
+            index,
+            values: Vec::with_capacity(chunks),
+        })
+        .collect();
+    let mut points = Vec::with_capacity(chunks);
+
+    let mut coeffs: Zeroizing<Vec<Scalar>> = Zeroizing::new(Vec::with_capacity(threshold));
+    for c in 0..chunks {
+        let mut chunk = Zeroizing::new([0u8; 32]);
+        let part = secret.get(c * CHUNK..).unwrap_or(&[]);
+        let part = &part[..part.len().min(CHUNK)];
+        chunk[..part.len()].copy_from_slice(part);
+
+        coeffs.clear();
+        coeffs.push(Scalar::from_bytes_mod_order(*chunk));
+        coeffs.extend((1..threshold).map(|_| Scalar::random(rng)));
+
+        points.push(
+            coeffs
+                .iter()
+                .map(|a| RistrettoPoint::mul_base(a).compress())
+                .collect(),
+        );
+        for share in &mut shares {
+            share
+                .values
+                .push(eval_poly(&coeffs, Scalar::from_index(share.index)));
+        }
+    }
+
+    let commitments = Commitments {
+        secret_len: secret.len(),
+        points,
+    };
+    Ok((shares, commitments))
+}
+
+/// Verify every share against `commitments` and reconstruct the secret from
+/// the first `threshold` of them. Any share that fails verification is an
+/// error naming it, so a cheating dealer or custodian is identified.
+pub fn vss_reconstruct(
+    shares: &[VssShare],
+    commitments: &Commitments,
+) -> Result<Zeroizing<Vec<u8>>, VssError> {
+    let threshold = commitments.threshold();
+    if threshold == 0 || commitments.points.len() != chunk_count(commitments.secret_len) {
+        return Err(VssError::Malformed("commitments"));
+    }
+    if shares.len() < threshold {
+        return Err(VssError::NotEnoughShares);
+    }
+    for share in shares {
+        share.verify(commitments)?;
+    }
+
+    let used = &shares[..threshold];
+    let xs: Vec<Scalar> = used.iter().map(|s| Scalar::from_index(s.index)).collect();
+    let weights = lagrange_weights_at_zero(&xs).ok_or(VssError::InvalidIndex)?;
+
+    let mut secret = Zeroizing::new(Vec::with_capacity(commitments.points.len() * CHUNK));
+    for c in 0..commitments.points.len() {
+        let value = used
+            .iter()
+            .zip(&weights)
+            .fold(Scalar::ZERO, |acc, (s, &w)| acc + s.values[c] * w);
+        let bytes = Zeroizing::new(value.to_bytes());
+        secret.extend_from_slice(&bytes[..CHUNK]);
+    }
+    secret.truncate(commitments.secret_len);
+    Ok(secret)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn vss_round_trip_across_chunks() {
+        let secret: Vec<u8> = (0..100u8).collect();
+        let (shares, commitments) = vss_split(&secret, 3, 5).unwrap();
+        assert_eq!(commitments.points.len(), 4);
+        for share in &shares {
+            share.verify(&commitments).unwrap();
+        }
+        assert_eq!(
+            &vss_reconstruct(&shares[2..], &commitments).unwrap()[..],
+            &secret[..]
+        );
+
+        let published = Commitments::from_bytes(&commitments.to_bytes()).unwrap();
+        assert_eq!(published, commitments);
+        let back = VssShare::from_bytes(&shares[0].to_bytes()).unwrap();
+        assert_eq!(back, shares[0]);
+    }
+
+    #[test]
+    fn detects_inconsistent_dealer_and_tampered_shares() {
+        let (mut shares, commitments) = vss_split(b"vss", 2, 3).unwrap();
+        // A dealer that hands share 2 a value off the committed polynomial.
+        shares[1].values[0] += Scalar::ONE;
+        assert_eq!(
+            shares[1].verify(&commitments),
+            Err(VssError::InvalidShare(2))
+        );
+        assert_eq!(
+            vss_reconstruct(&shares, &commitments),
+            Err(VssError::InvalidShare(2))
+        );
+        // Shares from a different split do not match these commitments.
+        let (other, _) = vss_split(b"vss", 2, 3).unwrap();
+        assert!(other[0].verify(&commitments).is_err());
+        assert!(Commitments::from_bytes(&[1, 0, 0, 0, 2, 9]).is_err());
+    }
+
+    #[test]
+    fn empty_secret_and_parameter_checks() {
+        let (shares, commitments) = vss_split(b"", 2, 2).unwrap();
+        assert!(vss_reconstruct(&shares, &commitments).unwrap().is_empty());
+        assert_eq!(
+            vss_split(b"x", 3, 2).unwrap_err(),
+            VssError::InvalidParameters
+        );
+        assert_eq!(
+            vss_reconstruct(&shares[..1], &commitments).unwrap_err(),
+            VssError::NotEnoughShares
+        );
+    }
+}
@@ -0,0 +1,271 @@
+/*
+Passphrase-protected shares (behind the `passphrase` feature).
+
+For custodians who keep their share on a USB stick: the encoded frame is
+encrypted with XChaCha20-Poly1305 under a key derived from a passphrase with
+Argon2id. The wrapped blob has its own magic, which every decoder in this
+crate recognizes and reports as `ShareCodecError::Wrapped` instead of a
+generic format error, so callers know to ask for the passphrase.
+
+SHW1 layout (integers little-endian):
+  magic:  b"SHW1"     // 4 bytes
+  m_cost: u32         // Argon2id memory, KiB
+  t_cost: u32         // Argon2id passes
+  p_cost: u32         // Argon2id lanes
+  salt:   [u8; 16]
+  nonce:  [u8; 24]
+  ct:     [u8]        // AEAD(SHR1/SHR2 frame) || 16-byte tag
+
+The header is the AEAD's associated data, so cost parameters cannot be
+lowered undetected. The frame inside keeps its RS parity, but the tag rejects
+any damage to the ciphertext: store wrapped shares on media you trust, and
+keep them as the text form (`wrap_share` writes hex; any `ShareEncoding`
+of the same bytes unwraps too).
+*/
+
+use crate::Share;
+use crate::encoding::ENCODINGS;
+use crate::share_codec::{
+    CorrectionReport, MAGIC_WRAPPED, ShareCodecError, decode_frame, encode_frame, is_wrapped,
+};
+use alloc::string::String;
+use alloc::vec::Vec;
+use argon2::{Algorithm, Argon2, Params, Version};
+use chacha20poly1305::aead::{Aead, KeyInit, Payload};
+use chacha20poly1305::{XChaCha20Poly1305, XNonce};
+use rand::{CryptoRng, RngCore};
+use zeroize::Zeroizing;
+
+const SALT_LEN: usize = 16;
+const NONCE_LEN: usize = 24;
+const HEADER_LEN: usize = 4 + 12 + SALT_LEN + NONCE_LEN;
+
+/// Ceilings applied when wrapping and unwrapping, so a crafted header cannot
+/// make the reader allocate gigabytes or spin for minutes. Memory is capped at
+/// twice the default, which a browser tab can still afford.
+pub const MAX_M_COST: u32 = 2 * 64 * 1024; // 128 MiB
+const MAX_T_COST: u32 = 64;
+const MAX_P_COST: u32 = 16;
+
+/// Argon2id cost parameters.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct WrapParams {
+    /// Memory in KiB.
+    pub m_cost: u32,
+    /// Number of passes.
+    pub t_cost: u32,
+    /// Degree of parallelism.
+    pub p_cost: u32,
+}
+
+impl Default for WrapParams {
+    /// RFC 9106's second recommended option (64 MiB, 3 passes), single lane
+    /// so it runs the same in the browser.
+    fn default() -> Self {
+        WrapParams {
+            m_cost: 64 * 1024,
+            t_cost: 3,
+            p_cost: 1,
+        }
+    }
+}
+
+impl WrapParams {
+    /// Whether unwrapping will accept these costs.
+    fn within_limits(&self) -> bool {
+        self.m_cost <= MAX_M_COST && self.t_cost <= MAX_T_COST && self.p_cost <= MAX_P_COST
+    }
+}
+
+fn derive_key(
+    passphrase: &str,
+    salt: &[u8],
+    params: &WrapParams,
+) -> Result<Zeroizing<[u8; 32]>, ShareCodecError> {
+    let argon_params = Params::new(params.m_cost, params.t_cost, params.p_cost, Some(32))
+        .map_err(|_| ShareCodecError::Encoding("argon2 parameters"))?;
+    let mut key = Zeroizing::new([0u8; 32]);
+    Argon2::new(Algorithm::Argon2id, Version::V0x13, argon_params)
+        .hash_password_into(passphrase.as_bytes(), salt, &mut key[..])
+        .map_err(|_| ShareCodecError::Encoding("argon2 parameters"))?;
+    Ok(key)
+}
+
+/// Wrap `share` (framed with `ecc_len` parity bytes) under `passphrase`;
+/// returns hex like [`share_to_hex`](crate::share_to_hex).
+#[cfg(feature = "std")]
+pub fn wrap_share(
+    share: &Share,
+    ecc_len: usize,
+    passphrase: &str,
+    params: &WrapParams,
+) -> Result<String, ShareCodecError> {
+    wrap_share_with_rng(share, ecc_len, passphrase, params, &mut rand::thread_rng())
+}
+
+/// Like [`wrap_share`], but draws salt and nonce from `rng`.
+pub fn wrap_share_with_rng<R: RngCore + CryptoRng>(
+    share: &Share,
+    ecc_len: usize,
+    passphrase: &str,
+    params: &WrapParams,
+    rng: &mut R,
+) -> Result<String, ShareCodecError> {
+    if !params.within_limits() {
+        return Err(ShareCodecError::Encoding("argon2 parameters"));
+    }
+    let frame = encode_frame(share, ecc_len);
+    let mut salt = [0u8; SALT_LEN];
+    let mut nonce = [0u8; NONCE_LEN];
+    rng.fill_bytes(&mut salt);
+    rng.fill_bytes(&mut nonce);
+
+    let mut out = Vec::with_capacity(HEADER_LEN + frame.len() + 16);
+    out.extend_from_slice(MAGIC_WRAPPED);
+    out.extend_from_slice(&params.m_cost.to_le_bytes());
+    out.extend_from_slice(&params.t_cost.to_le_bytes());
+    out.extend_from_slice(&params.p_cost.to_le_bytes());
+    out.extend_from_slice(&salt);
+    out.extend_from_slice(&nonce);
+
+    let key = derive_key(passphrase, &salt, params)?;
+    let ct = XChaCha20Poly1305::new(key.as_ref().into())
+        .encrypt(
+            XNonce::from_slice(&nonce),
+            Payload {
+                msg: &frame,
+                aad: &out,
+            },
+        )
+        .map_err(|_| ShareCodecError::Passphrase)?;
+    out.extend_from_slice(&ct);
+    Ok(hex::encode(out))
+}
+
+/// Unwrap a passphrase-protected share in any `ShareEncoding` (hex by default).
+pub fn unwrap_share(
+    text: &str,
+    passphrase: &str,
+) -> Result<(Share, CorrectionReport), ShareCodecError> {
+    let text = text.trim();
+    let bytes = ENCODINGS
+        .iter()
+        .find_map(|enc| enc.decode(text).ok().filter(|b| is_wrapped(b)))
+        .ok_or(ShareCodecError::BadMagic)?;
+    let frame = unwrap_bytes(&bytes, passphrase)?;
+    decode_frame(&frame, &[])
+}
+
+/// Decrypt an `SHW1` blob back to the `SHR1`/`SHR2` frame inside it.
+pub fn unwrap_bytes(bytes: &[u8], passphrase: &str) -> Result<Zeroizing<Vec<u8>>, ShareCodecError> {
+    if !is_wrapped(bytes) {
+        return Err(ShareCodecError::BadMagic);
+    }
+    if bytes.len() < HEADER_LEN + 16 {
+        return Err(ShareCodecError::Truncated);
+    }
+    let (header, ct) = bytes.split_at(HEADER_LEN);
+    let word = |i: usize| u32::from_le_bytes(header[i..i + 4].try_into().unwrap());
+    let params = WrapParams {
+        m_cost: word(4),
+        t_cost: word(8),
+        p_cost: word(12),
+    };
+    if !params.within_limits() {
+        return Err(ShareCodecError::Encoding("argon2 parameters"));
+    }
+    let salt = &header[16..16 + SALT_LEN];
+    let nonce = &header[16 + SALT_LEN..];
+
+    let key = derive_key(passphrase, salt, &params)?;
+    let frame = XChaCha20Poly1305::new(key.as_ref().into())
+        .decrypt(
+            XNonce::from_slice(nonce),
+            Payload {
+                msg: ct,
+                aad: header,
+            },
+        )
+        .map_err(|_| ShareCodecError::Passphrase)?;
+    Ok(Zeroizing::new(frame))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{GF256, parse_any_share, share_decode, share_from_hex};
+
+    // Small costs keep the tests fast; the format is the same.
+    const FAST: WrapParams = WrapParams {
+        m_cost: 64,
+        t_cost: 1,
+        p_cost: 1,
+    };
+
+    fn sample() -> Share {
+        Share {
+            x: GF256(4),
+            y: b"usb stick".to_vec(),
+        }
+    }
+
+    #[test]
+    fn wrap_round_trip_and_wrong_passphrase() {
+        let text = wrap_share(&sample(), 8, "correct horse", &FAST).unwrap();
+        assert!(text.starts_with("53485731"));
+        let (share, report) = unwrap_share(&text, "correct horse").unwrap();
+        assert_eq!(share, sample());
+        assert_eq!(report.corrected(), 0);
+        assert!(matches!(
+            unwrap_share(&text, "battery staple"),
+            Err(ShareCodecError::Passphrase)
+        ));
+    }
+
+    #[test]
+    fn decoders_recognize_wrapped_shares() {
+        let text = wrap_share(&sample(), 8, "pw", &FAST).unwrap();
+        assert!(matches!(
+            share_from_hex(&text),
+            Err(ShareCodecError::Wrapped)
+        ));
+        assert!(matches!(share_decode(&text), Err(ShareCodecError::Wrapped)));
+        let err = parse_any_share(&text).unwrap_err();
+        assert!(err.needs_passphrase());
+    }
+
+    #[test]
+    fn header_is_authenticated() {
+        let mut bytes = hex::decode(wrap_share(&sample(), 8, "pw", &FAST).unwrap()).unwrap();
+        // Lowering t_cost must break the tag, not weaken the KDF.
+        bytes[8] = 2;
+        assert!(matches!(
+            unwrap_bytes(&bytes, "pw"),
+            Err(ShareCodecError::Passphrase)
+        ));
+        bytes[4..8].copy_from_slice(&u32::MAX.to_le_bytes());
+        assert!(matches!(
+            unwrap_bytes(&bytes, "pw"),
+            Err(ShareCodecError::Encoding(_))
+        ));
+    }
+
+    #[test]
+    fn memory_cost_above_the_cap_is_refused() {
+        let mut bytes = hex::decode(wrap_share(&sample(), 8, "pw", &FAST).unwrap()).unwrap();
+        // Rejected from the header alone, before Argon2 allocates anything.
+        bytes[4..8].copy_from_slice(&(MAX_M_COST + 1).to_le_bytes());
+        assert!(matches!(
+            unwrap_bytes(&bytes, "pw"),
+            Err(ShareCodecError::Encoding("argon2 parameters"))
+        ));
+
+        // Nor can such a share be written in the first place.
+        let greedy = WrapParams {
+            m_cost: MAX_M_COST + 1,
+            ..FAST
+        };
+        assert!(wrap_share(&sample(), 8, "pw", &greedy).is_err());
+        assert!(WrapParams::default().within_limits());
+    }
+}
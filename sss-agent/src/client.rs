@@ -0,0 +1,135 @@
+// client.rs
+//
+// The client side: `add`, `get`, `status` and `clear` each open one
+// connection per request. Shares are parsed here, in any format the library
+// reads, so the agent only ever sees bare (x, y) pairs.
+
+use crate::error::AgentError;
+use crate::protocol::{MAX_LINE, Request, Response};
+use clap::Args;
+use shamir_gf256::parse_share_list;
+use std::fs::{self, OpenOptions};
+use std::io::{self, Read, Write};
+use std::net::Shutdown;
+use std::os::unix::fs::OpenOptionsExt;
+use std::os::unix::net::UnixStream;
+use std::path::{Path, PathBuf};
+use zeroize::Zeroizing;
+
+#[derive(Args)]
+pub struct AddArgs {
+    /// Files holding shares (any format, several per file allowed); stdin if omitted
+    pub files: Vec<PathBuf>,
+}
+
+#[derive(Args)]
+pub struct GetArgs {
+    /// Write the secret to this new file (mode 0600) instead of stdout
+    #[arg(short, long)]
+    pub output: Option<PathBuf>,
+}
+
+/// Send one request and return the reply; `ERR` replies become errors.
+fn call(socket: &Path, request: &Request) -> Result<Response, AgentError> {
+    let mut stream = UnixStream::connect(socket).map_err(|source| AgentError::Connect {
+        path: socket.to_path_buf(),
+        source,
+    })?;
+    stream.write_all(request.to_line().as_bytes())?;
+    stream.shutdown(Shutdown::Write)?;
+    let mut reply = Zeroizing::new(String::with_capacity(MAX_LINE));
+    stream.take(MAX_LINE as u64).read_to_string(&mut reply)?;
+    match Response::parse(&reply).map_err(AgentError::Protocol)? {
+        Response::Err(reason) => Err(AgentError::Refused(reason)),
+        response => Ok(response),
+    }
+}
+
+fn expect_ok(response: Response) -> Result<String, AgentError> {
+    match response {
+        Response::Ok(text) => Ok(text),
+        _ => Err(AgentError::Protocol("expected OK".into())),
+    }
+}
+
+fn read_texts(paths: &[PathBuf]) -> Result<Vec<(String, Zeroizing<String>)>, AgentError> {
+    if paths.is_empty() || paths.iter().all(|p| p == Path::new("-")) {
+        let mut text = Zeroizing::new(String::new());
+        io::stdin().read_to_string(&mut text)?;
+        return Ok(vec![("<stdin>".to_string(), text)]);
+    }
+    paths
+        .iter()
+        .map(|p| {
+            let text = fs::read_to_string(p).map_err(|source| AgentError::File {
+                path: p.clone(),
+                source,
+            })?;
+            Ok((p.display().to_string(), Zeroizing::new(text)))
+        })
+        .collect()
+}
+
+pub fn add(socket: &Path, args: AddArgs) -> Result<(), AgentError> {
+    let mut shares = Vec::new();
+    for (label, text) in read_texts(&args.files)? {
+        for (line, outcome) in parse_share_list(&text) {
+            let location = format!("{label}:{line}");
+            match outcome {
+                Ok(parsed) => shares.push((location, parsed.share)),
+                Err(source) => return Err(AgentError::Parse { location, source }),
+            }
+        }
+    }
+    if shares.is_empty() {
+        return Err(AgentError::NoShares);
+    }
+    for (location, share) in shares {
+        let reply = call(socket, &Request::Add(share))
+            .and_then(expect_ok)
+            .map_err(|e| match e {
+                AgentError::Refused(reason) => AgentError::Refused(format!("{location}: {reason}")),
+                e => e,
+            })?;
+        println!("{location}: {reply}");
+    }
+    Ok(())
+}
+
+pub fn get(socket: &Path, args: GetArgs) -> Result<(), AgentError> {
+    let Response::Secret(secret) = call(socket, &Request::Get)? else {
+        return Err(AgentError::Protocol("expected SECRET".into()));
+    };
+    match &args.output {
+        Some(path) => {
+            let file_error = |source| AgentError::File {
+                path: path.clone(),
+                source,
+            };
+            OpenOptions::new()
+                .write(true)
+                .create_new(true)
+                .mode(0o600)
+                .open(path)
+                .map_err(file_error)?
+                .write_all(&secret)
+                .map_err(file_error)?;
+        }
+        None => {
+            let mut out = io::stdout().lock();
+            out.write_all(&secret)?;
+            out.flush()?;
+        }
+    }
+    Ok(())
+}
+
+pub fn status(socket: &Path) -> Result<(), AgentError> {
+    println!("{}", expect_ok(call(socket, &Request::Status)?)?);
+    Ok(())
+}
+
+pub fn clear(socket: &Path) -> Result<(), AgentError> {
+    println!("{}", expect_ok(call(socket, &Request::Clear)?)?);
+    Ok(())
+}
@@ -0,0 +1,249 @@
+// daemon.rs
+//
+// `sss-agent serve`: collect shares over a Unix socket, reconstruct once the
+// threshold is reached, hand the secret to clients running as the same user
+// until the TTL runs out, then wipe it. The TTL starts with the first share,
+// so a half-finished collection does not linger either.
+//
+// Requests are served one at a time; each is a single short line, and a
+// client that stalls is dropped after `IO_TIMEOUT`.
+
+use crate::error::AgentError;
+use crate::protocol::{MAX_LINE, Request, Response};
+use clap::Args;
+use shamir_gf256::{Share, reconstruct};
+use std::fs;
+use std::future::pending;
+use std::io;
+use std::os::unix::fs::PermissionsExt;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+use tokio::net::{UnixListener, UnixStream};
+use tokio::signal::unix::{SignalKind, signal};
+use tokio::time::{Instant, sleep_until, timeout};
+use zeroize::Zeroizing;
+
+/// How long a client may take to send its request and read the reply.
+const IO_TIMEOUT: Duration = Duration::from_secs(5);
+
+#[derive(Args)]
+pub struct ServeArgs {
+    /// Shares needed to reconstruct the secret
+    #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..))]
+    pub threshold: u8,
+    /// Seconds the shares and the secret are kept, from the first share
+    #[arg(long, default_value_t = 300, value_parser = clap::value_parser!(u64).range(1..))]
+    pub ttl: u64,
+}
+
+/// What the agent holds: shares being collected, or the secret they gave.
+struct Agent {
+    threshold: usize,
+    ttl: Duration,
+    shares: Vec<Share>,
+    secret: Option<Zeroizing<Vec<u8>>>,
+    deadline: Option<Instant>,
+}
+
+impl Agent {
+    fn wipe(&mut self) {
+        // `Share` and `Zeroizing` clear their bytes on drop.
+        self.shares.clear();
+        self.secret = None;
+        self.deadline = None;
+    }
+
+    fn remaining(&self) -> u64 {
+        self.deadline
+            .map_or(0, |d| d.saturating_duration_since(Instant::now()).as_secs())
+    }
+
+    fn add(&mut self, share: Share) -> Response {
+        if self.secret.is_some() {
+            return Response::Err("a secret is already held; clear it first".into());
+        }
+        if self.shares.iter().any(|s| s.x == share.x) {
+            return Response::Err(format!("share {} was already added", share.x.0));
+        }
+        if self
+            .shares
+            .first()
+            .is_some_and(|s| s.y.len() != share.y.len())
+        {
+            return Response::Err(format!(
+                "share {} is {} bytes long, the others are {}",
+                share.x.0,
+                share.y.len(),
+                self.shares[0].y.len()
+            ));
+        }
+        if self.shares.is_empty() {
+            self.shares.reserve_exact(self.threshold);
+            self.deadline = Some(Instant::now() + self.ttl);
+        }
+        self.shares.push(share);
+        if self.shares.len() < self.threshold {
+            return Response::Ok(format!(
+                "{} of {} shares",
+                self.shares.len(),
+                self.threshold
+            ));
+        }
+        self.secret = Some(Zeroizing::new(reconstruct(&self.shares, self.threshold)));
+        self.shares.clear();
+        self.deadline = Some(Instant::now() + self.ttl);
+        Response::Ok(format!(
+            "secret reconstructed; held for {} s",
+            self.ttl.as_secs()
+        ))
+    }
+
+    fn handle(&mut self, request: Request) -> Response {
+        match request {
+            Request::Add(share) => self.add(share),
+            Request::Get => match &self.secret {
+                Some(secret) => Response::Secret(secret.clone()),
+                None => Response::Err(format!(
+                    "no secret held ({} of {} shares)",
+                    self.shares.len(),
+                    self.threshold
+                )),
+            },
+            Request::Status => Response::Ok(match &self.secret {
+                Some(secret) => format!(
+                    "holding a {}-byte secret for {} s",
+                    secret.len(),
+                    self.remaining()
+                ),
+                None if self.shares.is_empty() => {
+                    format!("empty; waiting for {} shares", self.threshold)
+                }
+                None => format!(
+                    "collecting {} of {} shares for {} s",
+                    self.shares.len(),
+                    self.threshold,
+                    self.remaining()
+                ),
+            }),
+            Request::Clear => {
+                self.wipe();
+                Response::Ok("cleared".into())
+            }
+        }
+    }
+
+    async fn serve(&mut self, stream: UnixStream) -> io::Result<()> {
+        // SAFETY: geteuid has no preconditions and cannot fail.
+        let uid = unsafe { libc::geteuid() };
+        let peer = stream.peer_cred()?.uid();
+        let (reader, mut writer) = stream.into_split();
+        let response = if peer != uid {
+            Response::Err("permission denied".into())
+        } else {
+            let mut line = Zeroizing::new(String::with_capacity(MAX_LINE));
+            BufReader::new(reader.take(MAX_LINE as u64))
+                .read_line(&mut line)
+                .await?;
+            if !line.ends_with('\n') {
+                Response::Err("request too long or incomplete".into())
+            } else {
+                match Request::parse(&line) {
+                    Ok(request) => self.handle(request),
+                    Err(reason) => Response::Err(reason),
+                }
+            }
+        };
+        writer.write_all(response.to_line().as_bytes()).await?;
+        writer.shutdown().await
+    }
+}
+
+/// Removes the socket file when the agent exits.
+struct SocketFile(PathBuf);
+
+impl Drop for SocketFile {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.0);
+    }
+}
+
+/// Bind `path` with mode 0600, replacing a stale socket but never a live one.
+fn bind(path: &Path) -> Result<(UnixListener, SocketFile), AgentError> {
+    let file_error = |source| AgentError::File {
+        path: path.to_path_buf(),
+        source,
+    };
+    if path.exists() {
+        if std::os::unix::net::UnixStream::connect(path).is_ok() {
+            return Err(AgentError::AlreadyRunning(path.to_path_buf()));
+        }
+        fs::remove_file(path).map_err(file_error)?;
+    }
+    // Create the socket without group or world access from the start.
+    // SAFETY: umask only swaps the process file-mode mask.
+    let old = unsafe { libc::umask(0o177) };
+    let listener = UnixListener::bind(path);
+    unsafe { libc::umask(old) };
+    let listener = listener.map_err(file_error)?;
+    let guard = SocketFile(path.to_path_buf());
+    fs::set_permissions(path, fs::Permissions::from_mode(0o600)).map_err(file_error)?;
+    Ok((listener, guard))
+}
+
+async fn expiry(deadline: Option<Instant>) {
+    match deadline {
+        Some(deadline) => sleep_until(deadline).await,
+        None => pending().await,
+    }
+}
+
+async fn serve(listener: UnixListener, mut agent: Agent) -> io::Result<()> {
+    let mut terminate = signal(SignalKind::terminate())?;
+    loop {
+        tokio::select! {
+            accepted = listener.accept() => {
+                let (stream, _) = accepted?;
+                match timeout(IO_TIMEOUT, agent.serve(stream)).await {
+                    Ok(Ok(())) => {}
+                    Ok(Err(e)) => eprintln!("sss-agent: client: {e}"),
+                    Err(_) => eprintln!("sss-agent: client timed out"),
+                }
+            }
+            () = expiry(agent.deadline) => {
+                agent.wipe();
+                eprintln!("sss-agent: TTL expired; wiped");
+            }
+            _ = tokio::signal::ctrl_c() => break,
+            _ = terminate.recv() => break,
+        }
+    }
+    agent.wipe();
+    Ok(())
+}
+
+pub fn run(socket: &Path, args: ServeArgs) -> Result<(), AgentError> {
+    // Keep the secret out of core dumps and away from ptrace by other
+    // processes of the same user.
+    #[cfg(target_os = "linux")]
+    // SAFETY: PR_SET_DUMPABLE takes a plain integer argument.
+    unsafe {
+        libc::prctl(libc::PR_SET_DUMPABLE, 0);
+    }
+
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()?;
+    runtime.block_on(async {
+        let (listener, _socket) = bind(socket)?;
+        eprintln!("sss-agent: listening on {}", socket.display());
+        let agent = Agent {
+            threshold: args.threshold.into(),
+            ttl: Duration::from_secs(args.ttl),
+            shares: Vec::new(),
+            secret: None,
+            deadline: None,
+        };
+        Ok(serve(listener, agent).await?)
+    })
+}
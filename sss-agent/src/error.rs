@@ -0,0 +1,30 @@
+use shamir_gf256::ParseAnyError;
+use std::io;
+use std::path::PathBuf;
+use thiserror::Error;
+
+/// Everything that makes `sss-agent` exit non-zero.
+#[derive(Debug, Error)]
+pub enum AgentError {
+    #[error("{}: {source}", path.display())]
+    File { path: PathBuf, source: io::Error },
+    #[error("{0}")]
+    Io(#[from] io::Error),
+    #[error("{location}: {source}")]
+    Parse {
+        location: String,
+        source: ParseAnyError,
+    },
+    #[error("no shares found in the input")]
+    NoShares,
+    #[error("no agent socket: pass --socket or set SSS_AGENT_SOCK")]
+    NoSocket,
+    #[error("an agent is already listening on {}", .0.display())]
+    AlreadyRunning(PathBuf),
+    #[error("cannot reach the agent at {}: {source}", path.display())]
+    Connect { path: PathBuf, source: io::Error },
+    #[error("agent: {0}")]
+    Refused(String),
+    #[error("garbled reply from the agent: {0}")]
+    Protocol(String),
+}
@@ -0,0 +1,92 @@
+// main.rs
+//
+// `sss-agent`: an ssh-agent-style daemon for reconstructed secrets. `serve`
+// listens on a Unix socket, `add` feeds it shares, and once the threshold is
+// met `get` returns the secret to processes of the same user until the TTL
+// runs out and the agent wipes it.
+
+#[cfg(unix)]
+mod client;
+#[cfg(unix)]
+mod daemon;
+#[cfg(unix)]
+mod error;
+#[cfg(unix)]
+mod protocol;
+
+use std::process::ExitCode;
+
+#[cfg(unix)]
+mod cli {
+    use crate::error::AgentError;
+    use crate::{client, daemon};
+    use clap::{Parser, Subcommand};
+    use std::path::PathBuf;
+
+    #[derive(Parser)]
+    #[command(
+        name = "sss-agent",
+        version,
+        about = "Hold a reconstructed secret in memory for a limited time"
+    )]
+    pub struct Cli {
+        /// Agent socket [default: $XDG_RUNTIME_DIR/sss-agent.sock]
+        #[arg(short, long, global = true, env = "SSS_AGENT_SOCK")]
+        socket: Option<PathBuf>,
+        #[command(subcommand)]
+        command: Command,
+    }
+
+    #[derive(Subcommand)]
+    enum Command {
+        /// Run the agent in the foreground
+        Serve(daemon::ServeArgs),
+        /// Send shares to the agent
+        Add(client::AddArgs),
+        /// Fetch the reconstructed secret
+        Get(client::GetArgs),
+        /// Show what the agent holds
+        Status,
+        /// Wipe the shares and the secret now
+        Clear,
+    }
+
+    fn socket_path(socket: Option<PathBuf>) -> Result<PathBuf, AgentError> {
+        socket
+            .or_else(|| {
+                std::env::var_os("XDG_RUNTIME_DIR")
+                    .filter(|dir| !dir.is_empty())
+                    .map(|dir| PathBuf::from(dir).join("sss-agent.sock"))
+            })
+            .ok_or(AgentError::NoSocket)
+    }
+
+    pub fn run(cli: Cli) -> Result<(), AgentError> {
+        let socket = socket_path(cli.socket)?;
+        match cli.command {
+            Command::Serve(args) => daemon::run(&socket, args),
+            Command::Add(args) => client::add(&socket, args),
+            Command::Get(args) => client::get(&socket, args),
+            Command::Status => client::status(&socket),
+            Command::Clear => client::clear(&socket),
+        }
+    }
+}
+
+#[cfg(unix)]
+fn main() -> ExitCode {
+    use clap::Parser;
+    match cli::run(cli::Cli::parse()) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("sss-agent: error: {e}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+#[cfg(not(unix))]
+fn main() -> ExitCode {
+    eprintln!("sss-agent: error: Unix domain sockets are required");
+    ExitCode::FAILURE
+}
@@ -0,0 +1,103 @@
+// protocol.rs
+//
+// One request line and one response line per connection:
+//
+//   ADD <x> <y-hex>   ->  OK <progress>   | ERR <reason>
+//   GET               ->  SECRET <hex>    | ERR <reason>
+//   STATUS            ->  OK <status>
+//   CLEAR             ->  OK <message>
+//
+// Lines carrying share or secret bytes are built and read in `Zeroizing`
+// buffers with room reserved up front, so they are not reallocated (and left
+// behind) while they grow.
+
+use shamir_gf256::{GF256, Share};
+use std::fmt::Write;
+use zeroize::Zeroizing;
+
+/// Longest line either side accepts: a 64 KiB secret in hex, plus framing.
+pub const MAX_LINE: usize = 2 * 64 * 1024 + 64;
+
+pub enum Request {
+    Add(Share),
+    Get,
+    Status,
+    Clear,
+}
+
+pub enum Response {
+    Ok(String),
+    Secret(Zeroizing<Vec<u8>>),
+    Err(String),
+}
+
+fn hex_line(prefix: &str, bytes: &[u8]) -> Zeroizing<String> {
+    let mut line = Zeroizing::new(String::with_capacity(prefix.len() + 2 * bytes.len() + 1));
+    line.push_str(prefix);
+    for b in bytes {
+        let _ = write!(line, "{b:02x}");
+    }
+    line.push('\n');
+    line
+}
+
+fn unhex(text: &str) -> Result<Zeroizing<Vec<u8>>, String> {
+    let mut bytes = Zeroizing::new(vec![0u8; text.len() / 2]);
+    hex::decode_to_slice(text, &mut bytes).map_err(|e| format!("bad hex: {e}"))?;
+    Ok(bytes)
+}
+
+impl Request {
+    pub fn to_line(&self) -> Zeroizing<String> {
+        match self {
+            Request::Add(share) => hex_line(&format!("ADD {} ", share.x.0), &share.y),
+            Request::Get => Zeroizing::new("GET\n".into()),
+            Request::Status => Zeroizing::new("STATUS\n".into()),
+            Request::Clear => Zeroizing::new("CLEAR\n".into()),
+        }
+    }
+
+    pub fn parse(line: &str) -> Result<Request, String> {
+        let line = line.trim_end();
+        let (verb, rest) = line.split_once(' ').unwrap_or((line, ""));
+        match verb {
+            "ADD" => {
+                let (x, y) = rest.split_once(' ').ok_or("ADD needs x and y")?;
+                let x: u8 = x.parse().map_err(|_| "x must be a number in 1..=255")?;
+                if x == 0 {
+                    return Err("x must be a number in 1..=255".into());
+                }
+                let y = unhex(y)?;
+                Ok(Request::Add(Share {
+                    x: GF256(x),
+                    y: y.to_vec(),
+                }))
+            }
+            "GET" => Ok(Request::Get),
+            "STATUS" => Ok(Request::Status),
+            "CLEAR" => Ok(Request::Clear),
+            _ => Err(format!("unknown request {verb:?}")),
+        }
+    }
+}
+
+impl Response {
+    pub fn to_line(&self) -> Zeroizing<String> {
+        match self {
+            Response::Ok(text) => Zeroizing::new(format!("OK {text}\n")),
+            Response::Secret(secret) => hex_line("SECRET ", secret),
+            Response::Err(text) => Zeroizing::new(format!("ERR {text}\n")),
+        }
+    }
+
+    pub fn parse(line: &str) -> Result<Response, String> {
+        let line = line.trim_end();
+        let (verb, rest) = line.split_once(' ').unwrap_or((line, ""));
+        match verb {
+            "OK" => Ok(Response::Ok(rest.to_string())),
+            "SECRET" => Ok(Response::Secret(unhex(rest)?)),
+            "ERR" => Ok(Response::Err(rest.to_string())),
+            _ => Err(format!("unexpected reply {verb:?}")),
+        }
+    }
+}
@@ -0,0 +1,158 @@
+// agent.rs
+//
+// Runs the `sss-agent` binary as a daemon on a socket in a temp directory
+// and drives it with the client subcommands.
+
+#![cfg(unix)]
+
+use shamir_gf256::{share_to_hex, split};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command, Output, Stdio};
+use std::thread::sleep;
+use std::time::{Duration, Instant};
+use std::{env, fs, process};
+
+const BIN: &str = env!("CARGO_BIN_EXE_sss-agent");
+
+struct Agent {
+    dir: PathBuf,
+    socket: PathBuf,
+    child: Child,
+}
+
+impl Agent {
+    fn start(name: &str, threshold: u8, ttl: u64) -> Agent {
+        let dir = env::temp_dir().join(format!("sss-agent-{name}-{}", process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        let socket = dir.join("agent.sock");
+        let child = Command::new(BIN)
+            .args([
+                "serve",
+                "-t",
+                &threshold.to_string(),
+                "--ttl",
+                &ttl.to_string(),
+            ])
+            .arg("--socket")
+            .arg(&socket)
+            .stderr(Stdio::null())
+            .spawn()
+            .unwrap();
+        let started = Instant::now();
+        while !socket.exists() {
+            assert!(
+                started.elapsed() < Duration::from_secs(10),
+                "agent did not start"
+            );
+            sleep(Duration::from_millis(20));
+        }
+        Agent { dir, socket, child }
+    }
+
+    fn run(&self, args: &[&str], stdin: &str) -> Output {
+        let mut child = Command::new(BIN)
+            .args(args)
+            .env("SSS_AGENT_SOCK", &self.socket)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .unwrap();
+        child
+            .stdin
+            .take()
+            .unwrap()
+            .write_all(stdin.as_bytes())
+            .unwrap();
+        child.wait_with_output().unwrap()
+    }
+
+    fn stop(mut self) -> PathBuf {
+        // SAFETY: plain kill(2) on our own child.
+        unsafe { libc::kill(self.child.id() as libc::pid_t, libc::SIGTERM) };
+        assert!(self.child.wait().unwrap().success());
+        self.socket.clone()
+    }
+}
+
+impl Drop for Agent {
+    fn drop(&mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+        let _ = fs::remove_dir_all(&self.dir);
+    }
+}
+
+fn stderr(output: &Output) -> String {
+    String::from_utf8_lossy(&output.stderr).into_owned()
+}
+
+fn mode(path: &Path) -> u32 {
+    use std::os::unix::fs::PermissionsExt;
+    fs::metadata(path).unwrap().permissions().mode() & 0o777
+}
+
+#[test]
+fn serves_the_secret_until_the_ttl_expires() {
+    let agent = Agent::start("ttl", 2, 2);
+    assert_eq!(mode(&agent.socket), 0o600);
+    let shares = split(b"disk unlock key", 2, 3);
+
+    let added = agent.run(&["add"], &share_to_hex(&shares[0], 16));
+    assert!(added.status.success(), "{}", stderr(&added));
+    assert!(String::from_utf8_lossy(&added.stdout).contains("1 of 2 shares"));
+    let early = agent.run(&["get"], "");
+    assert!(!early.status.success());
+    assert!(stderr(&early).contains("no secret held (1 of 2 shares)"));
+
+    let added = agent.run(&["add"], &share_to_hex(&shares[0], 16));
+    assert!(stderr(&added).contains("share 1 was already added"));
+    let added = agent.run(&["add"], &share_to_hex(&shares[2], 16));
+    assert!(added.status.success(), "{}", stderr(&added));
+    assert!(String::from_utf8_lossy(&added.stdout).contains("secret reconstructed"));
+
+    let got = agent.run(&["get"], "");
+    assert!(got.status.success(), "{}", stderr(&got));
+    assert_eq!(got.stdout, b"disk unlock key");
+    let out = agent.dir.join("secret");
+    let got = agent.run(&["get", "-o", out.to_str().unwrap()], "");
+    assert!(got.status.success(), "{}", stderr(&got));
+    assert_eq!(fs::read(&out).unwrap(), b"disk unlock key");
+    assert_eq!(mode(&out), 0o600);
+
+    sleep(Duration::from_millis(2500));
+    let late = agent.run(&["get"], "");
+    assert!(!late.status.success());
+    assert!(stderr(&late).contains("no secret held (0 of 2 shares)"));
+
+    let socket = agent.stop();
+    assert!(!socket.exists(), "the socket is removed on exit");
+}
+
+#[test]
+fn clear_wipes_and_a_second_agent_is_refused() {
+    let agent = Agent::start("clear", 1, 300);
+    let shares = split(b"short", 1, 1);
+    let added = agent.run(&["add"], &share_to_hex(&shares[0], 16));
+    assert!(added.status.success(), "{}", stderr(&added));
+    let status = agent.run(&["status"], "");
+    assert!(String::from_utf8_lossy(&status.stdout).contains("holding a 5-byte secret"));
+
+    let second = Command::new(BIN)
+        .args(["serve", "-t", "1", "--socket"])
+        .arg(&agent.socket)
+        .output()
+        .unwrap();
+    assert!(!second.status.success());
+    assert!(stderr(&second).contains("already listening"));
+
+    let bad = agent.run(&["add"], "not a share\n");
+    assert!(stderr(&bad).contains("<stdin>:1"), "{}", stderr(&bad));
+
+    assert!(agent.run(&["clear"], "").status.success());
+    let status = agent.run(&["status"], "");
+    assert!(String::from_utf8_lossy(&status.stdout).contains("empty"));
+    assert!(!agent.run(&["get"], "").status.success());
+}
@@ -0,0 +1,57 @@
+// error.rs
+//
+// Errors returned to HTTP clients. Messages never include share material or
+// tokens; they name custodians so the owner knows whom to chase.
+
+use axum::http::{StatusCode, header};
+use axum::response::{IntoResponse, Response};
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum PortalError {
+    #[error("missing or invalid bearer token")]
+    Unauthorized,
+    #[error("no such session (it may have expired or been closed)")]
+    NotFound,
+    #[error("{0}")]
+    BadRequest(String),
+    #[error("{0} has already submitted a share")]
+    AlreadySubmitted(String),
+    #[error("share {x} was already submitted by {holder}")]
+    DuplicateShare { x: u8, holder: String },
+    #[error("{collected} of {threshold} shares collected")]
+    NotReady { collected: usize, threshold: usize },
+    #[error("the share from {0} does not fit the others; nothing was released")]
+    Tampered(String),
+    #[error(
+        "the shares do not lie on one polynomial and the odd one out cannot be identified; nothing was released"
+    )]
+    Inconsistent,
+}
+
+impl PortalError {
+    fn status(&self) -> StatusCode {
+        match self {
+            PortalError::Unauthorized => StatusCode::UNAUTHORIZED,
+            PortalError::NotFound => StatusCode::NOT_FOUND,
+            PortalError::BadRequest(_) => StatusCode::BAD_REQUEST,
+            PortalError::AlreadySubmitted(_)
+            | PortalError::DuplicateShare { .. }
+            | PortalError::NotReady { .. }
+            | PortalError::Tampered(_)
+            | PortalError::Inconsistent => StatusCode::CONFLICT,
+        }
+    }
+}
+
+impl IntoResponse for PortalError {
+    fn into_response(self) -> Response {
+        let status = self.status();
+        let body = format!("{self}\n");
+        if status == StatusCode::UNAUTHORIZED {
+            (status, [(header::WWW_AUTHENTICATE, "Bearer")], body).into_response()
+        } else {
+            (status, body).into_response()
+        }
+    }
+}
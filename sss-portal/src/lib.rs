@@ -0,0 +1,142 @@
+// lib.rs
+//
+// `sss-portal`: an HTTPS service for asynchronous recovery. An operator opens
+// a session naming the custodians and the threshold; each custodian submits
+// their share with their own bearer token whenever they are online; the
+// session owner collects the secret once enough shares are in, which closes
+// the session and wipes it. Sessions that expire first are wiped too.
+//
+//   POST   /sessions               operator  open a session
+//   GET    /sessions/{id}          any       progress
+//   POST   /sessions/{id}/share    custodian submit one share (text body)
+//   POST   /sessions/{id}/release  owner     reconstruct, return the secret, close
+//   DELETE /sessions/{id}          owner     close without reconstructing
+//
+// The router is exposed so tests (and embedders) can serve it in-process.
+
+pub mod error;
+pub mod session;
+
+use axum::extract::{DefaultBodyLimit, Path, State};
+use axum::http::{HeaderMap, StatusCode, header};
+use axum::response::IntoResponse;
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use error::PortalError;
+use session::{Created, Sessions, Status, token_hash};
+use std::sync::Arc;
+use std::time::Duration;
+use zeroize::Zeroizing;
+
+/// Session lifetime unless the operator asks for another.
+pub const DEFAULT_TTL: Duration = Duration::from_secs(24 * 60 * 60);
+/// Longest lifetime a session may be given.
+pub const MAX_TTL: Duration = Duration::from_secs(7 * 24 * 60 * 60);
+/// Request bodies are a share or a short JSON document.
+const BODY_LIMIT: usize = 16 * 1024;
+
+/// Shared state behind the router.
+pub struct Portal {
+    operator: [u8; 32],
+    pub sessions: Sessions,
+}
+
+impl Portal {
+    /// `operator_token` authorizes opening sessions; only its digest is kept.
+    pub fn new(operator_token: &str) -> Self {
+        Portal {
+            operator: token_hash(operator_token),
+            sessions: Sessions::default(),
+        }
+    }
+}
+
+pub fn router(portal: Arc<Portal>) -> Router {
+    Router::new()
+        .route("/sessions", post(create))
+        .route("/sessions/{id}", get(status).delete(cancel))
+        .route("/sessions/{id}/share", post(submit))
+        .route("/sessions/{id}/release", post(release))
+        .layer(DefaultBodyLimit::max(BODY_LIMIT))
+        .with_state(portal)
+}
+
+fn bearer(headers: &HeaderMap) -> Result<&str, PortalError> {
+    headers
+        .get(header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.strip_prefix("Bearer "))
+        .map(str::trim)
+        .ok_or(PortalError::Unauthorized)
+}
+
+#[derive(serde::Deserialize)]
+struct CreateRequest {
+    threshold: usize,
+    custodians: Vec<String>,
+    ttl_secs: Option<u64>,
+}
+
+async fn create(
+    State(portal): State<Arc<Portal>>,
+    headers: HeaderMap,
+    Json(request): Json<CreateRequest>,
+) -> Result<(StatusCode, Json<Created>), PortalError> {
+    if token_hash(bearer(&headers)?) != portal.operator {
+        return Err(PortalError::Unauthorized);
+    }
+    let ttl = request
+        .ttl_secs
+        .map_or(DEFAULT_TTL, Duration::from_secs)
+        .min(MAX_TTL);
+    let created = portal
+        .sessions
+        .create(request.threshold, &request.custodians, ttl)?;
+    Ok((StatusCode::CREATED, Json(created)))
+}
+
+async fn status(
+    State(portal): State<Arc<Portal>>,
+    Path(id): Path<String>,
+    headers: HeaderMap,
+) -> Result<Json<Status>, PortalError> {
+    Ok(Json(portal.sessions.status(&id, bearer(&headers)?)?))
+}
+
+async fn submit(
+    State(portal): State<Arc<Portal>>,
+    Path(id): Path<String>,
+    headers: HeaderMap,
+    body: String,
+) -> Result<Json<Status>, PortalError> {
+    let body = Zeroizing::new(body);
+    Ok(Json(portal.sessions.submit(
+        &id,
+        bearer(&headers)?,
+        &body,
+    )?))
+}
+
+async fn release(
+    State(portal): State<Arc<Portal>>,
+    Path(id): Path<String>,
+    headers: HeaderMap,
+) -> Result<impl IntoResponse, PortalError> {
+    let secret = portal.sessions.release(&id, bearer(&headers)?)?;
+    Ok((
+        [
+            (header::CONTENT_TYPE, "application/octet-stream"),
+            (header::CACHE_CONTROL, "no-store"),
+        ],
+        secret.to_vec(),
+    ))
+}
+
+async fn cancel(
+    State(portal): State<Arc<Portal>>,
+    Path(id): Path<String>,
+    headers: HeaderMap,
+) -> Result<StatusCode, PortalError> {
+    portal.sessions.cancel(&id, bearer(&headers)?)?;
+    Ok(StatusCode::NO_CONTENT)
+}
@@ -0,0 +1,112 @@
+// main.rs
+//
+// Serves the portal over HTTPS (rustls). `--plain-http` is for running behind
+// a TLS-terminating proxy on the same host and is refused on non-loopback
+// addresses, so shares never cross a network in the clear.
+
+use axum_server::Handle;
+use axum_server::tls_rustls::RustlsConfig;
+use clap::Parser;
+use sss_portal::{Portal, router};
+use std::io;
+use std::net::SocketAddr;
+use std::path::PathBuf;
+use std::process::ExitCode;
+use std::sync::Arc;
+use std::time::Duration;
+use zeroize::Zeroizing;
+
+/// How often expired sessions are wiped.
+const SWEEP_INTERVAL: Duration = Duration::from_secs(60);
+
+#[derive(Parser)]
+#[command(
+    name = "sss-portal",
+    version,
+    about = "HTTPS portal where custodians submit shares for asynchronous recovery"
+)]
+struct Args {
+    /// Address to listen on
+    #[arg(long, default_value = "127.0.0.1:8443")]
+    listen: SocketAddr,
+    /// PEM certificate chain
+    #[arg(long, required_unless_present = "plain_http")]
+    cert: Option<PathBuf>,
+    /// PEM private key
+    #[arg(long, required_unless_present = "plain_http")]
+    key: Option<PathBuf>,
+    /// File holding the operator token that may open sessions
+    #[arg(long)]
+    operator_token_file: PathBuf,
+    /// Serve plain HTTP (loopback only, behind a TLS proxy)
+    #[arg(long, conflicts_with_all = ["cert", "key"])]
+    plain_http: bool,
+}
+
+fn file_error(path: &std::path::Path, e: io::Error) -> io::Error {
+    io::Error::new(e.kind(), format!("{}: {e}", path.display()))
+}
+
+async fn run(args: Args) -> io::Result<()> {
+    let token = Zeroizing::new(
+        std::fs::read_to_string(&args.operator_token_file)
+            .map_err(|e| file_error(&args.operator_token_file, e))?,
+    );
+    if token.trim().is_empty() {
+        return Err(io::Error::other("the operator token file is empty"));
+    }
+    let portal = Arc::new(Portal::new(token.trim()));
+    drop(token);
+
+    let sweeper = Arc::clone(&portal);
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(SWEEP_INTERVAL);
+        loop {
+            interval.tick().await;
+            sweeper.sessions.sweep();
+        }
+    });
+
+    let handle = Handle::new();
+    let shutdown = handle.clone();
+    tokio::spawn(async move {
+        let _ = tokio::signal::ctrl_c().await;
+        shutdown.graceful_shutdown(Some(Duration::from_secs(5)));
+    });
+
+    let app = router(portal).into_make_service();
+    if args.plain_http {
+        if !args.listen.ip().is_loopback() {
+            return Err(io::Error::other(
+                "--plain-http is only allowed on a loopback address",
+            ));
+        }
+        eprintln!("sss-portal: listening on http://{}", args.listen);
+        axum_server::bind(args.listen)
+            .handle(handle)
+            .serve(app)
+            .await
+    } else {
+        let (cert, key) = (args.cert.unwrap(), args.key.unwrap());
+        let tls = RustlsConfig::from_pem_file(&cert, &key)
+            .await
+            .map_err(|e| file_error(&cert, e))?;
+        eprintln!("sss-portal: listening on https://{}", args.listen);
+        axum_server::bind_rustls(args.listen, tls)
+            .handle(handle)
+            .serve(app)
+            .await
+    }
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let _ = rustls::crypto::ring::default_provider().install_default();
+    match run(Args::parse()).await {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("sss-portal: error: {e}");
+            ExitCode::FAILURE
+        }
+    }
+}
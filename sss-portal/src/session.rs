@@ -0,0 +1,441 @@
+// session.rs
+//
+// Recovery sessions: who may submit, what has been submitted, and when it all
+// expires. Share bytes are sealed with ChaCha20-Poly1305 under a random
+// per-session key as soon as they arrive, so a stray debug print or heap
+// snapshot of the store shows ciphertext; the key lives only as long as the
+// session. Tokens are kept as SHA-256 digests, never in the clear.
+
+use crate::error::PortalError;
+use chacha20poly1305::aead::{Aead, KeyInit, Payload};
+use chacha20poly1305::{ChaCha20Poly1305, Key, Nonce};
+use rand::RngCore;
+use rand::rngs::OsRng;
+use sha2::{Digest, Sha256};
+use shamir_gf256::{GF256, ShareRef, inconsistent_shares, parse_any_share, reconstruct_into};
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::Duration;
+use tokio::time::Instant;
+use zeroize::{Zeroize, Zeroizing};
+
+/// Random bytes in session ids and bearer tokens.
+const ID_LEN: usize = 16;
+const TOKEN_LEN: usize = 32;
+
+type TokenHash = [u8; 32];
+
+fn random_hex(len: usize) -> String {
+    let mut bytes = vec![0u8; len];
+    OsRng.fill_bytes(&mut bytes);
+    let hex = bytes.iter().map(|b| format!("{b:02x}")).collect();
+    bytes.zeroize();
+    hex
+}
+
+pub(crate) fn token_hash(token: &str) -> TokenHash {
+    Sha256::digest(token.as_bytes()).into()
+}
+
+struct Custodian {
+    name: String,
+    token: TokenHash,
+    submitted: bool,
+}
+
+/// A share as held in memory: x in the clear, y sealed.
+struct Sealed {
+    custodian: usize,
+    x: GF256,
+    nonce: [u8; 12],
+    y: Vec<u8>,
+}
+
+struct Session {
+    threshold: usize,
+    owner: TokenHash,
+    custodians: Vec<Custodian>,
+    key: Zeroizing<[u8; 32]>,
+    shares: Vec<Sealed>,
+    secret_len: Option<usize>,
+    expires_at: Instant,
+}
+
+/// Who presented a token.
+enum Role {
+    Owner,
+    Custodian(usize),
+}
+
+impl Session {
+    fn role(&self, token: &str) -> Option<Role> {
+        let hash = token_hash(token);
+        if hash == self.owner {
+            return Some(Role::Owner);
+        }
+        self.custodians
+            .iter()
+            .position(|c| c.token == hash)
+            .map(Role::Custodian)
+    }
+
+    fn cipher(&self) -> ChaCha20Poly1305 {
+        ChaCha20Poly1305::new(Key::from_slice(&self.key[..]))
+    }
+
+    /// Binds each ciphertext to its session and x-coordinate.
+    fn aad(id: &str, x: GF256) -> Vec<u8> {
+        let mut aad = id.as_bytes().to_vec();
+        aad.push(x.0);
+        aad
+    }
+
+    fn seal(&self, id: &str, custodian: usize, x: GF256, y: &[u8]) -> Sealed {
+        let mut nonce = [0u8; 12];
+        OsRng.fill_bytes(&mut nonce);
+        let aad = Self::aad(id, x);
+        let y = self
+            .cipher()
+            .encrypt(Nonce::from_slice(&nonce), Payload { msg: y, aad: &aad })
+            .expect("ChaCha20-Poly1305 encryption cannot fail for in-memory buffers");
+        Sealed {
+            custodian,
+            x,
+            nonce,
+            y,
+        }
+    }
+
+    fn open(&self, id: &str, sealed: &Sealed) -> Zeroizing<Vec<u8>> {
+        let aad = Self::aad(id, sealed.x);
+        let y = self
+            .cipher()
+            .decrypt(
+                Nonce::from_slice(&sealed.nonce),
+                Payload {
+                    msg: &sealed.y,
+                    aad: &aad,
+                },
+            )
+            .expect("sealed shares are only written by this session");
+        Zeroizing::new(y)
+    }
+
+    fn status(&self) -> Status {
+        let (submitted, pending) = self
+            .custodians
+            .iter()
+            .partition::<Vec<_>, _>(|c| c.submitted);
+        Status {
+            threshold: self.threshold,
+            collected: self.shares.len(),
+            submitted: submitted.into_iter().map(|c| c.name.clone()).collect(),
+            pending: pending.into_iter().map(|c| c.name.clone()).collect(),
+            expires_in: self
+                .expires_at
+                .saturating_duration_since(Instant::now())
+                .as_secs(),
+        }
+    }
+}
+
+/// What the operator gets back when opening a session. The tokens are shown
+/// once; hand each custodian theirs out of band.
+#[derive(Debug, serde::Serialize)]
+pub struct Created {
+    pub id: String,
+    pub owner_token: String,
+    pub custodians: Vec<CustodianToken>,
+    pub expires_in: u64,
+}
+
+#[derive(Debug, serde::Serialize)]
+pub struct CustodianToken {
+    pub name: String,
+    pub token: String,
+}
+
+/// Progress of a session, as shown to its owner and custodians.
+#[derive(Debug, serde::Serialize)]
+pub struct Status {
+    pub threshold: usize,
+    pub collected: usize,
+    pub submitted: Vec<String>,
+    pub pending: Vec<String>,
+    pub expires_in: u64,
+}
+
+/// All open sessions. Expired ones are unreachable at once and dropped on
+/// the next `sweep` or access.
+#[derive(Default)]
+pub struct Sessions {
+    inner: Mutex<HashMap<String, Session>>,
+}
+
+impl Sessions {
+    pub fn create(
+        &self,
+        threshold: usize,
+        custodians: &[String],
+        ttl: Duration,
+    ) -> Result<Created, PortalError> {
+        if custodians.len() > 255 || !(1..=custodians.len()).contains(&threshold) {
+            return Err(PortalError::BadRequest(
+                "threshold must be between 1 and the number of custodians (at most 255)".into(),
+            ));
+        }
+        for (i, name) in custodians.iter().enumerate() {
+            if name.trim().is_empty() {
+                return Err(PortalError::BadRequest(
+                    "custodian names must not be empty".into(),
+                ));
+            }
+            if custodians[..i].contains(name) {
+                return Err(PortalError::BadRequest(format!(
+                    "custodian {name:?} is listed twice"
+                )));
+            }
+        }
+
+        let id = random_hex(ID_LEN);
+        let owner_token = random_hex(TOKEN_LEN);
+        let tokens: Vec<CustodianToken> = custodians
+            .iter()
+            .map(|name| CustodianToken {
+                name: name.clone(),
+                token: random_hex(TOKEN_LEN),
+            })
+            .collect();
+        let mut key = Zeroizing::new([0u8; 32]);
+        OsRng.fill_bytes(&mut key[..]);
+
+        let session = Session {
+            threshold,
+            owner: token_hash(&owner_token),
+            custodians: tokens
+                .iter()
+                .map(|t| Custodian {
+                    name: t.name.clone(),
+                    token: token_hash(&t.token),
+                    submitted: false,
+                })
+                .collect(),
+            key,
+            shares: Vec::new(),
+            secret_len: None,
+            expires_at: Instant::now() + ttl,
+        };
+        self.inner.lock().unwrap().insert(id.clone(), session);
+        Ok(Created {
+            id,
+            owner_token,
+            custodians: tokens,
+            expires_in: ttl.as_secs(),
+        })
+    }
+
+    /// Run `f` on a live session, dropping it first if it has expired.
+    fn with<T>(
+        &self,
+        id: &str,
+        f: impl FnOnce(&mut Session) -> Result<T, PortalError>,
+    ) -> Result<T, PortalError> {
+        let mut sessions = self.inner.lock().unwrap();
+        match sessions.get_mut(id) {
+            Some(session) if session.expires_at > Instant::now() => f(session),
+            Some(_) => {
+                sessions.remove(id);
+                Err(PortalError::NotFound)
+            }
+            None => Err(PortalError::NotFound),
+        }
+    }
+
+    pub fn status(&self, id: &str, token: &str) -> Result<Status, PortalError> {
+        self.with(id, |session| {
+            session.role(token).ok_or(PortalError::Unauthorized)?;
+            Ok(session.status())
+        })
+    }
+
+    /// Accept one custodian's share. Each custodian submits once; shares
+    /// must be distinct and as long as the first one.
+    pub fn submit(&self, id: &str, token: &str, text: &str) -> Result<Status, PortalError> {
+        self.with(id, |session| {
+            let Some(Role::Custodian(custodian)) = session.role(token) else {
+                return Err(PortalError::Unauthorized);
+            };
+            if session.custodians[custodian].submitted {
+                return Err(PortalError::AlreadySubmitted(
+                    session.custodians[custodian].name.clone(),
+                ));
+            }
+            let parsed =
+                parse_any_share(text).map_err(|e| PortalError::BadRequest(e.to_string()))?;
+            let share = parsed.share;
+            if let Some(other) = session.shares.iter().find(|s| s.x == share.x) {
+                return Err(PortalError::DuplicateShare {
+                    x: share.x.0,
+                    holder: session.custodians[other.custodian].name.clone(),
+                });
+            }
+            if session.secret_len.is_some_and(|len| len != share.y.len()) {
+                return Err(PortalError::BadRequest(
+                    "share length differs from the shares submitted so far".into(),
+                ));
+            }
+
+            let sealed = session.seal(id, custodian, share.x, &share.y);
+            session.shares.push(sealed);
+            session.secret_len = Some(share.y.len());
+            session.custodians[custodian].submitted = true;
+            Ok(session.status())
+        })
+    }
+
+    /// Reconstruct for the owner once the threshold is met, then wipe the
+    /// session. When more than `threshold` shares are in, they must all lie
+    /// on one polynomial; otherwise nothing is released, and the custodian
+    /// whose share is the odd one out is named if that can be decided.
+    pub fn release(&self, id: &str, token: &str) -> Result<Zeroizing<Vec<u8>>, PortalError> {
+        let secret = self.with(id, |session| {
+            let Some(Role::Owner) = session.role(token) else {
+                return Err(PortalError::Unauthorized);
+            };
+            if session.shares.len() < session.threshold {
+                return Err(PortalError::NotReady {
+                    collected: session.shares.len(),
+                    threshold: session.threshold,
+                });
+            }
+
+            let opened: Vec<Zeroizing<Vec<u8>>> =
+                session.shares.iter().map(|s| session.open(id, s)).collect();
+            let refs: Vec<ShareRef<'_>> = session
+                .shares
+                .iter()
+                .zip(&opened)
+                .map(|(s, y)| ShareRef { x: s.x, y })
+                .collect();
+            if !consistent(&refs, session.threshold)? {
+                return Err(match blame(&refs, session.threshold)? {
+                    Some(i) => {
+                        let custodian = session.shares[i].custodian;
+                        PortalError::Tampered(session.custodians[custodian].name.clone())
+                    }
+                    None => PortalError::Inconsistent,
+                });
+            }
+
+            let mut secret = Zeroizing::new(vec![0u8; opened[0].len()]);
+            reconstruct_into(&refs, session.threshold, &mut secret)
+                .map_err(|e| PortalError::BadRequest(e.to_string()))?;
+            Ok(secret)
+        })?;
+        self.inner.lock().unwrap().remove(id);
+        Ok(secret)
+    }
+
+    /// Close a session without reconstructing. Owner only.
+    pub fn cancel(&self, id: &str, token: &str) -> Result<(), PortalError> {
+        self.with(id, |session| match session.role(token) {
+            Some(Role::Owner) => Ok(()),
+            _ => Err(PortalError::Unauthorized),
+        })?;
+        self.inner.lock().unwrap().remove(id);
+        Ok(())
+    }
+
+    /// Drop every expired session.
+    pub fn sweep(&self) {
+        let now = Instant::now();
+        self.inner
+            .lock()
+            .unwrap()
+            .retain(|_, session| session.expires_at > now);
+    }
+}
+
+fn consistent(shares: &[ShareRef<'_>], threshold: usize) -> Result<bool, PortalError> {
+    inconsistent_shares(shares, threshold)
+        .map(|bad| bad.is_empty())
+        .map_err(|e| PortalError::BadRequest(e.to_string()))
+}
+
+/// Index of the one share whose removal makes the rest agree, as in
+/// `sss verify`. `inconsistent_shares` measures later shares against the
+/// first `threshold`, so its outliers are honest whenever the bad share is
+/// among those first ones; leaving each share out in turn avoids that.
+fn blame(shares: &[ShareRef<'_>], threshold: usize) -> Result<Option<usize>, PortalError> {
+    // Dropping one of t + 1 shares always leaves a consistent set.
+    if shares.len() < threshold + 2 {
+        return Ok(None);
+    }
+    let mut suspects = Vec::new();
+    for i in 0..shares.len() {
+        let mut rest = shares.to_vec();
+        rest.remove(i);
+        if consistent(&rest, threshold)? {
+            suspects.push(i);
+        }
+    }
+    Ok(match suspects[..] {
+        [i] => Some(i),
+        _ => None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use shamir_gf256::{share_to_hex, split};
+
+    #[test]
+    fn shares_are_sealed_at_rest_and_the_session_is_wiped_on_release() {
+        let sessions = Sessions::default();
+        let names = ["alice".to_string(), "bob".into(), "carol".into()];
+        let created = sessions.create(2, &names, Duration::from_secs(60)).unwrap();
+        let shares = split(b"launch codes", 2, 3);
+
+        for (share, custodian) in shares.iter().zip(&created.custodians).take(2) {
+            sessions
+                .submit(&created.id, &custodian.token, &share_to_hex(share, 16))
+                .unwrap();
+        }
+        {
+            let inner = sessions.inner.lock().unwrap();
+            let session = &inner[&created.id];
+            for (sealed, share) in session.shares.iter().zip(&shares) {
+                assert_ne!(&sealed.y[..share.y.len()], &share.y[..]);
+            }
+        }
+
+        assert!(matches!(
+            sessions.release(&created.id, &created.custodians[0].token),
+            Err(PortalError::Unauthorized)
+        ));
+        let secret = sessions.release(&created.id, &created.owner_token).unwrap();
+        assert_eq!(&secret[..], b"launch codes");
+        assert!(sessions.inner.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn an_unattributable_mismatch_names_nobody() {
+        let sessions = Sessions::default();
+        let names = ["alice".to_string(), "bob".into(), "carol".into()];
+        let created = sessions.create(2, &names, Duration::from_secs(60)).unwrap();
+        let mut shares = split(b"launch codes", 2, 3);
+        shares[0].y[0] ^= 1;
+        for (share, custodian) in shares.iter().zip(&created.custodians) {
+            sessions
+                .submit(&created.id, &custodian.token, &share_to_hex(share, 16))
+                .unwrap();
+        }
+
+        // With t + 1 shares, leaving any one out leaves a consistent pair.
+        assert!(matches!(
+            sessions.release(&created.id, &created.owner_token),
+            Err(PortalError::Inconsistent)
+        ));
+    }
+}
@@ -0,0 +1,235 @@
+// portal.rs
+//
+// End-to-end tests against an in-process HTTPS server on a loopback port,
+// with a throwaway self-signed certificate.
+
+use axum_server::Handle;
+use axum_server::tls_rustls::RustlsConfig;
+use reqwest::{Certificate, Client, StatusCode};
+use serde_json::{Value, json};
+use shamir_gf256::{share_to_hex, split};
+use sss_portal::{Portal, router};
+use std::sync::Arc;
+use std::time::Duration;
+
+const OPERATOR: &str = "operator-token";
+
+struct Server {
+    client: Client,
+    base: String,
+    _handle: Handle,
+}
+
+impl Server {
+    async fn start() -> Server {
+        let _ = rustls::crypto::ring::default_provider().install_default();
+        let cert = rcgen::generate_simple_self_signed(vec!["localhost".into()]).unwrap();
+        let tls = RustlsConfig::from_pem(
+            cert.cert.pem().into_bytes(),
+            cert.signing_key.serialize_pem().into_bytes(),
+        )
+        .await
+        .unwrap();
+
+        let handle = Handle::new();
+        let app = router(Arc::new(Portal::new(OPERATOR)));
+        let server = axum_server::bind_rustls("127.0.0.1:0".parse().unwrap(), tls)
+            .handle(handle.clone())
+            .serve(app.into_make_service());
+        tokio::spawn(server);
+        let addr = handle.listening().await.unwrap();
+
+        let client = Client::builder()
+            .tls_certs_only([Certificate::from_pem(cert.cert.pem().as_bytes()).unwrap()])
+            .resolve("localhost", addr)
+            .https_only(true)
+            .build()
+            .unwrap();
+        Server {
+            client,
+            base: format!("https://localhost:{}", addr.port()),
+            _handle: handle,
+        }
+    }
+
+    async fn open(&self, threshold: usize, custodians: &[&str], ttl_secs: u64) -> Value {
+        let response = self
+            .client
+            .post(format!("{}/sessions", self.base))
+            .bearer_auth(OPERATOR)
+            .json(
+                &json!({ "threshold": threshold, "custodians": custodians, "ttl_secs": ttl_secs }),
+            )
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::CREATED);
+        response.json().await.unwrap()
+    }
+
+    async fn submit(
+        &self,
+        session: &Value,
+        custodian: usize,
+        share: String,
+    ) -> (StatusCode, String) {
+        let response = self
+            .client
+            .post(format!(
+                "{}/sessions/{}/share",
+                self.base,
+                session["id"].as_str().unwrap()
+            ))
+            .bearer_auth(session["custodians"][custodian]["token"].as_str().unwrap())
+            .body(share)
+            .send()
+            .await
+            .unwrap();
+        (response.status(), response.text().await.unwrap())
+    }
+
+    async fn release(&self, session: &Value, token: &str) -> (StatusCode, Vec<u8>) {
+        let response = self
+            .client
+            .post(format!(
+                "{}/sessions/{}/release",
+                self.base,
+                session["id"].as_str().unwrap()
+            ))
+            .bearer_auth(token)
+            .send()
+            .await
+            .unwrap();
+        (response.status(), response.bytes().await.unwrap().to_vec())
+    }
+
+    async fn status(&self, session: &Value, token: &str) -> (StatusCode, Value) {
+        let response = self
+            .client
+            .get(format!(
+                "{}/sessions/{}",
+                self.base,
+                session["id"].as_str().unwrap()
+            ))
+            .bearer_auth(token)
+            .send()
+            .await
+            .unwrap();
+        let status = response.status();
+        (status, response.json().await.unwrap_or(Value::Null))
+    }
+}
+
+fn owner(session: &Value) -> &str {
+    session["owner_token"].as_str().unwrap()
+}
+
+#[tokio::test]
+async fn custodians_submit_and_the_owner_releases_once() {
+    let server = Server::start().await;
+    let shares = split(b"root CA key", 2, 3);
+
+    let unauthorized = server
+        .client
+        .post(format!("{}/sessions", server.base))
+        .bearer_auth("guess")
+        .json(&json!({ "threshold": 2, "custodians": ["alice", "bob"] }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(unauthorized.status(), StatusCode::UNAUTHORIZED);
+
+    let session = server.open(2, &["alice", "bob", "carol"], 3600).await;
+    let (code, _) = server
+        .submit(&session, 0, share_to_hex(&shares[0], 16))
+        .await;
+    assert_eq!(code, StatusCode::OK);
+    let (code, body) = server
+        .submit(&session, 0, share_to_hex(&shares[0], 16))
+        .await;
+    assert_eq!(code, StatusCode::CONFLICT);
+    assert!(body.contains("alice has already submitted"), "{body}");
+    let (code, body) = server
+        .submit(&session, 1, share_to_hex(&shares[0], 16))
+        .await;
+    assert_eq!(code, StatusCode::CONFLICT);
+    assert!(body.contains("already submitted by alice"), "{body}");
+    let (code, _) = server.submit(&session, 1, "not a share".into()).await;
+    assert_eq!(code, StatusCode::BAD_REQUEST);
+
+    let (code, body) = server.release(&session, owner(&session)).await;
+    assert_eq!(code, StatusCode::CONFLICT);
+    assert_eq!(String::from_utf8_lossy(&body), "1 of 2 shares collected\n");
+
+    let (code, _) = server
+        .submit(&session, 2, share_to_hex(&shares[2], 16))
+        .await;
+    assert_eq!(code, StatusCode::OK);
+    let (code, status) = server.status(&session, owner(&session)).await;
+    assert_eq!(code, StatusCode::OK);
+    assert_eq!(status["collected"], 2);
+    assert_eq!(status["pending"], json!(["bob"]));
+
+    let custodian = session["custodians"][0]["token"].as_str().unwrap();
+    let (code, _) = server.release(&session, custodian).await;
+    assert_eq!(code, StatusCode::UNAUTHORIZED);
+    let (code, secret) = server.release(&session, owner(&session)).await;
+    assert_eq!(code, StatusCode::OK);
+    assert_eq!(secret, b"root CA key");
+
+    // Released sessions are wiped.
+    let (code, _) = server.release(&session, owner(&session)).await;
+    assert_eq!(code, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn tampered_shares_block_release_and_owner_can_cancel() {
+    let server = Server::start().await;
+    let mut shares = split(b"backup passphrase", 2, 4);
+    // Bob's share is among the first `threshold`, which later shares are
+    // checked against; the honest custodians after him must not be blamed.
+    shares[1].y[0] ^= 0x40;
+
+    let session = server
+        .open(2, &["alice", "bob", "carol", "dave"], 3600)
+        .await;
+    for (i, share) in shares.iter().enumerate() {
+        let (code, _) = server.submit(&session, i, share_to_hex(share, 16)).await;
+        assert_eq!(code, StatusCode::OK);
+    }
+    let (code, body) = server.release(&session, owner(&session)).await;
+    assert_eq!(code, StatusCode::CONFLICT);
+    let body = String::from_utf8_lossy(&body);
+    assert_eq!(
+        body,
+        "the share from bob does not fit the others; nothing was released\n"
+    );
+
+    let response = server
+        .client
+        .delete(format!(
+            "{}/sessions/{}",
+            server.base,
+            session["id"].as_str().unwrap()
+        ))
+        .bearer_auth(owner(&session))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::NO_CONTENT);
+    let (code, _) = server.status(&session, owner(&session)).await;
+    assert_eq!(code, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn expired_sessions_are_wiped() {
+    let server = Server::start().await;
+    let shares = split(b"short-lived", 1, 1);
+    let session = server.open(1, &["alice"], 1).await;
+    tokio::time::sleep(Duration::from_millis(1100)).await;
+
+    let (code, _) = server
+        .submit(&session, 0, share_to_hex(&shares[0], 16))
+        .await;
+    assert_eq!(code, StatusCode::NOT_FOUND);
+}
@@ -0,0 +1,328 @@
+// ceremony.rs
+//
+// `sss ceremony combine`: custodians take turns at one terminal to unseal a
+// secret. Shares are typed without echo, checked on entry, and the screen is
+// cleared before the next custodian sits down. The transcript records who
+// contributed which share number, never share material.
+
+use crate::error::CliError;
+use clap::{Args, Subcommand};
+use shamir_gf256::{
+    ParseAnyError, ParsedShare, Share, ShareCodecError, ShareRef, parse_any_share, reconstruct_into,
+};
+use std::fs::{File, OpenOptions};
+use std::io::{self, BufRead, IsTerminal, Write};
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+use std::time::{SystemTime, UNIX_EPOCH};
+use zeroize::Zeroizing;
+
+#[derive(Args)]
+pub struct CeremonyArgs {
+    #[command(subcommand)]
+    pub command: CeremonyCommand,
+}
+
+#[derive(Subcommand)]
+pub enum CeremonyCommand {
+    /// Collect shares from custodians in turn and reconstruct the secret
+    Combine(CeremonyCombineArgs),
+}
+
+#[derive(Args)]
+#[command(group = clap::ArgGroup::new("sink").required(true).args(["output", "pipe"]))]
+pub struct CeremonyCombineArgs {
+    /// Shares needed to reconstruct
+    #[arg(short, long)]
+    pub threshold: u8,
+    /// Write the secret to this file (created with mode 0600; must not exist)
+    #[arg(short, long)]
+    pub output: Option<PathBuf>,
+    /// Run this shell command with the secret on its stdin
+    #[arg(long)]
+    pub pipe: Option<String>,
+    /// Append the transcript to this file instead of printing it
+    #[arg(long)]
+    pub transcript: Option<PathBuf>,
+    /// Also accept formats without RS protection (ssss, Vault, JSON)
+    #[arg(long)]
+    pub allow_unframed: bool,
+}
+
+/// Prompts go to stderr; on a terminal shares are read without echo and the
+/// screen is cleared between custodians. Piped input is read line by line,
+/// which is how rehearsals and tests drive a ceremony.
+struct Console {
+    tty: bool,
+    stdin: io::StdinLock<'static>,
+    err: io::Stderr,
+}
+
+impl Console {
+    fn new() -> Self {
+        Console {
+            tty: io::stdin().is_terminal() && io::stderr().is_terminal(),
+            stdin: io::stdin().lock(),
+            err: io::stderr(),
+        }
+    }
+
+    fn say(&mut self, msg: &str) -> io::Result<()> {
+        writeln!(self.err, "{msg}")
+    }
+
+    /// An echoed line, or `None` at end of input.
+    fn line(&mut self, prompt: &str) -> io::Result<Option<String>> {
+        write!(self.err, "{prompt}")?;
+        self.err.flush()?;
+        let mut line = String::new();
+        if self.stdin.read_line(&mut line)? == 0 {
+            return Ok(None);
+        }
+        Ok(Some(line.trim().to_string()))
+    }
+
+    /// A line read without echo, or `None` at end of input.
+    fn secret(&mut self, prompt: &str) -> io::Result<Option<Zeroizing<String>>> {
+        if self.tty {
+            return rpassword::prompt_password(prompt).map(|s| Some(Zeroizing::new(s)));
+        }
+        write!(self.err, "{prompt}")?;
+        self.err.flush()?;
+        let mut line = Zeroizing::new(String::new());
+        if self.stdin.read_line(&mut line)? == 0 {
+            return Ok(None);
+        }
+        Ok(Some(line))
+    }
+
+    fn clear(&mut self) -> io::Result<()> {
+        if self.tty {
+            write!(self.err, "\x1b[2J\x1b[3J\x1b[H")?;
+            self.err.flush()?;
+        }
+        Ok(())
+    }
+}
+
+/// Seconds since the epoch as `YYYY-MM-DDTHH:MM:SSZ`.
+fn utc_now() -> String {
+    let secs = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs());
+    let (days, rem) = (secs / 86_400, secs % 86_400);
+    // Civil-from-days (H. Hinnant), valid for any date after 1970.
+    let z = days + 719_468;
+    let era = z / 146_097;
+    let doe = z % 146_097;
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + u64::from(month <= 2);
+    format!(
+        "{year:04}-{month:02}-{day:02}T{:02}:{:02}:{:02}Z",
+        rem / 3600,
+        rem / 60 % 60,
+        rem % 60
+    )
+}
+
+/// Why a typed share is turned away; the custodian may try again.
+fn check_share(
+    parsed: &ParsedShare,
+    accepted: &[(String, Share)],
+    allow_unframed: bool,
+) -> Result<(), String> {
+    if parsed.report.is_none() && !allow_unframed {
+        return Err(format!(
+            "{} shares carry no error correction; pass --allow-unframed to accept them",
+            parsed.format
+        ));
+    }
+    let share = &parsed.share;
+    if let Some((name, _)) = accepted.iter().find(|(_, s)| s.x == share.x) {
+        return Err(format!(
+            "share {} was already contributed by {name}",
+            share.x.0
+        ));
+    }
+    if let Some((_, first)) = accepted.first()
+        && first.y.len() != share.y.len()
+    {
+        return Err("share length differs from the earlier shares".into());
+    }
+    Ok(())
+}
+
+/// What the custodian is shown, and what the transcript records, for a share
+/// that does not parse. Parse errors may quote a character of the input, so
+/// the transcript never carries their text.
+fn unreadable(name: &str, e: &ParseAnyError) -> (String, String) {
+    match &e.attempts[..] {
+        [(format, ShareCodecError::EccDecode)] => (
+            format!("{name}'s {format} share is damaged beyond repair"),
+            "share damaged beyond repair".into(),
+        ),
+        _ => (e.to_string(), "share unreadable".into()),
+    }
+}
+
+fn collect(
+    console: &mut Console,
+    threshold: usize,
+    allow_unframed: bool,
+    transcript: &mut Vec<String>,
+) -> Result<Vec<(String, Share)>, CliError> {
+    let mut accepted: Vec<(String, Share)> = Vec::with_capacity(threshold);
+    while accepted.len() < threshold {
+        console.clear()?;
+        console.say(&format!(
+            "Key ceremony: {} of {threshold} collected\n",
+            accepted.len()
+        ))?;
+        let Some(name) = console.line("Custodian name: ")? else {
+            return Err(CliError::CeremonyAborted);
+        };
+        if name.is_empty() {
+            continue;
+        }
+        let share = loop {
+            let Some(text) = console.secret("Share (input hidden): ")? else {
+                return Err(CliError::CeremonyAborted);
+            };
+            if text.trim().is_empty() {
+                continue;
+            }
+            let (shown, logged) = match parse_any_share(&text) {
+                Ok(parsed) => match check_share(&parsed, &accepted, allow_unframed) {
+                    Ok(()) => break parsed,
+                    Err(reason) => (reason.clone(), reason),
+                },
+                Err(e) => unreadable(&name, &e),
+            };
+            console.say(&format!("✗ {shown}; try again"))?;
+            transcript.push(format!("{} rejected: {name}: {logged}", utc_now()));
+        };
+
+        let corrected = share.report.as_ref().map_or(0, |r| r.corrected());
+        console.say(&format!(
+            "✓ share {} accepted ({}, {corrected} byte(s) repaired)",
+            share.share.x.0, share.format
+        ))?;
+        transcript.push(format!(
+            "{} accepted: {name}: share {} ({}, {corrected} byte(s) repaired)",
+            utc_now(),
+            share.share.x.0,
+            share.format
+        ));
+        accepted.push((name, share.share.clone()));
+
+        if accepted.len() < threshold && console.tty {
+            console.line("Press Enter and hand over to the next custodian.")?;
+        }
+    }
+    console.clear()?;
+    console.say(&format!("{threshold} of {threshold} collected"))?;
+    Ok(accepted)
+}
+
+fn deliver(secret: &[u8], args: &CeremonyCombineArgs) -> Result<String, CliError> {
+    if let Some(path) = &args.output {
+        let mut options = OpenOptions::new();
+        options.write(true).create_new(true);
+        #[cfg(unix)]
+        std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+        let file_error = |source| CliError::File {
+            path: path.clone(),
+            source,
+        };
+        let mut file = options.open(path).map_err(file_error)?;
+        file.write_all(secret).map_err(file_error)?;
+        file.sync_all().map_err(file_error)?;
+        return Ok(format!("written to {}", path.display()));
+    }
+    let command = args
+        .pipe
+        .as_deref()
+        .expect("clap requires --output or --pipe");
+    let mut child = Command::new("sh")
+        .args(["-c", command])
+        .stdin(Stdio::piped())
+        .spawn()?;
+    let written = child
+        .stdin
+        .take()
+        .expect("stdin is piped")
+        .write_all(secret);
+    let status = child.wait()?;
+    // A command that exits without reading everything closes the pipe early;
+    // its exit status is the more useful error.
+    if !status.success() {
+        return Err(CliError::Pipe {
+            command: command.to_string(),
+            status,
+        });
+    }
+    written?;
+    Ok(format!("piped to `{command}`"))
+}
+
+fn write_transcript(lines: &[String], path: Option<&PathBuf>) -> Result<(), CliError> {
+    let text: String = lines.iter().map(|l| format!("{l}\n")).collect();
+    match path {
+        Some(path) => OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .and_then(|mut f: File| f.write_all(text.as_bytes()))
+            .map_err(|source| CliError::File {
+                path: path.clone(),
+                source,
+            }),
+        None => {
+            let mut out = io::stdout().lock();
+            out.write_all(text.as_bytes())?;
+            out.flush()?;
+            Ok(())
+        }
+    }
+}
+
+fn combine(args: CeremonyCombineArgs) -> Result<(), CliError> {
+    let threshold = args.threshold;
+    if threshold == 0 {
+        return Err(CliError::Usage("threshold must be at least 1".into()));
+    }
+    let mut console = Console::new();
+    let mut transcript = vec![format!(
+        "{} ceremony started: threshold {threshold}",
+        utc_now()
+    )];
+
+    let outcome = collect(
+        &mut console,
+        threshold as usize,
+        args.allow_unframed,
+        &mut transcript,
+    )
+    .and_then(|shares| {
+        let refs: Vec<ShareRef<'_>> = shares.iter().map(|(_, s)| s.as_share_ref()).collect();
+        let mut secret = Zeroizing::new(vec![0u8; refs[0].y.len()]);
+        reconstruct_into(&refs, refs.len(), &mut secret)?;
+        deliver(&secret, &args)
+    });
+    transcript.push(match &outcome {
+        Ok(destination) => format!("{} secret reconstructed and {destination}", utc_now()),
+        Err(e) => format!("{} ceremony failed: {e}", utc_now()),
+    });
+    write_transcript(&transcript, args.transcript.as_ref())?;
+    outcome.map(|_| ())
+}
+
+pub fn run(args: CeremonyArgs) -> Result<(), CliError> {
+    match args.command {
+        CeremonyCommand::Combine(args) => combine(args),
+    }
+}
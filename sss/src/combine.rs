@@ -0,0 +1,118 @@
+use crate::error::CliError;
+use crate::input::read_share_texts;
+use clap::Args;
+use shamir_gf256::{ParsedShare, ShareRef, parse_share_list, reconstruct_into};
+use std::fs;
+use std::io::{self, Write};
+use std::path::PathBuf;
+use zeroize::Zeroizing;
+
+#[derive(Args)]
+pub struct CombineArgs {
+    /// Files holding shares (any format, several per file allowed); stdin if omitted
+    pub files: Vec<PathBuf>,
+    /// Shares needed; defaults to the armor `Threshold` header, else all given shares
+    #[arg(short, long)]
+    pub threshold: Option<u8>,
+    /// Write the secret to this file instead of stdout
+    #[arg(short, long)]
+    pub output: Option<PathBuf>,
+}
+
+/// A parsed share and where it came from (`file:line`).
+pub struct Located {
+    pub location: String,
+    pub parsed: ParsedShare,
+}
+
+/// Parse every share in `texts`, failing on the first unreadable one.
+pub fn collect_shares(texts: &[(String, Zeroizing<String>)]) -> Result<Vec<Located>, CliError> {
+    let mut shares = Vec::new();
+    for (label, text) in texts {
+        for (line, outcome) in parse_share_list(text) {
+            let location = format!("{label}:{line}");
+            match outcome {
+                Ok(parsed) => shares.push(Located { location, parsed }),
+                Err(source) => return Err(CliError::Parse { location, source }),
+            }
+        }
+    }
+    Ok(shares)
+}
+
+/// Reject duplicate x-coordinates and shares whose armor `Set-Id` differs
+/// from the first one that has one.
+pub fn check_consistency(shares: &[Located]) -> Result<(), CliError> {
+    for (i, a) in shares.iter().enumerate() {
+        if let Some(b) = shares[..i]
+            .iter()
+            .find(|b| b.parsed.share.x == a.parsed.share.x)
+        {
+            return Err(CliError::DuplicateShare {
+                x: a.parsed.share.x.0,
+                first: b.location.clone(),
+                second: a.location.clone(),
+            });
+        }
+    }
+    let set_id = |s: &Located| s.parsed.headers.as_ref().and_then(|h| h.set_id.clone());
+    if let Some(first) = shares.iter().find_map(set_id)
+        && let Some(foreign) = shares
+            .iter()
+            .find(|s| set_id(s).is_some_and(|id| id != first))
+    {
+        return Err(CliError::ForeignShare(foreign.location.clone()));
+    }
+    Ok(())
+}
+
+/// Threshold from the command line, else from armor headers, else all shares.
+pub fn threshold_for(shares: &[Located], requested: Option<u8>) -> usize {
+    requested
+        .or_else(|| {
+            shares
+                .iter()
+                .find_map(|s| s.parsed.headers.as_ref()?.meta.map(|m| m.threshold))
+        })
+        .map_or(shares.len(), usize::from)
+}
+
+/// Interpolate the first `threshold` shares.
+pub fn combine(shares: &[Located], threshold: usize) -> Result<Zeroizing<Vec<u8>>, CliError> {
+    if shares.is_empty() {
+        return Err(CliError::NoShares);
+    }
+    if shares.len() < threshold {
+        return Err(CliError::NotEnoughShares {
+            needed: threshold,
+            got: shares.len(),
+        });
+    }
+    let refs: Vec<ShareRef<'_>> = shares
+        .iter()
+        .map(|s| s.parsed.share.as_share_ref())
+        .collect();
+    let mut secret = Zeroizing::new(vec![0u8; refs[0].y.len()]);
+    reconstruct_into(&refs, threshold, &mut secret)?;
+    Ok(secret)
+}
+
+pub fn run(args: CombineArgs) -> Result<(), CliError> {
+    let texts = read_share_texts(&args.files)?;
+    let shares = collect_shares(&texts)?;
+    check_consistency(&shares)?;
+    let secret = combine(&shares, threshold_for(&shares, args.threshold))?;
+
+    match &args.output {
+        Some(path) => fs::write(path, &secret[..]).map_err(|source| CliError::File {
+            path: path.clone(),
+            source,
+        })?,
+        None => {
+            let mut out = io::stdout().lock();
+            out.write_all(&secret)?;
+            out.flush()?;
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,58 @@
+use shamir_gf256::{ParseAnyError, ShareCodecError, SssError};
+use std::io;
+use std::path::PathBuf;
+use std::process::ExitStatus;
+use thiserror::Error;
+
+/// Everything that makes `sss` exit non-zero. Messages name the input
+/// (file and line) so an operator can tell which share is at fault.
+#[derive(Debug, Error)]
+pub enum CliError {
+    #[error("{}: {source}", path.display())]
+    File { path: PathBuf, source: io::Error },
+    #[error("{0}")]
+    Io(#[from] io::Error),
+    #[error("{0}")]
+    Usage(String),
+    #[error("the secret is empty")]
+    EmptySecret,
+    #[error("{location}: {source}")]
+    Parse {
+        location: String,
+        source: ParseAnyError,
+    },
+    #[error("{0}")]
+    Codec(#[from] ShareCodecError),
+    #[error("no shares found in the input")]
+    NoShares,
+    #[error("need {needed} shares to reconstruct, got {got}")]
+    NotEnoughShares { needed: usize, got: usize },
+    #[error("{first} and {second} both hold share {x}")]
+    DuplicateShare {
+        x: u8,
+        first: String,
+        second: String,
+    },
+    #[error("{0} is from a different share set than the first share")]
+    ForeignShare(String),
+    #[error("{}: {reason}", path.display())]
+    BadShareFile { path: PathBuf, reason: String },
+    #[error("{} is from a different share set than {} ({field} differs)", path.display(), first.display())]
+    SetMismatch {
+        path: PathBuf,
+        field: &'static str,
+        first: PathBuf,
+    },
+    #[error("restored file does not match its checksum; a share is corrupt or forged")]
+    Checksum,
+    #[error("{0} share(s) could not be read")]
+    Unreadable(usize),
+    #[error("inconsistent share set: {0}")]
+    Inconsistent(String),
+    #[error("ceremony aborted before enough shares were collected")]
+    CeremonyAborted,
+    #[error("`{command}` failed: {status}")]
+    Pipe { command: String, status: ExitStatus },
+    #[error("reconstruction failed: {0}")]
+    Reconstruct(#[from] SssError),
+}
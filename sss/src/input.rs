@@ -0,0 +1,56 @@
+// input.rs
+//
+// Reading secrets and share text from files, stdin or the terminal. Every
+// buffer that may hold secret material is `Zeroizing`.
+
+use crate::error::CliError;
+use std::fs;
+use std::io::{self, IsTerminal, Read};
+use std::path::{Path, PathBuf};
+use zeroize::Zeroizing;
+
+/// Read the secret from `path`, or from stdin, or — when stdin is a
+/// terminal or `prompt` is set — from a no-echo prompt.
+pub fn read_secret(path: Option<&Path>, prompt: bool) -> Result<Zeroizing<Vec<u8>>, CliError> {
+    let secret = match path {
+        Some(p) if p != Path::new("-") => {
+            Zeroizing::new(fs::read(p).map_err(|source| CliError::File {
+                path: p.to_path_buf(),
+                source,
+            })?)
+        }
+        _ if prompt || io::stdin().is_terminal() => {
+            let typed = Zeroizing::new(rpassword::prompt_password("Secret (input hidden): ")?);
+            Zeroizing::new(typed.as_bytes().to_vec())
+        }
+        _ => {
+            let mut buf = Zeroizing::new(Vec::new());
+            io::stdin().read_to_end(&mut buf)?;
+            buf
+        }
+    };
+    if secret.is_empty() {
+        return Err(CliError::EmptySecret);
+    }
+    Ok(secret)
+}
+
+/// Share text from each file, or from stdin when `paths` is empty or `-`.
+/// Returns `(label, text)` pairs; the label is used in error messages.
+pub fn read_share_texts(paths: &[PathBuf]) -> Result<Vec<(String, Zeroizing<String>)>, CliError> {
+    if paths.is_empty() || paths.iter().all(|p| p == Path::new("-")) {
+        let mut text = Zeroizing::new(String::new());
+        io::stdin().read_to_string(&mut text)?;
+        return Ok(vec![("<stdin>".to_string(), text)]);
+    }
+    paths
+        .iter()
+        .map(|p| {
+            let text = fs::read_to_string(p).map_err(|source| CliError::File {
+                path: p.clone(),
+                source,
+            })?;
+            Ok((p.display().to_string(), Zeroizing::new(text)))
+        })
+        .collect()
+}
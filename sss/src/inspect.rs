@@ -0,0 +1,116 @@
+use crate::error::CliError;
+use clap::Args;
+use shamir_gf256::{ParseAnyError, ParsedShare, ShareCodecError, parse_share_list};
+use std::fs;
+use std::io::{self, Read, Write};
+use std::path::Path;
+use zeroize::Zeroizing;
+
+#[derive(Args)]
+pub struct InspectArgs {
+    /// Share text, or files holding shares; stdin if omitted
+    pub shares: Vec<String>,
+}
+
+/// Each argument is read as a file if one exists by that name, otherwise
+/// taken as share text.
+fn texts(args: &[String]) -> Result<Vec<(String, Zeroizing<String>)>, CliError> {
+    if args.is_empty() || args == ["-"] {
+        let mut text = Zeroizing::new(String::new());
+        io::stdin().read_to_string(&mut text)?;
+        return Ok(vec![("<stdin>".to_string(), text)]);
+    }
+    args.iter()
+        .enumerate()
+        .map(|(i, arg)| {
+            let path = Path::new(arg);
+            if path.is_file() {
+                let text = fs::read_to_string(path).map_err(|source| CliError::File {
+                    path: path.to_path_buf(),
+                    source,
+                })?;
+                Ok((arg.clone(), Zeroizing::new(text)))
+            } else {
+                Ok((format!("argument {}", i + 1), Zeroizing::new(arg.clone())))
+            }
+        })
+        .collect()
+}
+
+fn describe(out: &mut impl Write, parsed: &ParsedShare) -> io::Result<()> {
+    writeln!(out, "  format:       {}", parsed.format)?;
+    writeln!(out, "  x:            {}", parsed.share.x.0)?;
+    writeln!(out, "  y length:     {} bytes", parsed.share.y.len())?;
+    match &parsed.report {
+        Some(report) => {
+            writeln!(
+                out,
+                "  ecc length:   {} parity bytes × {} block(s)",
+                report.ecc_len, report.blocks
+            )?;
+            writeln!(
+                out,
+                "  corrections:  {} byte(s) repaired, {} of them `?` erasures; {} more error(s) correctable",
+                report.corrected(),
+                report.erasures,
+                report.remaining
+            )?;
+        }
+        None => writeln!(
+            out,
+            "  ecc length:   none (this format has no error correction)"
+        )?,
+    }
+    let headers = parsed.headers.as_ref();
+    let set_id = headers.and_then(|h| h.set_id.as_deref());
+    let meta = headers.and_then(|h| h.meta);
+    match (set_id, meta) {
+        (None, None) => writeln!(out, "  set:          not recorded in this share")?,
+        (id, meta) => {
+            let id = id.map_or(String::new(), |id| format!("Set-Id {id}"));
+            let meta = meta.map_or(String::new(), |m| {
+                format!("threshold {} of {}", m.threshold, m.share_count)
+            });
+            let sep = if id.is_empty() || meta.is_empty() {
+                ""
+            } else {
+                ", "
+            };
+            writeln!(out, "  set:          {id}{sep}{meta}")?;
+        }
+    }
+    Ok(())
+}
+
+fn describe_error(out: &mut impl Write, e: &ParseAnyError) -> io::Result<()> {
+    match &e.attempts[..] {
+        // Recognized as a frame, but damaged past its parity.
+        [(format, ShareCodecError::EccDecode)] => {
+            writeln!(out, "  format:       {format}")?;
+            writeln!(out, "  corrections:  uncorrectable (EccDecode)")
+        }
+        _ => writeln!(out, "  error:        {e}"),
+    }
+}
+
+pub fn run(args: InspectArgs) -> Result<(), CliError> {
+    let mut out = io::stdout().lock();
+    let mut unreadable = 0;
+    for (label, text) in texts(&args.shares)? {
+        for (line, outcome) in parse_share_list(&text) {
+            writeln!(out, "{label}:{line}")?;
+            match outcome {
+                Ok(parsed) => describe(&mut out, &parsed)?,
+                Err(e) => {
+                    describe_error(&mut out, &e)?;
+                    unreadable += 1;
+                }
+            }
+        }
+    }
+    out.flush()?;
+    if unreadable > 0 {
+        return Err(CliError::Unreadable(unreadable));
+    }
+    Ok(())
+}
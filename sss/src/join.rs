@@ -0,0 +1,224 @@
+use crate::error::CliError;
+use crate::share_file::{DIGEST_LEN, Manifest, read_frame};
+use clap::Args;
+use sha2::{Digest, Sha256};
+use shamir_gf256::{ShareRef, reconstruct_into, share_from_bytes};
+use std::fs::{self, File, OpenOptions};
+use std::io::{BufReader, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use zeroize::Zeroizing;
+
+#[derive(Args)]
+pub struct JoinArgs {
+    /// Share files, directories holding them, or quoted glob patterns
+    #[arg(required = true)]
+    pub inputs: Vec<String>,
+    /// Where to write the restored file; defaults to its original name
+    #[arg(short, long)]
+    pub output: Option<PathBuf>,
+    /// Replace the output file if it exists
+    #[arg(long)]
+    pub force: bool,
+}
+
+struct Input {
+    path: PathBuf,
+    manifest: Manifest,
+    reader: BufReader<File>,
+}
+
+/// Expand directories to the share files inside them and glob patterns to
+/// their matches; plain paths are kept as given.
+fn expand(inputs: &[String]) -> Result<Vec<PathBuf>, CliError> {
+    let mut paths = Vec::new();
+    for input in inputs {
+        let path = Path::new(input);
+        if path.is_dir() {
+            let entries = fs::read_dir(path).map_err(|source| CliError::File {
+                path: path.to_path_buf(),
+                source,
+            })?;
+            for entry in entries {
+                let entry = entry?;
+                let is_share = entry.file_name().to_string_lossy().contains(".share-");
+                if is_share && entry.file_type()?.is_file() {
+                    paths.push(entry.path());
+                }
+            }
+        } else if !path.exists() && input.contains(['*', '?', '[']) {
+            let matches =
+                glob::glob(input).map_err(|e| CliError::Usage(format!("{input}: {e}")))?;
+            for entry in matches {
+                paths.push(entry.map_err(|e| CliError::File {
+                    path: e.path().to_path_buf(),
+                    source: e.into(),
+                })?);
+            }
+        } else {
+            paths.push(path.to_path_buf());
+        }
+    }
+    paths.sort();
+    paths.dedup();
+    Ok(paths)
+}
+
+fn open(path: PathBuf) -> Result<Input, CliError> {
+    let file = File::open(&path).map_err(|source| CliError::File {
+        path: path.clone(),
+        source,
+    })?;
+    let mut reader = BufReader::new(file);
+    let manifest = Manifest::read_from(&mut reader, &path)?;
+    Ok(Input {
+        path,
+        manifest,
+        reader,
+    })
+}
+
+/// Open every share file and check that they form one set with distinct
+/// shares and enough of them to reach the threshold.
+fn open_set(paths: Vec<PathBuf>) -> Result<Vec<Input>, CliError> {
+    let inputs = paths.into_iter().map(open).collect::<Result<Vec<_>, _>>()?;
+    let Some(first) = inputs.first() else {
+        return Err(CliError::NoShares);
+    };
+    for (i, input) in inputs.iter().enumerate() {
+        if let Some(field) = first.manifest.mismatch(&input.manifest) {
+            return Err(CliError::SetMismatch {
+                path: input.path.clone(),
+                field,
+                first: first.path.clone(),
+            });
+        }
+        if let Some(dup) = inputs[..i]
+            .iter()
+            .find(|d| d.manifest.share == input.manifest.share)
+        {
+            return Err(CliError::DuplicateShare {
+                x: input.manifest.share,
+                first: dup.path.display().to_string(),
+                second: input.path.display().to_string(),
+            });
+        }
+    }
+    let needed = first.manifest.threshold as usize;
+    if inputs.len() < needed {
+        return Err(CliError::NotEnoughShares {
+            needed,
+            got: inputs.len(),
+        });
+    }
+    Ok(inputs)
+}
+
+/// Reconstruct the next chunk from every input, or `None` once all of them
+/// end together.
+fn next_chunk(inputs: &mut [Input]) -> Result<Option<Zeroizing<Vec<u8>>>, CliError> {
+    let mut shares = Vec::with_capacity(inputs.len());
+    for input in inputs.iter_mut() {
+        let bad = |reason: String| CliError::BadShareFile {
+            path: input.path.clone(),
+            reason,
+        };
+        let frame = read_frame(&mut input.reader).map_err(|e| bad(e.to_string()))?;
+        let share = match frame {
+            Some(frame) => Some(share_from_bytes(&frame).map_err(|e| bad(e.to_string()))?),
+            None => None,
+        };
+        if share
+            .as_ref()
+            .is_some_and(|s| s.x.0 != input.manifest.share)
+        {
+            return Err(bad("frame belongs to a different share".into()));
+        }
+        shares.push(share);
+    }
+    if shares.iter().all(Option::is_none) {
+        return Ok(None);
+    }
+    if let Some(i) = shares.iter().position(Option::is_none) {
+        return Err(CliError::BadShareFile {
+            path: inputs[i].path.clone(),
+            reason: "ends before the other shares".into(),
+        });
+    }
+    let refs: Vec<ShareRef<'_>> = shares.iter().flatten().map(|s| s.as_share_ref()).collect();
+    let mut chunk = Zeroizing::new(vec![0u8; refs[0].y.len()]);
+    reconstruct_into(&refs, refs.len(), &mut chunk)?;
+    Ok(Some(chunk))
+}
+
+/// Stream the restored file into `out`; the final chunk is the SHA-256
+/// of everything before it.
+fn restore(inputs: &mut [Input], out: &mut impl Write) -> Result<(), CliError> {
+    let size = inputs[0].manifest.size;
+    let mut hasher = Sha256::new();
+    let mut written = 0u64;
+    let mut pending = next_chunk(inputs)?;
+    while let Some(chunk) = pending {
+        pending = next_chunk(inputs)?;
+        if pending.is_none() {
+            if chunk.len() != DIGEST_LEN || written != size {
+                return Err(CliError::Checksum);
+            }
+            if hasher.finalize().as_slice() != chunk.as_slice() {
+                return Err(CliError::Checksum);
+            }
+            return Ok(());
+        }
+        hasher.update(&chunk);
+        out.write_all(&chunk)?;
+        written += chunk.len() as u64;
+    }
+    Err(CliError::Checksum)
+}
+
+pub fn run(args: JoinArgs) -> Result<(), CliError> {
+    let mut inputs = open_set(expand(&args.inputs)?)?;
+    inputs.truncate(inputs[0].manifest.threshold as usize);
+
+    // The stored name is untrusted: never let it point outside the
+    // current directory.
+    let output = match args.output {
+        Some(path) => path,
+        None => Path::new(&inputs[0].manifest.name)
+            .file_name()
+            .map(PathBuf::from)
+            .ok_or_else(|| CliError::Usage("share files carry no usable name; pass -o".into()))?,
+    };
+    if output.exists() && !args.force {
+        return Err(CliError::Usage(format!(
+            "{} exists; pass --force to replace it",
+            output.display()
+        )));
+    }
+    // Restore next to the target and only rename once the checksum matches.
+    let mut partial = output.clone().into_os_string();
+    partial.push(".partial");
+    let partial = PathBuf::from(partial);
+    let file_error = |path: &Path| {
+        let path = path.to_path_buf();
+        move |source| CliError::File { path, source }
+    };
+    let file = OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(&partial)
+        .map_err(file_error(&partial))?;
+
+    let mut out = BufWriter::new(file);
+    let restored = restore(&mut inputs, &mut out).and_then(|()| {
+        let file = out.into_inner().map_err(|e| e.into_error())?;
+        file.sync_all()?;
+        Ok(())
+    });
+    match restored {
+        Ok(()) => fs::rename(&partial, &output).map_err(file_error(&output)),
+        Err(e) => {
+            let _ = fs::remove_file(&partial);
+            Err(e)
+        }
+    }
+}
@@ -0,0 +1,68 @@
+// main.rs
+//
+// `sss`: split and combine secrets from the command line, for scripts and
+// air-gapped machines. Built on `shamir-gf256`; every share format the
+// library reads is accepted on input.
+
+use clap::{Parser, Subcommand};
+use std::process::ExitCode;
+
+mod ceremony;
+mod combine;
+mod error;
+mod input;
+mod inspect;
+mod join;
+mod share_file;
+mod split;
+mod split_file;
+mod verify;
+
+use error::CliError;
+
+#[derive(Parser)]
+#[command(name = "sss", version, about = "Shamir's Secret Sharing over GF(256)")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Split a secret into shares
+    Split(split::SplitArgs),
+    /// Reconstruct a secret from shares
+    Combine(combine::CombineArgs),
+    /// Split a file into one share file per custodian
+    SplitFile(split_file::SplitFileArgs),
+    /// Restore a file from share files written by `split-file`
+    Join(join::JoinArgs),
+    /// Interactive key ceremony at one terminal
+    Ceremony(ceremony::CeremonyArgs),
+    /// Describe shares without reconstructing anything
+    Inspect(inspect::InspectArgs),
+    /// Check that shares lie on one polynomial, without reconstructing
+    Verify(verify::VerifyArgs),
+}
+
+fn run(cli: Cli) -> Result<(), CliError> {
+    match cli.command {
+        Command::Split(args) => split::run(args),
+        Command::Combine(args) => combine::run(args),
+        Command::SplitFile(args) => split_file::run(args),
+        Command::Join(args) => join::run(args),
+        Command::Ceremony(args) => ceremony::run(args),
+        Command::Inspect(args) => inspect::run(args),
+        Command::Verify(args) => verify::run(args),
+    }
+}
+
+fn main() -> ExitCode {
+    match run(Cli::parse()) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("sss: error: {e}");
+            ExitCode::FAILURE
+        }
+    }
+}
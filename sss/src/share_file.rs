@@ -0,0 +1,232 @@
+// share_file.rs
+//
+// On-disk layout of one custodian's share of a whole file:
+//
+//   SSS-FILE/1
+//   Name: keystore.jks
+//   Set-Id: 1a2b3c4d
+//   Threshold: 3
+//   Share-Count: 5
+//   Share: 2
+//   Size: 123456
+//   Chunk-Size: 65536
+//   <blank line>
+//   { u32 LE frame length || SHR1/SHR2 frame } ...
+//
+// The input is split chunk by chunk so neither side holds the whole file in
+// memory. The last frame is the SHA-256 of the file, split like any other
+// chunk: `join` checks it without the digest ever being readable from fewer
+// than `Threshold` shares.
+
+use crate::error::CliError;
+use std::io::{self, BufRead, Read, Write};
+use std::path::Path;
+use zeroize::Zeroizing;
+
+pub const MAGIC: &str = "SSS-FILE/1";
+pub const DEFAULT_CHUNK_SIZE: u32 = 64 * 1024;
+pub const DIGEST_LEN: usize = 32;
+
+/// Largest frame `read_frame` accepts; bounds allocations on damaged input.
+const MAX_FRAME: u32 = 16 * 1024 * 1024;
+
+/// Header of a share file. Everything except `share` is the same across
+/// the set.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Manifest {
+    pub name: String,
+    pub set_id: String,
+    pub threshold: u8,
+    pub share_count: u8,
+    pub share: u8,
+    pub size: u64,
+    pub chunk_size: u32,
+}
+
+impl Manifest {
+    /// `name.share-01-of-05`; the index is padded to the width of the count.
+    pub fn file_name(&self) -> String {
+        let width = self.share_count.to_string().len().max(2);
+        format!(
+            "{}.share-{:0width$}-of-{:0width$}",
+            self.name, self.share, self.share_count
+        )
+    }
+
+    pub fn write_to(&self, out: &mut impl Write) -> io::Result<()> {
+        writeln!(out, "{MAGIC}")?;
+        writeln!(out, "Name: {}", self.name)?;
+        writeln!(out, "Set-Id: {}", self.set_id)?;
+        writeln!(out, "Threshold: {}", self.threshold)?;
+        writeln!(out, "Share-Count: {}", self.share_count)?;
+        writeln!(out, "Share: {}", self.share)?;
+        writeln!(out, "Size: {}", self.size)?;
+        writeln!(out, "Chunk-Size: {}", self.chunk_size)?;
+        writeln!(out)
+    }
+
+    /// Read the header, leaving `input` positioned at the first frame.
+    pub fn read_from(input: &mut impl BufRead, path: &Path) -> Result<Self, CliError> {
+        let bad = |reason: &str| CliError::BadShareFile {
+            path: path.to_path_buf(),
+            reason: reason.to_string(),
+        };
+        let mut line = String::new();
+        let mut next_line = |line: &mut String| -> Result<(), CliError> {
+            line.clear();
+            // A header line is short; refuse to buffer arbitrary binary data.
+            input.by_ref().take(1024).read_line(line)?;
+            if !line.ends_with('\n') {
+                return Err(bad("truncated or oversized header"));
+            }
+            line.truncate(line.trim_end_matches(['\r', '\n']).len());
+            Ok(())
+        };
+
+        next_line(&mut line)?;
+        if line != MAGIC {
+            return Err(bad("not an sss share file"));
+        }
+        let (mut name, mut set_id) = (None, None);
+        let (mut threshold, mut share_count, mut share) = (None, None, None);
+        let (mut size, mut chunk_size) = (None, None);
+        loop {
+            next_line(&mut line)?;
+            if line.is_empty() {
+                break;
+            }
+            let (key, value) = line
+                .split_once(": ")
+                .ok_or_else(|| bad("malformed header line"))?;
+            let value = value.trim();
+            match key {
+                "Name" => name = Some(value.to_string()),
+                "Set-Id" => set_id = Some(value.to_string()),
+                "Threshold" => threshold = value.parse().ok(),
+                "Share-Count" => share_count = value.parse().ok(),
+                "Share" => share = value.parse().ok(),
+                "Size" => size = value.parse().ok(),
+                "Chunk-Size" => chunk_size = value.parse().ok(),
+                // Unknown headers are informational.
+                _ => {}
+            }
+        }
+        let missing = |field: &str| bad(&format!("missing or invalid {field} header"));
+        let manifest = Manifest {
+            name: name.ok_or_else(|| missing("Name"))?,
+            set_id: set_id.ok_or_else(|| missing("Set-Id"))?,
+            threshold: threshold.ok_or_else(|| missing("Threshold"))?,
+            share_count: share_count.ok_or_else(|| missing("Share-Count"))?,
+            share: share.ok_or_else(|| missing("Share"))?,
+            size: size.ok_or_else(|| missing("Size"))?,
+            chunk_size: chunk_size
+                .filter(|&c| c > 0)
+                .ok_or_else(|| missing("Chunk-Size"))?,
+        };
+        if manifest.threshold == 0
+            || manifest.threshold > manifest.share_count
+            || manifest.share == 0
+            || manifest.share > manifest.share_count
+        {
+            return Err(bad("inconsistent threshold and share numbers"));
+        }
+        Ok(manifest)
+    }
+
+    /// The first header field on which `other` belongs to a different set.
+    pub fn mismatch(&self, other: &Manifest) -> Option<&'static str> {
+        if self.set_id != other.set_id {
+            Some("Set-Id")
+        } else if self.name != other.name {
+            Some("Name")
+        } else if self.threshold != other.threshold {
+            Some("Threshold")
+        } else if self.share_count != other.share_count {
+            Some("Share-Count")
+        } else if self.size != other.size {
+            Some("Size")
+        } else if self.chunk_size != other.chunk_size {
+            Some("Chunk-Size")
+        } else {
+            None
+        }
+    }
+}
+
+pub fn write_frame(out: &mut impl Write, frame: &[u8]) -> io::Result<()> {
+    let len: u32 = frame.len().try_into().expect("frame fits in u32");
+    out.write_all(&len.to_le_bytes())?;
+    out.write_all(frame)
+}
+
+/// The next length-prefixed frame, or `None` at a clean end of file.
+pub fn read_frame(input: &mut impl Read) -> io::Result<Option<Zeroizing<Vec<u8>>>> {
+    let mut len = [0u8; 4];
+    match input.read_exact(&mut len) {
+        Ok(()) => {}
+        Err(e) if e.kind() == io::ErrorKind::UnexpectedEof => return Ok(None),
+        Err(e) => return Err(e),
+    }
+    let len = u32::from_le_bytes(len);
+    if len > MAX_FRAME {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "frame length out of range",
+        ));
+    }
+    let mut frame = Zeroizing::new(vec![0u8; len as usize]);
+    input.read_exact(&mut frame)?;
+    Ok(Some(frame))
+}
+
+/// Fill `buf` from `input` until it is full or the input ends; returns the
+/// number of bytes read.
+pub fn read_chunk(input: &mut impl Read, buf: &mut [u8]) -> io::Result<usize> {
+    let mut filled = 0;
+    while filled < buf.len() {
+        match input.read(&mut buf[filled..]) {
+            Ok(0) => break,
+            Ok(n) => filled += n,
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(filled)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn manifest_round_trip() {
+        let manifest = Manifest {
+            name: "keystore.jks".into(),
+            set_id: "1a2b3c4d".into(),
+            threshold: 3,
+            share_count: 5,
+            share: 2,
+            size: 123_456,
+            chunk_size: DEFAULT_CHUNK_SIZE,
+        };
+        assert_eq!(manifest.file_name(), "keystore.jks.share-02-of-05");
+
+        let mut bytes = Vec::new();
+        manifest.write_to(&mut bytes).unwrap();
+        write_frame(&mut bytes, b"SHR1").unwrap();
+
+        let mut input = &bytes[..];
+        let read = Manifest::read_from(&mut input, Path::new("x")).unwrap();
+        assert_eq!(read, manifest);
+        assert_eq!(read_frame(&mut input).unwrap().unwrap().as_slice(), b"SHR1");
+        assert!(read_frame(&mut input).unwrap().is_none());
+
+        let wide = Manifest {
+            share_count: 120,
+            share: 7,
+            ..manifest
+        };
+        assert_eq!(wide.file_name(), "keystore.jks.share-007-of-120");
+        assert!(Manifest::read_from(&mut &b"garbage\n"[..], Path::new("x")).is_err());
+    }
+}
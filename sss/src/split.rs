@@ -0,0 +1,111 @@
+use crate::error::CliError;
+use crate::input::read_secret;
+use clap::{Args, ValueEnum};
+use rand::RngCore;
+use shamir_gf256::encoding::{Base58Check, Base64Url, Bech32m, CrockfordBase32, Hex, Words};
+use shamir_gf256::{
+    ArmorHeaders, Share, ShareEncoding, ShareSetMeta, share_encode, share_to_armor,
+};
+use std::io::{self, Write};
+use std::path::PathBuf;
+use zeroize::Zeroizing;
+
+#[derive(Args)]
+pub struct SplitArgs {
+    /// Shares needed to reconstruct
+    #[arg(short, long)]
+    pub threshold: u8,
+    /// Shares to create
+    #[arg(short = 'n', long = "shares")]
+    pub share_count: u8,
+    /// Read the secret from this file (`-` for stdin) instead of stdin/prompt
+    #[arg(short, long)]
+    pub input: Option<PathBuf>,
+    /// Always prompt for the secret without echo, even if stdin is a pipe
+    #[arg(long)]
+    pub prompt: bool,
+    /// Output format for the shares
+    #[arg(short, long, value_enum, default_value_t = Codec::Hex)]
+    pub codec: Codec,
+    /// Reed–Solomon parity bytes per block (2..=254)
+    #[arg(long, default_value_t = 16, value_parser = clap::value_parser!(u8).range(2..=254))]
+    pub ecc: u8,
+}
+
+/// Text forms `sss split` can write; `combine` reads all of them.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum Codec {
+    Hex,
+    Base32,
+    Base58check,
+    Base64url,
+    Bech32m,
+    Words,
+    /// `-----BEGIN SSS SHARE-----` blocks with set headers
+    Armor,
+}
+
+impl Codec {
+    fn encoding(self) -> Option<&'static dyn ShareEncoding> {
+        match self {
+            Codec::Hex => Some(&Hex),
+            Codec::Base32 => Some(&CrockfordBase32),
+            Codec::Base58check => Some(&Base58Check),
+            Codec::Base64url => Some(&Base64Url),
+            Codec::Bech32m => Some(&Bech32m),
+            Codec::Words => Some(&Words),
+            Codec::Armor => None,
+        }
+    }
+
+    /// Encode one share; armor gets the set's headers.
+    pub fn encode(
+        self,
+        share: &Share,
+        ecc: usize,
+        headers: &ArmorHeaders,
+    ) -> Result<Zeroizing<String>, CliError> {
+        let text = match self.encoding() {
+            Some(encoding) => share_encode(share, ecc, encoding)?,
+            None => share_to_armor(share, ecc, headers)?,
+        };
+        Ok(Zeroizing::new(text))
+    }
+}
+
+/// Random identifier written into every share of one split (armor `Set-Id`).
+pub fn new_set_id() -> String {
+    let mut id = [0u8; 4];
+    rand::thread_rng().fill_bytes(&mut id);
+    id.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+pub fn check_parameters(threshold: u8, share_count: u8) -> Result<ShareSetMeta, CliError> {
+    ShareSetMeta::new(threshold, share_count).ok_or_else(|| {
+            CliError::Usage(format!(
+                "threshold must be between 1 and the number of shares (got -t {threshold} -n {share_count})"
+            ))
+        })
+}
+
+pub fn run(args: SplitArgs) -> Result<(), CliError> {
+    let meta = check_parameters(args.threshold, args.share_count)?;
+    let secret = read_secret(args.input.as_deref(), args.prompt)?;
+    let shares = shamir_gf256::split(&secret, meta.threshold as usize, meta.share_count as usize);
+
+    let headers = ArmorHeaders {
+        set_id: Some(new_set_id()),
+        meta: Some(meta),
+        ..ArmorHeaders::default()
+    };
+    let mut out = io::stdout().lock();
+    for (i, share) in shares.iter().enumerate() {
+        let text = args.codec.encode(share, args.ecc as usize, &headers)?;
+        if args.codec == Codec::Armor && i > 0 {
+            writeln!(out)?;
+        }
+        writeln!(out, "{}", text.trim_end())?;
+    }
+    out.flush()?;
+    Ok(())
+}
@@ -0,0 +1,115 @@
+use crate::error::CliError;
+use crate::share_file::{DEFAULT_CHUNK_SIZE, Manifest, read_chunk, write_frame};
+use crate::split::{check_parameters, new_set_id};
+use clap::Args;
+use sha2::{Digest, Sha256};
+use shamir_gf256::{share_to_bytes, split};
+use std::fs::{self, File, OpenOptions};
+use std::io::{BufReader, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use zeroize::Zeroizing;
+
+#[derive(Args)]
+pub struct SplitFileArgs {
+    /// File to split
+    pub file: PathBuf,
+    /// Shares needed to reconstruct
+    #[arg(short, long)]
+    pub threshold: u8,
+    /// Shares to create, one file each
+    #[arg(short = 'n', long = "shares")]
+    pub share_count: u8,
+    /// Directory for the share files (created if missing)
+    #[arg(short, long, default_value = ".")]
+    pub out_dir: PathBuf,
+    /// Bytes of the input per frame
+    #[arg(long, default_value_t = DEFAULT_CHUNK_SIZE, value_parser = clap::value_parser!(u32).range(1..=1 << 22))]
+    pub chunk_size: u32,
+    /// Reed–Solomon parity bytes per block (2..=254)
+    #[arg(long, default_value_t = 16, value_parser = clap::value_parser!(u8).range(2..=254))]
+    pub ecc: u8,
+}
+
+fn file_error(path: &Path) -> impl FnOnce(std::io::Error) -> CliError + '_ {
+    |source| CliError::File {
+        path: path.to_path_buf(),
+        source,
+    }
+}
+
+pub fn run(args: SplitFileArgs) -> Result<(), CliError> {
+    let meta = check_parameters(args.threshold, args.share_count)?;
+    let input = File::open(&args.file).map_err(file_error(&args.file))?;
+    let size = input.metadata().map_err(file_error(&args.file))?.len();
+    let name = args
+        .file
+        .file_name()
+        .and_then(|n| n.to_str())
+        .ok_or_else(|| CliError::Usage(format!("{}: not a file name", args.file.display())))?
+        .to_string();
+
+    let set_id = new_set_id();
+    fs::create_dir_all(&args.out_dir).map_err(file_error(&args.out_dir))?;
+    let mut outputs = Vec::with_capacity(meta.share_count as usize);
+    for share in 1..=meta.share_count {
+        let manifest = Manifest {
+            name: name.clone(),
+            set_id: set_id.clone(),
+            threshold: meta.threshold,
+            share_count: meta.share_count,
+            share,
+            size,
+            chunk_size: args.chunk_size,
+        };
+        let path = args.out_dir.join(manifest.file_name());
+        // Never overwrite an earlier set's share with one from this set.
+        let file = OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(&path)
+            .map_err(file_error(&path))?;
+        let mut out = BufWriter::new(file);
+        manifest.write_to(&mut out).map_err(file_error(&path))?;
+        outputs.push((path, out));
+    }
+
+    let mut write_chunk = |chunk: &[u8]| -> Result<(), CliError> {
+        let shares = split(chunk, meta.threshold as usize, meta.share_count as usize);
+        for ((path, out), share) in outputs.iter_mut().zip(&shares) {
+            let frame = Zeroizing::new(share_to_bytes(share, args.ecc as usize));
+            write_frame(out, &frame).map_err(file_error(path))?;
+        }
+        Ok(())
+    };
+
+    let mut reader = BufReader::new(input);
+    let mut hasher = Sha256::new();
+    let mut buf = Zeroizing::new(vec![0u8; args.chunk_size as usize]);
+    let mut total = 0u64;
+    loop {
+        let n = read_chunk(&mut reader, &mut buf).map_err(file_error(&args.file))?;
+        if n == 0 {
+            break;
+        }
+        hasher.update(&buf[..n]);
+        write_chunk(&buf[..n])?;
+        total += n as u64;
+    }
+    if total != size {
+        return Err(CliError::Usage(format!(
+            "{} changed size while it was being split",
+            args.file.display()
+        )));
+    }
+    write_chunk(&hasher.finalize())?;
+
+    for (path, out) in outputs {
+        let file = out
+            .into_inner()
+            .map_err(|e| file_error(&path)(e.into_error()))?;
+        file.sync_all().map_err(file_error(&path))?;
+        println!("{}", path.display());
+    }
+    std::io::stdout().flush()?;
+    Ok(())
+}
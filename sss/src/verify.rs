@@ -0,0 +1,127 @@
+use crate::combine::{Located, check_consistency, collect_shares};
+use crate::error::CliError;
+use crate::input::read_share_texts;
+use clap::Args;
+use shamir_gf256::{ShareRef, inconsistent_shares};
+use std::path::PathBuf;
+
+#[derive(Args)]
+pub struct VerifyArgs {
+    /// Files holding shares (any format); stdin if omitted
+    pub files: Vec<PathBuf>,
+    /// Require the complete set: every share from 1 to the share count
+    #[arg(long)]
+    pub all: bool,
+    /// Shares needed to reconstruct; defaults to the armor `Threshold` header
+    #[arg(short, long)]
+    pub threshold: Option<u8>,
+    /// Shares issued; defaults to the armor `Share-Count` header
+    #[arg(short = 'n', long = "shares")]
+    pub share_count: Option<u8>,
+}
+
+fn refs(shares: &[Located]) -> Vec<ShareRef<'_>> {
+    shares
+        .iter()
+        .map(|s| s.parsed.share.as_share_ref())
+        .collect()
+}
+
+/// Check the shares against a complete set of `share_count`.
+fn check_complete(shares: &[Located], share_count: u8) -> Result<(), CliError> {
+    if let Some(s) = shares.iter().find(|s| s.parsed.share.x.0 > share_count) {
+        return Err(CliError::Inconsistent(format!(
+            "{} holds share {}, outside a set of {share_count}",
+            s.location, s.parsed.share.x.0
+        )));
+    }
+    let missing: Vec<String> = (1..=share_count)
+        .filter(|&x| shares.iter().all(|s| s.parsed.share.x.0 != x))
+        .map(|x| x.to_string())
+        .collect();
+    if !missing.is_empty() {
+        return Err(CliError::Inconsistent(format!(
+            "share(s) {} of {share_count} missing",
+            missing.join(", ")
+        )));
+    }
+    Ok(())
+}
+
+/// Name the damaged share when leaving out exactly one makes the rest agree.
+fn blame(shares: &[Located], threshold: usize) -> Result<String, CliError> {
+    // Dropping one of t + 1 shares always leaves a consistent set.
+    if shares.len() >= threshold + 2 {
+        let mut suspects = Vec::new();
+        for i in 0..shares.len() {
+            let mut rest = refs(shares);
+            rest.remove(i);
+            if inconsistent_shares(&rest, threshold)?.is_empty() {
+                suspects.push(&shares[i]);
+            }
+        }
+        if let [bad] = suspects[..] {
+            return Ok(format!(
+                "{} (share {}) is not on the polynomial the other shares agree on",
+                bad.location, bad.parsed.share.x.0
+            ));
+        }
+    }
+    Ok(format!(
+        "the shares do not lie on one polynomial of degree {}; one or more is damaged or from another split",
+        threshold - 1
+    ))
+}
+
+pub fn run(args: VerifyArgs) -> Result<(), CliError> {
+    let texts = read_share_texts(&args.files)?;
+    let shares = collect_shares(&texts)?;
+    check_consistency(&shares)?;
+    if shares.is_empty() {
+        return Err(CliError::NoShares);
+    }
+    let meta = shares.iter().find_map(|s| s.parsed.headers.as_ref()?.meta);
+    let threshold = args
+        .threshold
+        .or(meta.map(|m| m.threshold))
+        .ok_or_else(|| {
+            CliError::Usage("the threshold is not recorded in these shares; pass -t".into())
+        })?;
+    if threshold == 0 {
+        return Err(CliError::Usage("threshold must be at least 1".into()));
+    }
+    let threshold = usize::from(threshold);
+
+    if args.all {
+        let share_count = args
+            .share_count
+            .or(meta.map(|m| m.share_count))
+            .ok_or_else(|| {
+                CliError::Usage("the share count is not recorded in these shares; pass -n".into())
+            })?;
+        check_complete(&shares, share_count)?;
+        if shares.len() == threshold {
+            println!(
+                "ok: {} of {share_count} shares complete; consistency cannot be checked with exactly {threshold} shares",
+                shares.len()
+            );
+            return Ok(());
+        }
+    }
+    if shares.len() <= threshold {
+        return Err(CliError::Usage(format!(
+            "checking consistency needs more than {threshold} shares, got {}",
+            shares.len()
+        )));
+    }
+
+    if !inconsistent_shares(&refs(&shares), threshold)?.is_empty() {
+        return Err(CliError::Inconsistent(blame(&shares, threshold)?));
+    }
+    println!(
+        "ok: {} shares lie on one polynomial of degree {}",
+        shares.len(),
+        threshold - 1
+    );
+    Ok(())
+}
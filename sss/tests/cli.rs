@@ -0,0 +1,364 @@
+// End-to-end checks of the `sss` binary: exit codes and messages are part of
+// its interface for scripts.
+
+use std::io::Write;
+use std::process::{Command, Output, Stdio};
+
+fn sss(args: &[&str], stdin: &[u8]) -> Output {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_sss"))
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .expect("spawn sss");
+    child.stdin.take().unwrap().write_all(stdin).unwrap();
+    child.wait_with_output().unwrap()
+}
+
+fn lines(out: &Output) -> Vec<String> {
+    String::from_utf8(out.stdout.clone())
+        .unwrap()
+        .lines()
+        .map(str::to_string)
+        .collect()
+}
+
+#[test]
+fn split_then_combine_round_trips_in_every_codec() {
+    for codec in [
+        "hex",
+        "base32",
+        "base58check",
+        "base64url",
+        "bech32m",
+        "words",
+    ] {
+        let split = sss(
+            &["split", "-t", "2", "-n", "3", "--codec", codec],
+            b"s3cret\n",
+        );
+        assert!(split.status.success(), "{codec}: {split:?}");
+        let shares = lines(&split);
+        assert_eq!(shares.len(), 3);
+
+        let input = format!("{}\n{}\n", shares[2], shares[0]);
+        let combined = sss(&["combine"], input.as_bytes());
+        assert!(combined.status.success(), "{codec}: {combined:?}");
+        assert_eq!(combined.stdout, b"s3cret\n");
+    }
+}
+
+#[test]
+fn armor_carries_the_threshold() {
+    let split = sss(
+        &["split", "-t", "2", "-n", "3", "--codec", "armor"],
+        b"armored",
+    );
+    assert!(split.status.success());
+    let text = String::from_utf8(split.stdout).unwrap();
+    assert_eq!(text.matches("-----BEGIN SSS SHARE-----").count(), 3);
+
+    let combined = sss(&["combine"], text.as_bytes());
+    assert!(combined.status.success());
+    assert_eq!(combined.stdout, b"armored");
+
+    let first: String = text
+        .split_inclusive("-----END SSS SHARE-----\n")
+        .next()
+        .unwrap()
+        .into();
+    let short = sss(&["combine"], first.as_bytes());
+    assert_eq!(short.status.code(), Some(1));
+    assert!(String::from_utf8_lossy(&short.stderr).contains("need 2 shares to reconstruct, got 1"));
+}
+
+#[test]
+fn combine_fails_clearly() {
+    let bad = sss(&["combine"], b"not a share\n");
+    assert_eq!(bad.status.code(), Some(1));
+    let err = String::from_utf8_lossy(&bad.stderr);
+    assert!(
+        err.starts_with("sss: error: <stdin>:1: no share format matched"),
+        "{err}"
+    );
+
+    let split = sss(&["split", "-t", "2", "-n", "2"], b"dup");
+    let share = lines(&split).remove(0);
+    let dup = sss(&["combine"], format!("{share}\n{share}\n").as_bytes());
+    assert_eq!(dup.status.code(), Some(1));
+    assert!(String::from_utf8_lossy(&dup.stderr).contains("both hold share 1"));
+
+    let missing = sss(&["combine", "/nonexistent/share.txt"], b"");
+    assert_eq!(missing.status.code(), Some(1));
+
+    let params = sss(&["split", "-t", "4", "-n", "3"], b"x");
+    assert_eq!(params.status.code(), Some(1));
+    assert!(String::from_utf8_lossy(&params.stderr).contains("threshold must be between 1"));
+}
+
+#[test]
+fn split_file_then_join_from_a_directory() {
+    let dir = std::env::temp_dir().join(format!("sss-join-{}", std::process::id()));
+    let shares = dir.join("shares");
+    std::fs::create_dir_all(&dir).unwrap();
+    let secret: Vec<u8> = (0..10_000u32).map(|i| (i * 7 % 251) as u8).collect();
+    let input = dir.join("keystore.jks");
+    std::fs::write(&input, &secret).unwrap();
+
+    let split = sss(
+        &[
+            "split-file",
+            input.to_str().unwrap(),
+            "-t",
+            "3",
+            "-n",
+            "5",
+            "-o",
+            shares.to_str().unwrap(),
+            "--chunk-size",
+            "4096",
+        ],
+        b"",
+    );
+    assert!(split.status.success(), "{split:?}");
+    assert_eq!(lines(&split).len(), 5);
+    let name = |i: u8| shares.join(format!("keystore.jks.share-0{i}-of-05"));
+    assert!(name(5).exists());
+
+    // Two shares are not enough; three from a directory are.
+    for i in [1, 3] {
+        std::fs::remove_file(name(i)).unwrap();
+    }
+    std::fs::copy(name(4), dir.join("keep")).unwrap();
+    std::fs::remove_file(name(4)).unwrap();
+    let restored = dir.join("restored.jks");
+    let short = sss(
+        &[
+            "join",
+            shares.to_str().unwrap(),
+            "-o",
+            restored.to_str().unwrap(),
+        ],
+        b"",
+    );
+    assert_eq!(short.status.code(), Some(1));
+    assert!(String::from_utf8_lossy(&short.stderr).contains("need 3 shares"));
+    assert!(!restored.exists());
+
+    std::fs::rename(dir.join("keep"), name(4)).unwrap();
+    let joined = sss(
+        &[
+            "join",
+            shares.to_str().unwrap(),
+            "-o",
+            restored.to_str().unwrap(),
+        ],
+        b"",
+    );
+    assert!(joined.status.success(), "{joined:?}");
+    assert_eq!(std::fs::read(&restored).unwrap(), secret);
+
+    // A share from another split of the same file is rejected by Set-Id.
+    let other = dir.join("other");
+    let resplit = sss(
+        &[
+            "split-file",
+            input.to_str().unwrap(),
+            "-t",
+            "3",
+            "-n",
+            "5",
+            "-o",
+            other.to_str().unwrap(),
+        ],
+        b"",
+    );
+    assert!(resplit.status.success());
+    let pattern = format!("{}/*.share-0[24]-of-05", shares.display());
+    let mixed = sss(
+        &[
+            "join",
+            &pattern,
+            other.join("keystore.jks.share-01-of-05").to_str().unwrap(),
+            "-o",
+            dir.join("mixed").to_str().unwrap(),
+        ],
+        b"",
+    );
+    assert_eq!(mixed.status.code(), Some(1));
+    assert!(String::from_utf8_lossy(&mixed.stderr).contains("Set-Id differs"));
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn ceremony_collects_shares_and_keeps_them_out_of_the_transcript() {
+    let dir = std::env::temp_dir().join(format!("sss-ceremony-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let split = sss(
+        &["split", "-t", "2", "-n", "3", "--codec", "bech32m"],
+        b"unseal",
+    );
+    let shares = lines(&split);
+
+    // Bob first types alice's share again, then his own.
+    let typed = format!("alice\n{}\nbob\n{}\n{}\n", shares[0], shares[0], shares[2]);
+    let output = dir.join("secret");
+    let run = sss(
+        &[
+            "ceremony",
+            "combine",
+            "-t",
+            "2",
+            "-o",
+            output.to_str().unwrap(),
+        ],
+        typed.as_bytes(),
+    );
+    assert!(run.status.success(), "{run:?}");
+    assert_eq!(std::fs::read(&output).unwrap(), b"unseal");
+
+    let transcript = String::from_utf8(run.stdout).unwrap();
+    assert!(
+        transcript.contains("accepted: alice: share 1"),
+        "{transcript}"
+    );
+    assert!(transcript.contains("rejected: bob: share 1 was already contributed by alice"));
+    assert!(transcript.contains("accepted: bob: share 3"));
+    assert!(transcript.contains("secret reconstructed and written to"));
+    for share in &shares {
+        assert!(!transcript.contains(share.as_str()));
+    }
+    assert!(String::from_utf8_lossy(&run.stderr).contains("1 of 2 collected"));
+
+    let piped = dir.join("piped");
+    let typed = format!("carol\n{}\ndave\n{}\n", shares[1], shares[2]);
+    let run = sss(
+        &[
+            "ceremony",
+            "combine",
+            "-t",
+            "2",
+            "--pipe",
+            &format!("cat > '{}'", piped.display()),
+        ],
+        typed.as_bytes(),
+    );
+    assert!(run.status.success(), "{run:?}");
+    assert_eq!(std::fs::read(&piped).unwrap(), b"unseal");
+
+    let aborted = sss(
+        &[
+            "ceremony",
+            "combine",
+            "-t",
+            "2",
+            "-o",
+            dir.join("never").to_str().unwrap(),
+        ],
+        format!("erin\n{}\n", shares[0]).as_bytes(),
+    );
+    assert_eq!(aborted.status.code(), Some(1));
+    assert!(String::from_utf8_lossy(&aborted.stdout).contains("ceremony failed: ceremony aborted"));
+
+    // A share damaged past its parity is turned away by name instead of
+    // setting the share length for everyone after it; unframed formats need
+    // an explicit opt-in.
+    let hex = lines(&sss(&["split", "-t", "2", "-n", "3"], b"unseal"));
+    let mut damaged = hex[0].clone().into_bytes();
+    for b in &mut damaged[20..60] {
+        // Shift every hex digit: 20 byte errors, far from any codeword.
+        *b = b"123456789abcdef0"[(*b as char).to_digit(16).unwrap() as usize];
+    }
+    let damaged = String::from_utf8(damaged).unwrap();
+    let ssss = format!("1-{}", "ab".repeat(6));
+    let typed = format!("frank\n{damaged}\n{ssss}\n{}\ngrace\n{}\n", hex[0], hex[1]);
+    let output = dir.join("after-damage");
+    let run = sss(
+        &[
+            "ceremony",
+            "combine",
+            "-t",
+            "2",
+            "-o",
+            output.to_str().unwrap(),
+        ],
+        typed.as_bytes(),
+    );
+    assert!(run.status.success(), "{run:?}");
+    assert_eq!(std::fs::read(&output).unwrap(), b"unseal");
+    let stderr = String::from_utf8_lossy(&run.stderr);
+    assert!(
+        stderr.contains("frank's hex share is damaged beyond repair"),
+        "{stderr}"
+    );
+    assert!(stderr.contains("ssss shares carry no error correction"));
+    let transcript = String::from_utf8(run.stdout).unwrap();
+    assert!(transcript.contains("rejected: frank: share damaged beyond repair"));
+    assert!(transcript.contains("accepted: grace: share 2"));
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn inspect_and_verify_without_reconstructing() {
+    let split = sss(
+        &["split", "-t", "2", "-n", "3", "--codec", "armor"],
+        b"health",
+    );
+    let armored = String::from_utf8(split.stdout).unwrap();
+
+    let inspect = sss(&["inspect"], armored.as_bytes());
+    assert!(inspect.status.success(), "{inspect:?}");
+    let report = String::from_utf8(inspect.stdout).unwrap();
+    assert_eq!(report.matches("format:       armor").count(), 3);
+    assert!(report.contains("ecc length:   16 parity bytes × 1 block(s)"));
+    assert!(report.contains("threshold 2 of 3"));
+    assert!(!report.contains("health"));
+
+    let verify = sss(&["verify", "--all"], armored.as_bytes());
+    assert!(verify.status.success(), "{verify:?}");
+    assert!(String::from_utf8_lossy(&verify.stdout).contains("ok: 3 shares"));
+
+    // Damage past the ecc budget is reported as such, not as another format.
+    let hex = lines(&sss(&["split", "-t", "2", "-n", "3"], b"health"));
+    let mut damaged = hex[0].clone().into_bytes();
+    for b in &mut damaged[20..60] {
+        *b = b"123456789abcdef0"[(*b as char).to_digit(16).unwrap() as usize];
+    }
+    let inspect = sss(&["inspect"], &damaged);
+    assert_eq!(inspect.status.code(), Some(1));
+    let report = String::from_utf8(inspect.stdout).unwrap();
+    assert!(report.contains("format:       hex"), "{report}");
+    assert!(report.contains("corrections:  uncorrectable (EccDecode)"));
+    assert!(!report.contains("vault"));
+
+    // Swap in a share from another split: it no longer fits the polynomial.
+    let ours = lines(&sss(&["split", "-t", "2", "-n", "4"], b"health"));
+    let theirs = lines(&sss(&["split", "-t", "2", "-n", "4"], b"health"));
+    let mixed = format!("{}\n{}\n{}\n{}\n", ours[0], ours[1], ours[2], theirs[3]);
+    let verify = sss(&["verify", "-t", "2", "--all", "-n", "4"], mixed.as_bytes());
+    assert_eq!(verify.status.code(), Some(1));
+    assert!(
+        String::from_utf8_lossy(&verify.stderr)
+            .contains("<stdin>:4 (share 4) is not on the polynomial")
+    );
+
+    let partial = format!("{}\n{}\n{}\n", ours[0], ours[1], ours[3]);
+    let verify = sss(
+        &["verify", "-t", "2", "--all", "-n", "4"],
+        partial.as_bytes(),
+    );
+    assert!(String::from_utf8_lossy(&verify.stderr).contains("share(s) 3 of 4 missing"));
+
+    // A complete set of exactly t shares is accepted, though it proves nothing
+    // about consistency.
+    let pair = lines(&sss(&["split", "-t", "2", "-n", "2"], b"health")).join("\n");
+    let verify = sss(&["verify", "-t", "2", "--all", "-n", "2"], pair.as_bytes());
+    assert!(verify.status.success(), "{verify:?}");
+    assert!(
+        String::from_utf8_lossy(&verify.stdout)
+            .contains("complete; consistency cannot be checked with exactly 2 shares")
+    );
+}
@@ -0,0 +1,379 @@
+// app.rs
+//
+// State and key handling for the terminal UI, kept free of drawing code so it
+// can be driven from tests. Every buffer that holds a secret or a share is
+// `Zeroizing` (or a `Share`, which zeroizes on drop), so dropping the `App`
+// on exit wipes them.
+
+use ratatui::crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use shamir_gf256::{Share, ShareRef, parse_any_share, reconstruct_into, share_to_hex};
+use zeroize::Zeroizing;
+
+/// Parity bytes per share, as in the web UI.
+pub const ECC_LEN: usize = 16;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Tab {
+    Encrypt,
+    Decrypt,
+    Information,
+}
+
+impl Tab {
+    pub const ALL: [Tab; 3] = [Tab::Encrypt, Tab::Decrypt, Tab::Information];
+
+    pub fn title(self) -> &'static str {
+        match self {
+            Tab::Encrypt => "Encrypt",
+            Tab::Decrypt => "Decrypt",
+            Tab::Information => "Information",
+        }
+    }
+
+    /// Fields that take focus on this tab, in Tab-key order.
+    fn fields(self) -> &'static [Field] {
+        match self {
+            Tab::Encrypt => &[
+                Field::Threshold,
+                Field::ShareCount,
+                Field::Secret,
+                Field::Shares,
+            ],
+            Tab::Decrypt => &[Field::Threshold, Field::Entry],
+            Tab::Information => &[],
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Field {
+    Threshold,
+    ShareCount,
+    Secret,
+    Shares,
+    Entry,
+}
+
+/// A share accepted on the Decrypt tab and how it was read.
+pub struct Accepted {
+    pub share: Share,
+    pub note: String,
+}
+
+pub struct App {
+    pub tab: Tab,
+    pub focus: Option<Field>,
+    pub threshold: String,
+    pub share_count: String,
+    /// Show secrets in clear instead of `•`.
+    pub reveal: bool,
+    pub secret: Zeroizing<String>,
+    /// Hex shares from the last split.
+    pub shares: Vec<Zeroizing<String>>,
+    pub selected: usize,
+    pub entry: Zeroizing<String>,
+    pub accepted: Vec<Accepted>,
+    pub recovered: Option<Zeroizing<Vec<u8>>>,
+    pub message: Option<String>,
+    pub info_scroll: u16,
+    pub quit: bool,
+}
+
+impl Default for App {
+    fn default() -> Self {
+        App {
+            tab: Tab::Encrypt,
+            focus: Some(Field::Threshold),
+            threshold: String::new(),
+            share_count: String::new(),
+            reveal: false,
+            secret: Zeroizing::new(String::new()),
+            shares: Vec::new(),
+            selected: 0,
+            entry: Zeroizing::new(String::new()),
+            accepted: Vec::new(),
+            recovered: None,
+            message: None,
+            info_scroll: 0,
+            quit: false,
+        }
+    }
+}
+
+impl App {
+    /// Threshold as entered, if it is a usable number (> 1, as in the web UI).
+    pub fn threshold(&self) -> Option<usize> {
+        self.threshold
+            .parse()
+            .ok()
+            .filter(|&t| (2..=255).contains(&t))
+    }
+
+    /// Both numbers, if they describe a valid split.
+    pub fn parameters(&self) -> Option<(usize, usize)> {
+        let t = self.threshold()?;
+        let n: usize = self.share_count.parse().ok()?;
+        (t <= n && n <= 255).then_some((t, n))
+    }
+
+    /// Live check of the share being typed on the Decrypt tab.
+    pub fn entry_status(&self) -> Option<Result<String, String>> {
+        if self.entry.trim().is_empty() {
+            return None;
+        }
+        Some(match parse_any_share(&self.entry) {
+            Ok(parsed) => self
+                .duplicate_of(&parsed.share)
+                .map_or_else(|| Ok(describe(&parsed)), Err),
+            Err(e) => Err(e.to_string()),
+        })
+    }
+
+    fn duplicate_of(&self, share: &Share) -> Option<String> {
+        if self.accepted.iter().any(|a| a.share.x == share.x) {
+            return Some(format!("share {} is already entered", share.x.0));
+        }
+        match self.accepted.first() {
+            Some(first) if first.share.y.len() != share.y.len() => {
+                Some("length differs from the shares entered so far".into())
+            }
+            _ => None,
+        }
+    }
+
+    pub fn on_key(&mut self, key: KeyEvent) {
+        let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+        match key.code {
+            KeyCode::Esc => self.quit = true,
+            KeyCode::Char('q' | 'c') if ctrl => self.quit = true,
+            KeyCode::Char('r') if ctrl => self.reveal = !self.reveal,
+            KeyCode::F(n @ 1..=3) => self.switch(Tab::ALL[n as usize - 1]),
+            KeyCode::Tab => self.cycle(1),
+            KeyCode::BackTab => self.cycle(-1),
+            _ => match self.focus {
+                Some(field) => self.edit(field, key),
+                None => self.scroll(key.code),
+            },
+        }
+    }
+
+    fn switch(&mut self, tab: Tab) {
+        self.tab = tab;
+        self.focus = tab.fields().first().copied();
+        self.message = None;
+    }
+
+    fn cycle(&mut self, step: isize) {
+        let fields = self.tab.fields();
+        if let Some(current) = self.focus {
+            let i = fields.iter().position(|&f| f == current).unwrap_or(0) as isize;
+            let next = (i + step).rem_euclid(fields.len() as isize) as usize;
+            self.focus = Some(fields[next]);
+        }
+    }
+
+    fn scroll(&mut self, code: KeyCode) {
+        match code {
+            KeyCode::Up => self.info_scroll = self.info_scroll.saturating_sub(1),
+            KeyCode::Down => self.info_scroll = self.info_scroll.saturating_add(1),
+            KeyCode::PageUp => self.info_scroll = self.info_scroll.saturating_sub(10),
+            KeyCode::PageDown => self.info_scroll = self.info_scroll.saturating_add(10),
+            _ => {}
+        }
+    }
+
+    fn edit(&mut self, field: Field, key: KeyEvent) {
+        let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+        match (field, key.code) {
+            (Field::Entry, KeyCode::Char('u')) if ctrl => self.entry.clear(),
+            (Field::Entry, KeyCode::Char('d')) if ctrl => {
+                self.accepted.pop();
+                self.try_recover();
+            }
+            // Other control chords are not text.
+            (_, KeyCode::Char(_)) if ctrl => {}
+            (Field::Threshold, code) => {
+                edit_number(&mut self.threshold, code);
+                self.try_recover();
+            }
+            (Field::ShareCount, code) => edit_number(&mut self.share_count, code),
+            (Field::Secret, KeyCode::Enter) => self.generate(),
+            (Field::Secret, code) => edit_text(&mut self.secret, code),
+            (Field::Shares, KeyCode::Up) => self.selected = self.selected.saturating_sub(1),
+            (Field::Shares, KeyCode::Down) => {
+                self.selected = (self.selected + 1).min(self.shares.len().saturating_sub(1));
+            }
+            (Field::Shares, _) => {}
+            (Field::Entry, KeyCode::Enter) => self.accept(),
+            (Field::Entry, code) => edit_text(&mut self.entry, code),
+        }
+    }
+
+    /// Split the secret into hex shares.
+    pub fn generate(&mut self) {
+        let Some((threshold, share_count)) = self.parameters() else {
+            self.message = Some(
+                "Enter valid numbers (>1) for both fields; the threshold must not exceed the share count."
+                    .into(),
+            );
+            return;
+        };
+        if self.secret.is_empty() {
+            self.message = Some("Enter a secret first.".into());
+            return;
+        }
+        let shares = shamir_gf256::split(self.secret.as_bytes(), threshold, share_count);
+        self.shares = shares
+            .iter()
+            .map(|share| Zeroizing::new(share_to_hex(share, ECC_LEN)))
+            .collect();
+        self.selected = 0;
+        self.focus = Some(Field::Shares);
+        self.message = Some(format!(
+            "{share_count} shares generated; any {threshold} recover the secret."
+        ));
+    }
+
+    /// Take the typed share if it is valid, then reconstruct once there are
+    /// enough.
+    pub fn accept(&mut self) {
+        match parse_any_share(&self.entry) {
+            Ok(parsed) => {
+                if let Some(reason) = self.duplicate_of(&parsed.share) {
+                    self.message = Some(reason);
+                    return;
+                }
+                let note = describe(&parsed);
+                self.accepted.push(Accepted {
+                    share: parsed.share,
+                    note,
+                });
+                self.entry.clear();
+                self.message = None;
+                self.try_recover();
+            }
+            Err(e) => self.message = Some(e.to_string()),
+        }
+    }
+
+    fn try_recover(&mut self) {
+        self.recovered = None;
+        let Some(threshold) = self.threshold() else {
+            return;
+        };
+        if self.accepted.len() < threshold {
+            return;
+        }
+        let refs: Vec<ShareRef<'_>> = self
+            .accepted
+            .iter()
+            .map(|a| a.share.as_share_ref())
+            .collect();
+        let mut secret = Zeroizing::new(vec![0u8; refs[0].y.len()]);
+        match reconstruct_into(&refs, threshold, &mut secret) {
+            Ok(()) => self.recovered = Some(secret),
+            Err(e) => self.message = Some(format!("Reconstruction failed: {e}")),
+        }
+    }
+}
+
+fn describe(parsed: &shamir_gf256::ParsedShare) -> String {
+    let repaired = parsed.report.as_ref().map_or(0, |r| r.corrected());
+    let mut note = format!("share {} ({})", parsed.share.x.0, parsed.format);
+    if repaired > 0 {
+        note.push_str(&format!(", {repaired} byte(s) repaired"));
+    }
+    note
+}
+
+fn edit_number(value: &mut String, code: KeyCode) {
+    match code {
+        KeyCode::Char(c) if c.is_ascii_digit() && value.len() < 3 => value.push(c),
+        KeyCode::Backspace => {
+            value.pop();
+        }
+        _ => {}
+    }
+}
+
+fn edit_text(value: &mut Zeroizing<String>, code: KeyCode) {
+    match code {
+        KeyCode::Char(c) => value.push(c),
+        KeyCode::Backspace => {
+            value.pop();
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn type_text(app: &mut App, text: &str) {
+        for c in text.chars() {
+            app.on_key(KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE));
+        }
+    }
+
+    fn press(app: &mut App, code: KeyCode) {
+        app.on_key(KeyEvent::new(code, KeyModifiers::NONE));
+    }
+
+    #[test]
+    fn split_then_recover_share_by_share() {
+        let mut app = App::default();
+        type_text(&mut app, "2");
+        press(&mut app, KeyCode::Tab);
+        type_text(&mut app, "3");
+        press(&mut app, KeyCode::Tab);
+        type_text(&mut app, "vault key");
+        press(&mut app, KeyCode::Enter);
+        assert_eq!(app.shares.len(), 3);
+        assert_eq!(app.focus, Some(Field::Shares));
+        let shares: Vec<String> = app.shares.iter().map(|s| s.to_string()).collect();
+
+        press(&mut app, KeyCode::F(2));
+        assert_eq!(app.focus, Some(Field::Threshold));
+        press(&mut app, KeyCode::Tab);
+        type_text(&mut app, "not a share");
+        assert!(matches!(app.entry_status(), Some(Err(_))));
+        app.on_key(KeyEvent::new(KeyCode::Char('u'), KeyModifiers::CONTROL));
+
+        type_text(&mut app, &shares[2]);
+        assert!(matches!(app.entry_status(), Some(Ok(_))));
+        press(&mut app, KeyCode::Enter);
+        assert_eq!(app.accepted.len(), 1);
+        assert!(app.recovered.is_none());
+
+        type_text(&mut app, &shares[2]);
+        assert_eq!(
+            app.entry_status(),
+            Some(Err("share 3 is already entered".into()))
+        );
+        app.on_key(KeyEvent::new(KeyCode::Char('u'), KeyModifiers::CONTROL));
+
+        type_text(&mut app, &shares[0]);
+        press(&mut app, KeyCode::Enter);
+        assert_eq!(
+            app.recovered.as_deref().map(|s| &s[..]),
+            Some(&b"vault key"[..])
+        );
+
+        press(&mut app, KeyCode::Esc);
+        assert!(app.quit);
+    }
+
+    #[test]
+    fn generate_requires_valid_parameters() {
+        let mut app = App {
+            threshold: "4".into(),
+            share_count: "3".into(),
+            secret: Zeroizing::new("x".into()),
+            ..App::default()
+        };
+        app.generate();
+        assert!(app.shares.is_empty());
+        assert!(app.message.is_some());
+    }
+}
@@ -0,0 +1,38 @@
+// main.rs
+//
+// `ssstui`: a terminal front-end with the web UI's workflows (encrypt,
+// decrypt, information) for operators on air-gapped machines who would rather
+// not drive the flag-based `sss` CLI.
+
+mod app;
+mod qr;
+mod ui;
+
+use app::App;
+use ratatui::DefaultTerminal;
+use ratatui::crossterm::event::{self, Event, KeyEventKind};
+use std::io;
+
+fn run(terminal: &mut DefaultTerminal) -> io::Result<()> {
+    // Dropping `app` at the end of this function wipes every secret it holds.
+    let mut app = App::default();
+    while !app.quit {
+        terminal.draw(|frame| ui::draw(frame, &app))?;
+        if let Event::Key(key) = event::read()?
+            && key.kind == KeyEventKind::Press
+        {
+            app.on_key(key);
+        }
+    }
+    Ok(())
+}
+
+fn main() -> io::Result<()> {
+    // `init` switches to the alternate screen and restores the terminal on panic.
+    let mut terminal = ratatui::init();
+    let result = run(&mut terminal);
+    // Blank the alternate screen so no share or secret survives in it.
+    let cleared = terminal.clear();
+    ratatui::restore();
+    result.and(cleared)
+}
@@ -0,0 +1,60 @@
+// qr.rs
+//
+// QR codes drawn with half-block characters: each text row holds two module
+// rows, so a code stays roughly square in a terminal. The caller paints them
+// dark-on-light; scanners expect that polarity, and it matches paper.
+
+use qrcode::types::QrError;
+use qrcode::{Color, QrCode};
+use zeroize::Zeroizing;
+
+/// Quiet zone around the code, in modules (the QR spec asks for 4).
+const QUIET: usize = 4;
+
+/// Text rows of the QR code for `data`, quiet zone included.
+pub fn qr_lines(data: &str) -> Result<Vec<String>, QrError> {
+    // Upper case keeps hex in the compact alphanumeric mode; share parsers
+    // accept either case. The copy is sized up front so it never reallocates,
+    // and wiped once the code is built.
+    let mut upper = Zeroizing::new(String::with_capacity(data.len()));
+    upper.extend(data.chars().map(|c| c.to_ascii_uppercase()));
+    let code = QrCode::new(upper.as_bytes())?;
+    let width = code.width();
+    let colors = code.to_colors();
+    let size = width + 2 * QUIET;
+    let dark = |row: usize, col: usize| {
+        (QUIET..QUIET + width).contains(&row)
+            && (QUIET..QUIET + width).contains(&col)
+            && colors[(row - QUIET) * width + (col - QUIET)] == Color::Dark
+    };
+
+    Ok((0..size)
+        .step_by(2)
+        .map(|row| {
+            (0..size)
+                .map(|col| match (dark(row, col), dark(row + 1, col)) {
+                    (true, true) => '█',
+                    (true, false) => '▀',
+                    (false, true) => '▄',
+                    (false, false) => ' ',
+                })
+                .collect()
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lines_cover_the_code_and_quiet_zone() {
+        let lines = qr_lines("53485231").unwrap();
+        // Version 1 is 21 modules wide.
+        let size = 21 + 2 * QUIET;
+        assert_eq!(lines.len(), size.div_ceil(2));
+        assert!(lines.iter().all(|l| l.chars().count() == size));
+        assert!(lines[0].trim().is_empty());
+        assert!(lines[2].contains('█'));
+    }
+}
@@ -0,0 +1,324 @@
+// ui.rs
+//
+// Drawing only: every frame is rendered from `App` state. The layout follows
+// the web UI's `TabMenu` — a tab bar, the shared threshold/share-count
+// inputs, then the tab's own panel.
+
+use crate::app::{App, Field, Tab};
+use crate::qr::qr_lines;
+use ratatui::Frame;
+use ratatui::layout::{Constraint, Layout, Position, Rect};
+use ratatui::style::{Color, Modifier, Style, Stylize};
+use ratatui::text::{Line, Span, Text};
+use ratatui::widgets::{Block, List, ListItem, ListState, Paragraph, Tabs, Wrap};
+
+const INFORMATION: &[(&str, &str)] = &[
+    (
+        "Shamir's secret sharing",
+        "Split a secret into n pieces so that any t pieces can rebuild it, while fewer than t reveal nothing at all. Great for keys, recovery codes, and high-stakes passwords.",
+    ),
+    (
+        "Why people use it",
+        "Nobody holds the whole secret alone. Lose a few shares and recovery still works. Choose t and n to fit personal or team risk.",
+    ),
+    (
+        "Mental model",
+        "Each share is one point on a curve; the secret is where the curve crosses zero. With t points there is exactly one curve that fits. With fewer, every secret is equally possible.",
+    ),
+    (
+        "How it works",
+        "1. Choose t (threshold) and n (number of shares). 2. Type the secret on the Encrypt tab and press Enter. 3. Hand one share to each custodian, on paper via the QR code or as text. 4. To recover, enter any t shares on the Decrypt tab.",
+    ),
+    (
+        "Good defaults",
+        "2-of-3 for a personal backup, 3-of-5 for a small team, 5-of-8 or 5-of-9 for organisation leadership.",
+    ),
+    (
+        "Where Reed-Solomon fits",
+        "Every share carries Reed-Solomon parity, so a few mistyped or smudged characters are repaired automatically; the Decrypt tab reports how many bytes were fixed.",
+    ),
+    (
+        "Keys",
+        "F1/F2/F3 switch tabs, Tab/Shift-Tab move between fields, Ctrl-R shows or hides secrets, Esc or Ctrl-Q quits and wipes everything from memory and the screen.",
+    ),
+];
+
+fn masked(text: &str, reveal: bool) -> String {
+    if reveal {
+        text.to_string()
+    } else {
+        "•".repeat(text.chars().count())
+    }
+}
+
+/// A one-line input box; the cursor is placed at its end when focused.
+fn input(frame: &mut Frame, area: Rect, title: &str, value: &str, focused: bool) {
+    let style = if focused {
+        Style::new().fg(Color::Yellow)
+    } else {
+        Style::new()
+    };
+    let inner_width = area.width.saturating_sub(2) as usize;
+    // Keep the end of long values (shares) in view.
+    let shown: String = {
+        let len = value.chars().count();
+        value
+            .chars()
+            .skip(len.saturating_sub(inner_width.saturating_sub(1)))
+            .collect()
+    };
+    let width = shown.chars().count() as u16;
+    frame.render_widget(
+        Paragraph::new(shown).block(Block::bordered().title(title).border_style(style)),
+        area,
+    );
+    if focused {
+        frame.set_cursor_position(Position::new(area.x + 1 + width, area.y + 1));
+    }
+}
+
+pub fn draw(frame: &mut Frame, app: &App) {
+    let [tabs, body, status] = Layout::vertical([
+        Constraint::Length(3),
+        Constraint::Min(0),
+        Constraint::Length(2),
+    ])
+    .areas(frame.area());
+
+    let selected = Tab::ALL.iter().position(|&t| t == app.tab).unwrap_or(0);
+    frame.render_widget(
+        Tabs::new(
+            Tab::ALL
+                .iter()
+                .enumerate()
+                .map(|(i, t)| format!("F{} {}", i + 1, t.title())),
+        )
+        .select(selected)
+        .highlight_style(Style::new().bold().reversed())
+        .block(Block::bordered().title(" Shamir's Secret Sharing ")),
+        tabs,
+    );
+
+    match app.tab {
+        Tab::Encrypt => draw_encrypt(frame, body, app),
+        Tab::Decrypt => draw_decrypt(frame, body, app),
+        Tab::Information => draw_information(frame, body, app),
+    }
+
+    let help = "Tab: next field · F1-F3: tabs · Ctrl-R: reveal · Esc: quit and wipe";
+    let mut lines = vec![Line::from(help).dim()];
+    if let Some(message) = &app.message {
+        lines.insert(0, Line::from(message.as_str()).yellow());
+    }
+    frame.render_widget(Paragraph::new(lines), status);
+}
+
+fn draw_parameters(frame: &mut Frame, area: Rect, app: &App, with_count: bool) {
+    let [t, n] = Layout::horizontal([Constraint::Length(20), Constraint::Length(20)]).areas(area);
+    input(
+        frame,
+        t,
+        "Threshold",
+        &app.threshold,
+        app.focus == Some(Field::Threshold),
+    );
+    if with_count {
+        input(
+            frame,
+            n,
+            "Number of Shares",
+            &app.share_count,
+            app.focus == Some(Field::ShareCount),
+        );
+    }
+}
+
+fn draw_encrypt(frame: &mut Frame, area: Rect, app: &App) {
+    let [left, right] =
+        Layout::horizontal([Constraint::Percentage(50), Constraint::Percentage(50)]).areas(area);
+    let [params, secret, list] = Layout::vertical([
+        Constraint::Length(3),
+        Constraint::Length(3),
+        Constraint::Min(0),
+    ])
+    .areas(left);
+
+    draw_parameters(frame, params, app, true);
+    input(
+        frame,
+        secret,
+        "Secret to Encrypt (Enter to split)",
+        &masked(&app.secret, app.reveal),
+        app.focus == Some(Field::Secret),
+    );
+
+    let focused = app.focus == Some(Field::Shares);
+    let items: Vec<ListItem> = app
+        .shares
+        .iter()
+        .enumerate()
+        .map(|(i, s)| ListItem::new(format!("Share {}: {}", i + 1, masked(s, app.reveal))))
+        .collect();
+    let mut state =
+        ListState::default().with_selected((!app.shares.is_empty()).then_some(app.selected));
+    frame.render_stateful_widget(
+        List::new(items)
+            .highlight_style(Style::new().add_modifier(Modifier::REVERSED))
+            .block(
+                Block::bordered()
+                    .title("Generated Shares (↑/↓ to preview)")
+                    .border_style(if focused {
+                        Style::new().fg(Color::Yellow)
+                    } else {
+                        Style::new()
+                    }),
+            ),
+        list,
+        &mut state,
+    );
+
+    let block = Block::bordered().title("QR preview");
+    let Some(share) = app.shares.get(app.selected) else {
+        frame.render_widget(
+            Paragraph::new("Generate shares to preview them here.").block(block),
+            right,
+        );
+        return;
+    };
+    let text = match qr_lines(share) {
+        Ok(lines) if app.reveal => Text::from(
+            lines
+                .into_iter()
+                .map(|l| {
+                    Line::from(Span::styled(
+                        l,
+                        Style::new().fg(Color::Black).bg(Color::White),
+                    ))
+                })
+                .collect::<Vec<_>>(),
+        ),
+        Ok(_) => Text::from("Press Ctrl-R to show the QR code for printing."),
+        Err(e) => Text::from(format!("Cannot draw a QR code: {e}")),
+    };
+    frame.render_widget(
+        Paragraph::new(text).block(block.title_bottom(format!(
+            " Share {} of {} ",
+            app.selected + 1,
+            app.shares.len()
+        ))),
+        right,
+    );
+}
+
+fn draw_decrypt(frame: &mut Frame, area: Rect, app: &App) {
+    let [params, entry, status, accepted, recovered] = Layout::vertical([
+        Constraint::Length(3),
+        Constraint::Length(3),
+        Constraint::Length(1),
+        Constraint::Min(3),
+        Constraint::Length(3),
+    ])
+    .areas(area);
+
+    draw_parameters(frame, params, app, false);
+    input(
+        frame,
+        entry,
+        "Share (Enter to add · Ctrl-U clear · Ctrl-D remove last)",
+        &masked(&app.entry, app.reveal),
+        app.focus == Some(Field::Entry),
+    );
+    let live = match app.entry_status() {
+        None => Line::from("Type or paste one share in any supported format.").dim(),
+        Some(Ok(note)) => Line::from(format!("✓ {note}")).green(),
+        Some(Err(e)) => Line::from(format!("✗ {e}")).red(),
+    };
+    frame.render_widget(Paragraph::new(live), status);
+
+    let title = match app.threshold() {
+        Some(t) => format!("{} of {t} collected", app.accepted.len()),
+        None => "Enter a valid threshold (>1)".to_string(),
+    };
+    let items: Vec<ListItem> = app
+        .accepted
+        .iter()
+        .map(|a| ListItem::new(format!("✓ {}", a.note)))
+        .collect();
+    frame.render_widget(
+        List::new(items).block(Block::bordered().title(title)),
+        accepted,
+    );
+
+    let secret = match &app.recovered {
+        Some(secret) => masked(&String::from_utf8_lossy(secret), app.reveal),
+        None => String::new(),
+    };
+    frame.render_widget(
+        Paragraph::new(secret)
+            .wrap(Wrap { trim: false })
+            .block(Block::bordered().title("Recovered Secret")),
+        recovered,
+    );
+}
+
+fn draw_information(frame: &mut Frame, area: Rect, app: &App) {
+    let mut lines = Vec::new();
+    for (heading, body) in INFORMATION {
+        lines.push(Line::from(*heading).bold());
+        lines.push(Line::from(*body));
+        lines.push(Line::default());
+    }
+    frame.render_widget(
+        Paragraph::new(lines)
+            .wrap(Wrap { trim: true })
+            .scroll((app.info_scroll, 0))
+            .block(Block::bordered().title("Shamir's Secret Sharing Guide (↑/↓ to scroll)")),
+        area,
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::Terminal;
+    use ratatui::backend::TestBackend;
+
+    #[test]
+    fn draws_every_tab_without_leaking_hidden_secrets() {
+        let mut app = App {
+            threshold: "2".into(),
+            share_count: "3".into(),
+            secret: zeroize::Zeroizing::new("hunter2".into()),
+            ..App::default()
+        };
+        app.generate();
+        let mut terminal = Terminal::new(TestBackend::new(120, 40)).unwrap();
+        let screen = |terminal: &mut Terminal<TestBackend>, app: &App| {
+            terminal.draw(|frame| draw(frame, app)).unwrap();
+            let buffer = terminal.backend().buffer();
+            buffer
+                .content()
+                .iter()
+                .map(|c| c.symbol())
+                .collect::<String>()
+        };
+
+        let hidden = screen(&mut terminal, &app);
+        assert!(!hidden.contains("hunter2"));
+        assert!(!hidden.contains(&app.shares[0][..8]));
+        assert!(hidden.contains("Press Ctrl-R"));
+
+        app.reveal = true;
+        let shown = screen(&mut terminal, &app);
+        assert!(shown.contains("hunter2"));
+        assert!(shown.contains('█'));
+
+        for tab in Tab::ALL {
+            app.tab = tab;
+            screen(&mut terminal, &app);
+        }
+        // Tiny terminals must not panic.
+        let mut small = Terminal::new(TestBackend::new(20, 6)).unwrap();
+        screen(&mut small, &app);
+    }
+}
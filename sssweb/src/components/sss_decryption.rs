@@ -2,7 +2,11 @@ use yew::UseStateHandle;
 use yew::prelude::*;
 use zeroize::Zeroizing;
 
-use shamir_gf256::{Share, reconstruct, share_from_hex};
+use shamir_gf256::sign::verifying_key_from_hex;
+use shamir_gf256::wrap::unwrap_share;
+use shamir_gf256::{
+    CorrectionReport, Share, parse_share_list, reconstruct, share_from_hex_verified_with_report,
+};
 
 #[derive(Properties, PartialEq)]
 pub struct DecryptionProps {
@@ -13,13 +17,22 @@ pub struct DecryptionProps {
 pub fn sss_decryption(props: &DecryptionProps) -> Html {
     let threshold = props.threshold as usize;
 
-    // Raw text pasted/typed by the user (each line = one share hex)
+    // Raw text pasted/typed by the user (one share per line, or armored blocks)
     let shares_text = use_state(String::new);
 
+    // Dealer verification key (hex); when set, every share must carry a valid signature
+    let verification_key = use_state(String::new);
+
+    // Passphrase for wrapped shares; the field appears once one is pasted
+    let passphrase = use_state(String::new);
+    let needs_passphrase = use_state(|| false);
+
     // Parsed shares and the reconstructed secret
     let parts: UseStateHandle<Vec<Share>> = use_state(Vec::new);
     let result: UseStateHandle<Zeroizing<Vec<u8>>> = use_state(|| Zeroizing::new(Vec::new()));
     let parse_errors: UseStateHandle<Vec<String>> = use_state(Vec::new);
+    // Per-share RS repair summaries, so degraded backups can be re-issued
+    let repair_notes: UseStateHandle<Vec<(String, bool)>> = use_state(Vec::new);
 
     // Keep text in sync with the textarea
     let oninput = {
@@ -30,29 +43,117 @@ pub fn sss_decryption(props: &DecryptionProps) -> Html {
         })
     };
 
+    let on_key_input = {
+        let verification_key = verification_key.clone();
+        Callback::from(move |e: InputEvent| {
+            let input: web_sys::HtmlInputElement = e.target_unchecked_into();
+            verification_key.set(input.value());
+        })
+    };
+
+    let on_passphrase_input = {
+        let passphrase = passphrase.clone();
+        Callback::from(move |e: InputEvent| {
+            let input: web_sys::HtmlInputElement = e.target_unchecked_into();
+            passphrase.set(input.value());
+        })
+    };
+
     // Clone state handles for the onclick closure
     let shares_text_closure = shares_text.clone();
+    let key_closure = verification_key.clone();
+    let passphrase_closure = passphrase.clone();
+    let needs_passphrase_closure = needs_passphrase.clone();
     let parts_closure = parts.clone();
     let result_closure = result.clone();
     let errors_closure = parse_errors.clone();
+    let notes_closure = repair_notes.clone();
 
     let onclick = Callback::from(move |_| {
-        // Parse: one hex-encoded share per line (from `share_to_hex`)
+        // Parse: one share per line in any supported format (or armored
+        // blocks); in hex and words, `?` marks an unreadable digit or word and is
+        // decoded as an RS erasure.
         let mut parsed: Vec<Share> = Vec::new();
         let mut errors: Vec<String> = Vec::new();
+        let mut notes: Vec<(String, bool)> = Vec::new();
+        let mut wrapped_seen = false;
 
-        for (idx, line) in shares_text_closure.lines().enumerate() {
-            let l = line.trim();
-            if l.is_empty() {
-                continue;
+        let key_text = key_closure.trim();
+        if key_text.is_empty() {
+            for (line_no, outcome) in parse_share_list(&shares_text_closure) {
+                match outcome {
+                    Ok(p) => {
+                        let health = p
+                            .report
+                            .as_ref()
+                            .map_or_else(|| "no error correction".to_string(), health_note);
+                        let repaired = p.report.as_ref().is_some_and(|r| r.corrected() > 0);
+                        notes.push((
+                            format!("Share {} ({}): {}", p.share.x.0, p.format, health),
+                            repaired,
+                        ));
+                        parsed.push(p.share);
+                    }
+                    Err(e) if e.needs_passphrase() => {
+                        wrapped_seen = true;
+                        if passphrase_closure.is_empty() {
+                            errors.push(format!(
+                                "Line {line_no}: share is passphrase-protected; enter its passphrase below"
+                            ));
+                            continue;
+                        }
+                        let line = shares_text_closure.lines().nth(line_no - 1).unwrap_or("");
+                        match unwrap_share(line, &passphrase_closure) {
+                            Ok((sh, report)) => {
+                                notes.push((
+                                    format!(
+                                        "Share {} (passphrase): {}",
+                                        sh.x.0,
+                                        health_note(&report)
+                                    ),
+                                    report.corrected() > 0,
+                                ));
+                                parsed.push(sh);
+                            }
+                            Err(e) => errors.push(format!("Line {line_no}: {e}")),
+                        }
+                    }
+                    Err(e) => errors.push(format!("Line {line_no}: {e}")),
+                }
             }
-            match share_from_hex(l) {
-                Ok(sh) => parsed.push(sh),
-                Err(e) => errors.push(format!("Line {}: {}", idx + 1, e)),
+        } else {
+            // Signed shares are hex only; a bad signature rejects the share
+            // before it can reach interpolation.
+            match verifying_key_from_hex(key_text) {
+                Ok(key) => {
+                    for (idx, line) in shares_text_closure.lines().enumerate() {
+                        let l = line.trim();
+                        if l.is_empty() {
+                            continue;
+                        }
+                        match share_from_hex_verified_with_report(l, &key) {
+                            Ok((sh, report)) => {
+                                notes.push((
+                                    format!(
+                                        "Share {}: signature valid, {}",
+                                        sh.x.0,
+                                        health_note(&report)
+                                    ),
+                                    report.corrected() > 0,
+                                ));
+                                parsed.push(sh);
+                            }
+                            Err(e) => errors.push(format!("Line {}: {}", idx + 1, e)),
+                        }
+                    }
+                }
+                Err(e) => errors.push(format!("Verification key: {e}")),
             }
         }
 
+        needs_passphrase_closure.set(wrapped_seen);
         errors_closure.set(errors);
+        notes_closure.set(notes);
         parts_closure.set(parsed.clone());
 
         if parsed.len() >= threshold && threshold > 0 {
@@ -71,19 +172,47 @@ pub fn sss_decryption(props: &DecryptionProps) -> Html {
                 </div>
 
                 <div class="form-group">
-                    <label class="form-label" for="shares-input">{"Paste Shares (one hex blob per line)"}</label>
+                    <label class="form-label" for="shares-input">{"Paste Shares (one per line: hex, base32, base58, base64url, bech32m, words, JSON, Vault, ssss, or armored blocks)"}</label>
+                    <p class="text-secondary">{"The format of each share is detected automatically. In hex or word shares, type ? for any hex digit or word you cannot read; marked positions are repaired as erasures."}</p>
                     <textarea
                         class="form-input"
                         id="shares-input"
                         placeholder="Example (per line):
 53485231012a00000068656c6c6f
-53485231ff0a000000736f6d657061796c6f6164"
+53485231ff0a000000736f6d65706??96c6f6164"
                         rows="9"
                         value={(*shares_text).clone()}
                         oninput={oninput}
                     />
                 </div>
 
+                if *needs_passphrase {
+                    <div class="form-group">
+                        <label class="form-label" for="passphrase-input">{"Share Passphrase"}</label>
+                        <p class="text-secondary">{"Some shares are passphrase-protected. Unwrapping takes a moment (Argon2id)."}</p>
+                        <input
+                            class="form-input"
+                            id="passphrase-input"
+                            type="password"
+                            autocomplete="off"
+                            value={(*passphrase).clone()}
+                            oninput={on_passphrase_input}
+                        />
+                    </div>
+                }
+
+                <div class="form-group">
+                    <label class="form-label" for="verification-key-input">{"Dealer Verification Key (optional)"}</label>
+                    <p class="text-secondary">{"When set, only hex shares signed by this dealer are accepted."}</p>
+                    <input
+                        class="form-input"
+                        id="verification-key-input"
+                        placeholder="64 hex digits"
+                        value={(*verification_key).clone()}
+                        oninput={on_key_input}
+                    />
+                </div>
+
                 <div class="form-group">
                     <p class="text-secondary">{ format!("Threshold required: {}", threshold) }</p>
                     <button class="btn btn-primary" {onclick}>
@@ -106,6 +235,25 @@ pub fn sss_decryption(props: &DecryptionProps) -> Html {
                     }
                 }
 
+                {
+                    if !repair_notes.is_empty() {
+                        html! {
+                            <div class={if repair_notes.iter().any(|(_, repaired)| *repaired) {
+                                "status-message status-warning"
+                            } else {
+                                "status-message status-success"
+                            }}>
+                                <h4>{"Share Health"}</h4>
+                                <ul>
+                                    { for repair_notes.iter().map(|(n, _)| html!{ <li>{ n }</li> }) }
+                                </ul>
+                            </div>
+                        }
+                    } else {
+                        html! {}
+                    }
+                }
+
                 {
                     if !result.is_empty() {
                         html! {
@@ -140,3 +288,13 @@ pub fn sss_decryption(props: &DecryptionProps) -> Html {
             </div>
         }
 }
+
+/// "intact, 8 more correctable" / "2 bytes repaired, 6 more correctable"
+fn health_note(report: &CorrectionReport) -> String {
+    let repaired = match report.corrected() {
+        0 => "intact".to_string(),
+        1 => "1 byte repaired".to_string(),
+        n => format!("{n} bytes repaired"),
+    };
+    format!("{}, {} more correctable", repaired, report.remaining)
+}
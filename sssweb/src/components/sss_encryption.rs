@@ -1,4 +1,6 @@
 use super::copy_button::CopyButton;
+use shamir_gf256::seal::{parse_recipients, seal_text};
+use shamir_gf256::sign::{SigningKey, verifying_key_to_hex};
 use yew::prelude::*;
 use zeroize::Zeroizing;
 const ECC_LEN: usize = 16;
@@ -12,12 +14,23 @@ pub struct EncryptionProps {
 #[function_component(Encryption)]
 pub fn sss_encryption(props: &EncryptionProps) -> Html {
     let secret = use_state(|| "A".to_string());
-    let parts = use_state(Vec::new);
+    // Encoded shares (hex, signed when requested, sealed when recipients are given)
+    let parts: UseStateHandle<Vec<Zeroizing<String>>> = use_state(Vec::new);
+    // Custodians' age public keys, one per line; share i is sealed to line i
+    let recipients = use_state(String::new);
+    let error: UseStateHandle<Option<String>> = use_state(|| None);
+    let sign = use_state(|| false);
+    // Verification key of the last signed set; the signing key is dropped after the split
+    let verification_key: UseStateHandle<Option<String>> = use_state(|| None);
     let threshold = props.threshold as usize;
     let share_count = props.share_count as usize;
 
     let secret_closure = secret.clone();
     let parts_closure = parts.clone();
+    let sign_closure = sign.clone();
+    let key_closure = verification_key.clone();
+    let recipients_closure = recipients.clone();
+    let error_closure = error.clone();
 
     let secret_input = secret.clone();
 
@@ -29,17 +42,90 @@ pub fn sss_encryption(props: &EncryptionProps) -> Html {
         })
     };
 
+    let on_recipients = {
+        let recipients = recipients.clone();
+        Callback::from(move |e: InputEvent| {
+            let input: web_sys::HtmlTextAreaElement = e.target_unchecked_into();
+            recipients.set(input.value());
+        })
+    };
+
+    let on_sign = {
+        let sign = sign.clone();
+        Callback::from(move |e: Event| {
+            let input: web_sys::HtmlInputElement = e.target_unchecked_into();
+            sign.set(input.checked());
+        })
+    };
+
     let onclick = Callback::from(move |_| {
+        // Parse recipients first so a typo never leaves plaintext shares on screen
+        let recipient_keys = match parse_recipients(&recipients_closure) {
+            Ok(keys) if keys.is_empty() || keys.len() == share_count => keys,
+            Ok(keys) => {
+                error_closure.set(Some(format!(
+                    "Enter one recipient key per share: {} needed, {} given.",
+                    share_count,
+                    keys.len()
+                )));
+                return;
+            }
+            Err(e) => {
+                error_closure.set(Some(format!("Recipients: {e}")));
+                return;
+            }
+        };
+
         let secret_bytes = Zeroizing::new(secret_closure.as_bytes().to_vec());
-        let parts_new = shamir_gf256::split(&secret_bytes, threshold, share_count);
-        parts_closure.set(parts_new);
+        let shares = shamir_gf256::split(&secret_bytes, threshold, share_count);
+        let texts: Vec<Zeroizing<String>> = if *sign_closure {
+            // One fresh dealer key per share set
+            let key = SigningKey::generate(&mut rand::thread_rng());
+            key_closure.set(Some(verifying_key_to_hex(&key.verifying_key())));
+            shares
+                .iter()
+                .map(|share| {
+                    Zeroizing::new(shamir_gf256::share_to_hex_signed(share, ECC_LEN, &key))
+                })
+                .collect()
+        } else {
+            key_closure.set(None);
+            shares
+                .iter()
+                .map(|share| Zeroizing::new(shamir_gf256::share_to_hex(share, ECC_LEN)))
+                .collect()
+        };
+
+        if recipient_keys.is_empty() {
+            parts_closure.set(texts);
+            error_closure.set(None);
+            return;
+        }
+        match texts
+            .iter()
+            .zip(&recipient_keys)
+            .map(|(text, recipient)| seal_text(text, recipient).map(Zeroizing::new))
+            .collect::<Result<Vec<_>, _>>()
+        {
+            Ok(sealed) => {
+                parts_closure.set(sealed);
+                error_closure.set(None);
+            }
+            Err(e) => {
+                parts_closure.set(Vec::new());
+                error_closure.set(Some(e.to_string()));
+            }
+        }
     });
 
-    let copy_all_text: String = parts
-        .iter()
-        .map(|share| shamir_gf256::share_to_hex(share, ECC_LEN))
-        .collect::<Vec<_>>()
-        .join("\n");
+    let copy_all_text = Zeroizing::new(
+        parts
+            .iter()
+            .map(|p| p.as_str())
+            .collect::<Vec<_>>()
+            .join("\n"),
+    );
+    let sealed = parts.iter().any(|p| p.contains('\n'));
 
     html! {
         <div class="card">
@@ -59,26 +145,62 @@ pub fn sss_encryption(props: &EncryptionProps) -> Html {
                 />
             </div>
 
+            <div class="form-group">
+                <label class="form-label" for="recipients-input">{"Custodian Keys (optional)"}</label>
+                <p class="text-secondary">{"One age public key (age1…) per line; share i is encrypted to key i so only that custodian can open it."}</p>
+                <textarea
+                    class="form-input"
+                    id="recipients-input"
+                    placeholder="age1ql3z7hjy54pw3hyww5ayyfg7zqgvc7w3j2elw8zmrj2kg5sfn9aqmcac8p"
+                    rows="3"
+                    value={(*recipients).clone()}
+                    oninput={on_recipients}
+                />
+            </div>
+
+            <div class="form-group">
+                <label class="form-label">
+                    <input type="checkbox" checked={*sign} onchange={on_sign} />
+                    { " Sign shares with a fresh dealer key (Ed25519)" }
+                </label>
+            </div>
+
             <div class="form-group">
                 <button class="btn btn-primary" {onclick}>
                     {"Generate Shares"}
                 </button>
             </div>
 
+            { error.as_ref().map(|e| html! {
+                <div class="status-message status-error">{ e.clone() }</div>
+            }) }
+
             { (!parts.is_empty()).then(|| html! {
                 <div class="card">
                     <div class="card-header">
                         <h3 class="card-title">{"Generated Shares"}</h3>
                     </div>
+                    { verification_key.as_ref().map(|vk| html! {
+                        <div class="form-group">
+                            <label class="form-label">{"Dealer Verification Key (publish with the set)"}</label>
+                            <code class="result-display">{ vk.clone() }</code>
+                            <CopyButton text={vk.clone()} />
+                        </div>
+                    }) }
                     <div class="form-group">
-                        <CopyButton text={copy_all_text.clone()} />
+                        <CopyButton text={copy_all_text.to_string()} />
                     </div>
                     <div class="form-group">
                         {
-                            for copy_all_text.lines().enumerate().map(|(i, line)| html! {
+                            for parts.iter().enumerate().map(|(i, part)| html! {
                                 <div class="share-item">
                                     <p class="share-label">{ format!("Share {}:", i + 1)}</p>
-                                    <code class="result-display">{ format!("{line}") }</code>
+                                    if sealed {
+                                        <pre class="result-display">{ part.to_string() }</pre>
+                                        <CopyButton text={part.to_string()} />
+                                    } else {
+                                        <code class="result-display">{ part.to_string() }</code>
+                                    }
                                 </div>
                             })
                         }